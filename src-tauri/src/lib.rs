@@ -4,11 +4,48 @@ mod modules; // 声明 modules 目录为一个模块
 
 use std::sync::Arc;
 
+use num_bigint::BigUint;
+
 // 导入模块
+use modules::crypto::asn1::asn1_parse;
+use modules::crypto::base64_alphabet::{custom_base64_decode, custom_base64_encode};
+use modules::crypto::batch_gcd::batch_gcd_attack;
 use modules::crypto::big_rsa::solve_multi_layer_rsa;
 use modules::crypto::caesar::{caesar_crack, caesar_transform};
-use modules::crypto::common_modulus::{parse_biguint, recover_plaintext};
+use modules::crypto::codec::{convert_number, parse_biguint, render_number, NumberView};
+use modules::crypto::common_modulus::{common_modulus_attack_multi, recover_plaintext};
+use modules::crypto::coppersmith::coppersmith_attack;
+use modules::crypto::dlog::discrete_log;
+use modules::crypto::cbc_attack::{cbc_bitflip, padding_oracle_attack};
+use modules::crypto::ecb_attack::ecb_oracle_attack;
+use modules::crypto::ecc::{ecc_discrete_log, ecc_point_add, ecc_scalar_mul};
+use modules::crypto::lsb_oracle::lsb_oracle_attack;
+use modules::crypto::hastad::hastad_broadcast_attack;
+use modules::crypto::key_import::import_rsa_key;
+use modules::crypto::knapsack::knapsack_attack;
+use modules::crypto::language::{language_register, language_score};
+use modules::crypto::number_theory::{chinese_remainder, integer_root};
+use modules::crypto::factor::{factorize_rsa, rsa_decrypt_with_factors};
+use modules::crypto::franklin_reiter::franklin_reiter_attack;
+use modules::crypto::task::{cancel_crypto_task, CryptoTasks};
+use modules::crypto::tea::{tea_decrypt, tea_encrypt};
+use modules::crypto::public_key::{
+    elgamal_decrypt, elgamal_encrypt, elgamal_generate_key, paillier_add, paillier_decrypt,
+    paillier_encrypt, paillier_generate_key, paillier_scalar_mul, rabin_encrypt, rabin_generate_key,
+};
+use modules::crypto::rc4::{rc4_decrypt, rc4_encrypt};
 use modules::crypto::replacer::batch_replace;
+use modules::crypto::rsa_solver::rsa_solve;
+use modules::crypto::sm2::{
+    sm2_decrypt, sm2_encrypt, sm2_generate_key, sm2_import_key, sm2_sign, sm2_verify,
+};
+use modules::crypto::sm3::sm3_hash;
+use modules::crypto::symmetric::{symmetric_decrypt, symmetric_encrypt};
+use modules::crypto::modular_root::{rabin_decrypt, rsa_nth_root_decrypt};
+use modules::crypto::lcg::lcg_recover;
+use modules::crypto::lfsr::{lfsr_analyze, lfsr_combiner_attack};
+use modules::crypto::mt19937::mt19937_recover;
+use modules::crypto::wiener::small_private_exponent_attack;
 use modules::crypto::word_freq::analyze_text_advanced;
 use modules::encode_decode::vigenere::{crack_vigenere_auto, vigenere_cipher};
 use modules::images::image_structure_analyzer::{analyze_image_header, get_supported_templates};
@@ -30,7 +67,7 @@ fn common_modulus_attack(
     c1: String,
     e2: String,
     c2: String,
-) -> Result<String, String> {
+) -> Result<NumberView, String> {
     let n = parse_biguint(&n)?;
     let e1 = parse_biguint(&e1)?;
     let c1 = parse_biguint(&c1)?;
@@ -39,23 +76,7 @@ fn common_modulus_attack(
 
     let plaintext_bytes = recover_plaintext(&n, &e1, &c1, &e2, &c2)?;
 
-    match String::from_utf8(plaintext_bytes.clone()) {
-        Ok(s)
-            if !s.is_empty()
-                && s.chars()
-                    .all(|c| c.is_ascii_control() || c.is_ascii_graphic()) =>
-        {
-            Ok(s)
-        }
-        _ => {
-            let hex = plaintext_bytes
-                .iter()
-                .map(|b| format!("{:02x}", b))
-                .collect::<Vec<_>>()
-                .join("");
-            Ok(format!("[非文本数据] Hex: {}", hex))
-        }
-    }
+    Ok(render_number(&BigUint::from_bytes_be(&plaintext_bytes)))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -67,16 +88,74 @@ pub fn run() {
         .manage(FfmpegProcess(
             Arc::new(tokio::sync::Mutex::new(None))
         ))
+        .manage(CryptoTasks::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             analyze_text_advanced,
             common_modulus_attack,
+            common_modulus_attack_multi,
             solve_multi_layer_rsa,
+            batch_gcd_attack,
+            small_private_exponent_attack,
+            hastad_broadcast_attack,
+            chinese_remainder,
+            integer_root,
+            convert_number,
+            factorize_rsa,
+            rsa_decrypt_with_factors,
+            rsa_solve,
+            rsa_nth_root_decrypt,
+            rabin_decrypt,
+            rabin_generate_key,
+            rabin_encrypt,
+            paillier_generate_key,
+            paillier_encrypt,
+            paillier_decrypt,
+            paillier_add,
+            paillier_scalar_mul,
+            elgamal_generate_key,
+            elgamal_encrypt,
+            elgamal_decrypt,
+            coppersmith_attack,
+            franklin_reiter_attack,
+            discrete_log,
+            ecc_point_add,
+            ecc_scalar_mul,
+            ecc_discrete_log,
+            lsb_oracle_attack,
+            padding_oracle_attack,
+            cbc_bitflip,
+            ecb_oracle_attack,
+            symmetric_encrypt,
+            symmetric_decrypt,
+            tea_encrypt,
+            tea_decrypt,
+            rc4_encrypt,
+            rc4_decrypt,
+            custom_base64_encode,
+            custom_base64_decode,
+            sm3_hash,
+            sm2_generate_key,
+            sm2_import_key,
+            sm2_sign,
+            sm2_verify,
+            sm2_encrypt,
+            sm2_decrypt,
+            mt19937_recover,
+            lcg_recover,
+            lfsr_analyze,
+            lfsr_combiner_attack,
+            cancel_crypto_task,
+            import_rsa_key,
+            knapsack_attack,
+            asn1_parse,
             batch_replace,
             caesar_transform,
             caesar_crack,
             vigenere_cipher,
             crack_vigenere_auto,
+            language_score,
+            language_register,
             generate_mirage_tank,
             analyze_image_header,
             get_supported_templates,
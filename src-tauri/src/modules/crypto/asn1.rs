@@ -0,0 +1,477 @@
+use base64::{engine::general_purpose, Engine as _};
+use num_bigint::{BigInt, BigUint};
+use serde::Serialize;
+
+const MAX_DEPTH: usize = 64;
+
+pub const TAG_BOOLEAN: u32 = 1;
+pub const TAG_INTEGER: u32 = 2;
+pub const TAG_BIT_STRING: u32 = 3;
+pub const TAG_OCTET_STRING: u32 = 4;
+pub const TAG_NULL: u32 = 5;
+pub const TAG_OID: u32 = 6;
+pub const TAG_SEQUENCE: u32 = 16;
+
+pub const OID_RSA_ENCRYPTION: &str = "1.2.840.113549.1.1.1";
+
+// ===================== 数据结构 =====================
+
+/// 解析后的 ASN.1 节点, content 借用原始数据
+#[derive(Debug, Clone)]
+pub struct Asn1<'a> {
+    pub class: u8, // 0 universal, 1 application, 2 context-specific, 3 private
+    pub constructed: bool,
+    pub tag: u32,
+    pub offset: usize,
+    pub header_len: usize,
+    pub content: &'a [u8],
+    pub children: Vec<Asn1<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Asn1TreeNode {
+    pub offset: usize,
+    pub header_len: usize,
+    pub length: usize,
+    pub tag: String,
+    pub value: Option<String>,
+    pub children: Vec<Asn1TreeNode>,
+}
+
+// ===================== Commands =====================
+
+/// 以树形结构展示任意 DER/BER 数据 (PEM、Hex、Base64 文本或文件原始字节)
+#[tauri::command]
+pub fn asn1_parse(
+    content: Option<String>,
+    raw: Option<Vec<u8>>,
+) -> Result<Vec<Asn1TreeNode>, String> {
+    let der = decode_der_input(content, raw)?;
+    let nodes = parse_der(&der)?;
+    Ok(nodes.iter().map(tree_node).collect())
+}
+
+// ===================== 输入解码 =====================
+
+/// 解析文本中的第一个 PEM 块, 返回 (标签, DER 字节); 文本中没有 PEM 时返回 None
+pub fn pem_decode(text: &str) -> Result<Option<(String, Vec<u8>)>, String> {
+    let Some(start) = text.find("-----BEGIN ") else {
+        return Ok(None);
+    };
+    let rest = &text[start + 11..];
+    let label_end = rest.find("-----").ok_or("PEM 头部格式错误")?;
+    let label = rest[..label_end].to_string();
+    let body = &rest[label_end + 5..];
+    let end_marker = format!("-----END {}-----", label);
+    let body_end = body
+        .find(&end_marker)
+        .ok_or_else(|| format!("缺少 PEM 结束标记: {}", end_marker))?;
+
+    let mut b64 = String::new();
+    for line in body[..body_end].lines() {
+        let line = line.trim();
+        // RFC 1421 头部, 例如 Proc-Type: 4,ENCRYPTED
+        if line.contains(':') {
+            if line.contains("ENCRYPTED") {
+                return Err("PEM 已加密, 请先用口令解密".into());
+            }
+            continue;
+        }
+        b64.push_str(line);
+    }
+    let der = general_purpose::STANDARD
+        .decode(b64.as_bytes())
+        .map_err(|e| format!("PEM Base64 解码失败: {}", e))?;
+    Ok(Some((label, der)))
+}
+
+/// 统一处理命令输入: 优先使用原始字节, 否则把文本按 PEM → Hex → Base64 依次尝试
+pub fn decode_der_input(content: Option<String>, raw: Option<Vec<u8>>) -> Result<Vec<u8>, String> {
+    if let Some(bytes) = raw.filter(|b| !b.is_empty()) {
+        // 文件内容本身可能就是 PEM 文本
+        if let Ok(text) = std::str::from_utf8(&bytes) {
+            if let Some((_, der)) = pem_decode(text)? {
+                return Ok(der);
+            }
+        }
+        return Ok(bytes);
+    }
+
+    let text = content.unwrap_or_default();
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("输入为空".into());
+    }
+    if let Some((_, der)) = pem_decode(text)? {
+        return Ok(der);
+    }
+    let compact: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':')
+        .collect();
+    if compact.len().is_multiple_of(2) && compact.chars().all(|c| c.is_ascii_hexdigit()) {
+        return hex::decode(&compact).map_err(|e| e.to_string());
+    }
+    general_purpose::STANDARD
+        .decode(compact.as_bytes())
+        .map_err(|_| "无法识别的输入 (需要 PEM / Hex / Base64)".into())
+}
+
+// ===================== DER / BER 解析 =====================
+
+/// 解析连续的若干个顶层 TLV
+pub fn parse_der(data: &[u8]) -> Result<Vec<Asn1<'_>>, String> {
+    let mut nodes = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let (node, next) = parse_node(data, pos, 0)?;
+        nodes.push(node);
+        pos = next;
+    }
+    if nodes.is_empty() {
+        return Err("数据为空".into());
+    }
+    Ok(nodes)
+}
+
+fn parse_node(data: &[u8], start: usize, depth: usize) -> Result<(Asn1<'_>, usize), String> {
+    if depth > MAX_DEPTH {
+        return Err("ASN.1 嵌套层数过深".into());
+    }
+    let truncated = || format!("偏移 {} 处数据被截断", start);
+    let mut pos = start;
+
+    let first = *data.get(pos).ok_or_else(truncated)?;
+    pos += 1;
+    let class = first >> 6;
+    let constructed = first & 0x20 != 0;
+    let mut tag = (first & 0x1f) as u32;
+    if tag == 0x1f {
+        // 高位标签号, base-128 编码
+        tag = 0;
+        loop {
+            let b = *data.get(pos).ok_or_else(truncated)?;
+            pos += 1;
+            if tag > (u32::MAX >> 7) {
+                return Err(format!("偏移 {} 处标签号过大", start));
+            }
+            tag = (tag << 7) | (b & 0x7f) as u32;
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+
+    let len_byte = *data.get(pos).ok_or_else(truncated)?;
+    pos += 1;
+    let length = if len_byte == 0x80 {
+        None // BER 不定长, 以 00 00 结束
+    } else if len_byte & 0x80 != 0 {
+        let count = (len_byte & 0x7f) as usize;
+        if count > 8 {
+            return Err(format!("偏移 {} 处长度字段过长", start));
+        }
+        let bytes = data.get(pos..pos + count).ok_or_else(truncated)?;
+        pos += count;
+        let len = bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+        Some(usize::try_from(len).map_err(|_| truncated())?)
+    } else {
+        Some(len_byte as usize)
+    };
+    let header_len = pos - start;
+
+    let (content, children, next) = match length {
+        Some(len) => {
+            let end = pos.checked_add(len).ok_or_else(truncated)?;
+            let content = data.get(pos..end).ok_or_else(truncated)?;
+            let mut children = Vec::new();
+            if constructed {
+                let mut child_pos = pos;
+                while child_pos < end {
+                    let (child, next) = parse_node(&data[..end], child_pos, depth + 1)?;
+                    children.push(child);
+                    child_pos = next;
+                }
+            }
+            (content, children, end)
+        }
+        None => {
+            if !constructed {
+                return Err(format!("偏移 {} 处的基本类型不能使用不定长编码", start));
+            }
+            let mut children = Vec::new();
+            let mut child_pos = pos;
+            loop {
+                match data.get(child_pos..child_pos + 2) {
+                    Some([0, 0]) => break,
+                    Some(_) => {
+                        let (child, next) = parse_node(data, child_pos, depth + 1)?;
+                        children.push(child);
+                        child_pos = next;
+                    }
+                    None => return Err(truncated()),
+                }
+            }
+            (&data[pos..child_pos], children, child_pos + 2)
+        }
+    };
+
+    Ok((
+        Asn1 {
+            class,
+            constructed,
+            tag,
+            offset: start,
+            header_len,
+            content,
+            children,
+        },
+        next,
+    ))
+}
+
+impl<'a> Asn1<'a> {
+    pub fn is(&self, tag: u32) -> bool {
+        self.class == 0 && self.tag == tag
+    }
+
+    pub fn as_biguint(&self) -> Option<BigUint> {
+        if !self.is(TAG_INTEGER) || self.content.is_empty() {
+            return None;
+        }
+        Some(BigUint::from_bytes_be(self.content))
+    }
+
+    pub fn as_oid(&self) -> Option<String> {
+        if !self.is(TAG_OID) || self.content.is_empty() {
+            return None;
+        }
+        let mut arcs: Vec<u64> = Vec::new();
+        let mut value = 0u64;
+        for &b in self.content {
+            value = value.checked_mul(128)? | (b & 0x7f) as u64;
+            if b & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first = (value / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(value - first * 40);
+                } else {
+                    arcs.push(value);
+                }
+                value = 0;
+            }
+        }
+        Some(
+            arcs.iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+                .join("."),
+        )
+    }
+
+    /// BIT STRING / OCTET STRING 中封装的 DER (如 SPKI 里的公钥、PKCS#8 里的私钥)
+    pub fn encapsulated(&self) -> Option<Vec<Asn1<'a>>> {
+        let inner = if self.is(TAG_BIT_STRING) {
+            match self.content.split_first() {
+                Some((0, rest)) => rest,
+                _ => return None,
+            }
+        } else if self.is(TAG_OCTET_STRING) {
+            self.content
+        } else {
+            return None;
+        };
+        let nodes = parse_der(inner).ok()?;
+        // 只接受完整解析且以构造类型开头的内容, 避免把随机字节误认成 DER
+        if nodes.len() == 1 && nodes[0].constructed {
+            Some(nodes)
+        } else {
+            None
+        }
+    }
+}
+
+// ===================== 树形展示 =====================
+
+fn tag_name(node: &Asn1) -> String {
+    match node.class {
+        0 => match node.tag {
+            1 => "BOOLEAN".into(),
+            2 => "INTEGER".into(),
+            3 => "BIT STRING".into(),
+            4 => "OCTET STRING".into(),
+            5 => "NULL".into(),
+            6 => "OBJECT IDENTIFIER".into(),
+            10 => "ENUMERATED".into(),
+            12 => "UTF8String".into(),
+            16 => "SEQUENCE".into(),
+            17 => "SET".into(),
+            19 => "PrintableString".into(),
+            20 => "T61String".into(),
+            22 => "IA5String".into(),
+            23 => "UTCTime".into(),
+            24 => "GeneralizedTime".into(),
+            30 => "BMPString".into(),
+            n => format!("UNIVERSAL {}", n),
+        },
+        1 => format!("[APPLICATION {}]", node.tag),
+        2 => format!("[{}]", node.tag),
+        _ => format!("[PRIVATE {}]", node.tag),
+    }
+}
+
+fn oid_name(oid: &str) -> Option<&'static str> {
+    Some(match oid {
+        "1.2.840.113549.1.1.1" => "rsaEncryption",
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption",
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption",
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption",
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption",
+        "1.2.840.113549.1.5.13" => "pbes2",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        "1.2.840.10045.2.1" => "ecPublicKey",
+        "1.2.840.10045.3.1.7" => "prime256v1",
+        "1.2.840.10045.4.3.2" => "ecdsaWithSHA256",
+        "1.3.132.0.10" => "secp256k1",
+        "1.3.132.0.34" => "secp384r1",
+        "1.2.156.10197.1.301" => "sm2",
+        "1.3.101.112" => "Ed25519",
+        "2.5.4.3" => "commonName",
+        "2.5.4.6" => "countryName",
+        "2.5.4.7" => "localityName",
+        "2.5.4.8" => "stateOrProvinceName",
+        "2.5.4.10" => "organizationName",
+        "2.5.4.11" => "organizationalUnitName",
+        "2.5.29.14" => "subjectKeyIdentifier",
+        "2.5.29.15" => "keyUsage",
+        "2.5.29.17" => "subjectAltName",
+        "2.5.29.19" => "basicConstraints",
+        "2.5.29.35" => "authorityKeyIdentifier",
+        _ => return None,
+    })
+}
+
+fn tree_node(node: &Asn1) -> Asn1TreeNode {
+    let mut children: Vec<Asn1TreeNode> = node.children.iter().map(tree_node).collect();
+    let mut value = None;
+
+    if !node.constructed {
+        if let Some(inner) = node.encapsulated() {
+            children = inner.iter().map(tree_node).collect();
+        } else {
+            value = primitive_value(node);
+        }
+    }
+
+    Asn1TreeNode {
+        offset: node.offset,
+        header_len: node.header_len,
+        length: node.content.len(),
+        tag: tag_name(node),
+        value,
+        children,
+    }
+}
+
+fn primitive_value(node: &Asn1) -> Option<String> {
+    let content = node.content;
+    if node.class != 0 {
+        return Some(hex::encode(content));
+    }
+    match node.tag {
+        TAG_BOOLEAN => Some((content.first().copied().unwrap_or(0) != 0).to_string()),
+        TAG_INTEGER | 10 => Some(BigInt::from_signed_bytes_be(content).to_string()),
+        TAG_BIT_STRING => content
+            .split_first()
+            .map(|(unused, bits)| format!("(unused {}) {}", unused, hex::encode(bits))),
+        TAG_NULL => None,
+        TAG_OID => node.as_oid().map(|oid| match oid_name(&oid) {
+            Some(name) => format!("{} ({})", oid, name),
+            None => oid,
+        }),
+        12 | 19 | 20 | 22 | 23 | 24 => Some(String::from_utf8_lossy(content).into_owned()),
+        30 => {
+            let units: Vec<u16> = content
+                .chunks(2)
+                .map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        }
+        _ => Some(hex::encode(content)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBfTCCASegAwIBAgIUQs0l9VxDc85046Aj5j3kFcgno9cwDQYJKoZIhvcNAQEL
+BQAwEzERMA8GA1UEAwwIY3RmLnRlc3QwHhcNMjYxMDE3MTM1OTA4WhcNMzYxMDE0
+MTM1OTA4WjATMREwDwYDVQQDDAhjdGYudGVzdDBcMA0GCSqGSIb3DQEBAQUAA0sA
+MEgCQQC8Emcpv1CqH1LmitevmafwuQVlSO2oNuH5/FMcRn+uD7E8c51IqbXqpwXt
+Kvq+hYvbVwyDcrlNXDFE8fHtsTXrAgMBAAGjUzBRMB0GA1UdDgQWBBQ58sS1b3R0
+n5/Cv2FwzfqVwMROejAfBgNVHSMEGDAWgBQ58sS1b3R0n5/Cv2FwzfqVwMROejAP
+BgNVHRMBAf8EBTADAQH/MA0GCSqGSIb3DQEBCwUAA0EAhJaRNzs0KDJvpCDNwJ9r
+zKSSRPuaDnCBp3iAalpSwfOCyHN6NXDiUJfJ1pT3ludzhJXYyjeJF3+6xpug71hB
+Qw==
+-----END CERTIFICATE-----";
+
+    fn find<'t>(
+        node: &'t Asn1TreeNode,
+        pred: &dyn Fn(&Asn1TreeNode) -> bool,
+    ) -> Option<&'t Asn1TreeNode> {
+        if pred(node) {
+            return Some(node);
+        }
+        node.children.iter().find_map(|c| find(c, pred))
+    }
+
+    #[test]
+    fn test_certificate_tree() {
+        let tree = asn1_parse(Some(CERT_PEM.into()), None).unwrap();
+        assert_eq!(tree.len(), 1);
+        let root = &tree[0];
+        assert_eq!(root.tag, "SEQUENCE");
+        assert_eq!(root.children.len(), 3);
+
+        let cn = find(root, &|n| n.value.as_deref() == Some("ctf.test")).unwrap();
+        assert_eq!(cn.tag, "UTF8String");
+        assert!(find(root, &|n| n.value.as_deref()
+            == Some("1.2.840.113549.1.1.1 (rsaEncryption)"))
+        .is_some());
+
+        // SPKI 的 BIT STRING 中封装了 RSAPublicKey, 应展开为子节点
+        let modulus = find(root, &|n| {
+            n.tag == "INTEGER"
+                && n.value
+                    .as_deref()
+                    .is_some_and(|v| v.starts_with("98501239575759"))
+        });
+        assert!(modulus.is_some());
+    }
+
+    #[test]
+    fn test_ber_indefinite_and_hex_input() {
+        // SEQUENCE (不定长) { INTEGER 5, [0] 不定长 { NULL } }
+        let tree = asn1_parse(Some("30 80 02 01 05 a0 80 05 00 00 00 00 00".into()), None).unwrap();
+        let root = &tree[0];
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].value.as_deref(), Some("5"));
+        assert_eq!(root.children[1].tag, "[0]");
+        assert_eq!(root.children[1].children[0].tag, "NULL");
+
+        // 高位标签号 + 长格式长度
+        let data = [0x9f, 0x81, 0x00, 0x81, 0x01, 0xab];
+        let nodes = parse_der(&data).unwrap();
+        assert_eq!((nodes[0].class, nodes[0].tag), (2, 128));
+        assert_eq!(nodes[0].content, &[0xab]);
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert!(asn1_parse(Some("30 05 02 01".into()), None).is_err());
+        assert!(asn1_parse(Some("30 80 02 01 05".into()), None).is_err());
+        assert!(asn1_parse(Some("???".into()), None).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use base64::alphabet::{Alphabet, STANDARD};
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine as _;
+use serde::Deserialize;
+
+use super::codec::{parse_text_or_bytes, render_bytes, BytesView};
+
+/// 换表 Base64
+///
+/// - alphabet: 64 个互不相同的可打印 ASCII 字符 (不含 `=`), 留空为标准表
+/// - pad: 填充字符, 缺省为 `=`; 留空字符串表示编码时不填充。解码时填充可有可无
+/// - data 编码时按 parse_text_or_bytes 解析
+#[derive(Deserialize, Default)]
+pub struct CustomBase64Params {
+    pub alphabet: Option<String>,
+    pub pad: Option<String>,
+    pub data: String,
+}
+
+#[tauri::command]
+pub fn custom_base64_encode(params: CustomBase64Params) -> Result<String, String> {
+    let data = parse_text_or_bytes(&params.data).map_err(|e| format!("数据格式错误: {}", e))?;
+    let (engine, pad) = engine(&params)?;
+    let encoded = engine.encode(data);
+    Ok(match pad {
+        Some(pad) => encoded.replace('=', &pad.to_string()),
+        None => encoded.trim_end_matches('=').to_string(),
+    })
+}
+
+#[tauri::command]
+pub fn custom_base64_decode(params: CustomBase64Params) -> Result<BytesView, String> {
+    let (engine, pad) = engine(&params)?;
+    let compact: String = params.data.chars().filter(|c| !c.is_whitespace()).collect();
+    let body = match pad {
+        Some(pad) => compact.trim_end_matches(pad),
+        None => &compact,
+    };
+    engine
+        .decode(body.trim_end_matches('='))
+        .map(|bytes| render_bytes(&bytes))
+        .map_err(|e| format!("按给定码表解码失败: {}", e))
+}
+
+fn engine(params: &CustomBase64Params) -> Result<(GeneralPurpose, Option<char>), String> {
+    let alphabet = match params.alphabet.as_deref().map(str::trim) {
+        Some(s) if !s.is_empty() => Alphabet::new(s).map_err(|e| format!("码表无效: {}", e))?,
+        _ => STANDARD,
+    };
+    let pad = match params.pad.as_deref() {
+        None => Some('='),
+        Some("") => None,
+        Some(s) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !alphabet.as_str().contains(c) => Some(c),
+                (Some(_), None) => return Err("填充字符不能出现在码表中".into()),
+                _ => return Err("填充须为单个字符".into()),
+            }
+        }
+    };
+    // 填充由调用方处理, 引擎始终输出 `=` 并容忍缺失的填充
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(true)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    Ok((GeneralPurpose::new(&alphabet, config), pad))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUSTOM: &str = "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/";
+
+    fn params(alphabet: Option<&str>, pad: Option<&str>, data: &str) -> CustomBase64Params {
+        CustomBase64Params {
+            alphabet: alphabet.map(Into::into),
+            pad: pad.map(Into::into),
+            data: data.into(),
+        }
+    }
+
+    #[test]
+    fn test_standard_alphabet_rfc4648() {
+        for (plain, encoded) in [
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(
+                custom_base64_encode(params(None, None, plain)).unwrap(),
+                encoded
+            );
+            let dec = custom_base64_decode(params(None, None, encoded)).unwrap();
+            assert_eq!(dec.utf8.as_deref(), Some(plain));
+        }
+    }
+
+    #[test]
+    fn test_custom_alphabet_and_padding() {
+        // 换表结果应与标准编码逐字符映射一致
+        let standard = STANDARD.as_str();
+        let plain = "flag{custom_base64_table}";
+        let expected: String = custom_base64_encode(params(None, None, plain))
+            .unwrap()
+            .chars()
+            .map(|c| match standard.find(c) {
+                Some(i) => CUSTOM.as_bytes()[i] as char,
+                None => c,
+            })
+            .collect();
+        let encoded = custom_base64_encode(params(Some(CUSTOM), None, plain)).unwrap();
+        assert_eq!(encoded, expected);
+        let dec = custom_base64_decode(params(Some(CUSTOM), None, &encoded)).unwrap();
+        assert_eq!(dec.utf8.as_deref(), Some(plain));
+
+        // 自定义填充字符与不填充
+        let encoded = custom_base64_encode(params(Some(CUSTOM), Some("."), "f")).unwrap();
+        assert!(encoded.ends_with(".."));
+        let dec = custom_base64_decode(params(Some(CUSTOM), Some("."), &encoded)).unwrap();
+        assert_eq!(dec.utf8.as_deref(), Some("f"));
+        let bare = custom_base64_encode(params(Some(CUSTOM), Some(""), "f")).unwrap();
+        assert_eq!(bare.len(), 2);
+        let dec = custom_base64_decode(params(Some(CUSTOM), None, &bare)).unwrap();
+        assert_eq!(dec.utf8.as_deref(), Some("f"));
+
+        assert!(custom_base64_encode(params(Some("ABC"), None, "f")).is_err());
+        assert!(custom_base64_encode(params(Some(CUSTOM), Some("Z"), "f")).is_err());
+    }
+}
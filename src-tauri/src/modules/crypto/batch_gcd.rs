@@ -0,0 +1,248 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use super::codec::{parse_biguint, NumberView};
+use super::factor::rsa_from_factors;
+use super::number_theory::is_probable_prime;
+
+#[derive(Serialize, Debug)]
+pub struct WeakModulus {
+    pub index: usize, // 从 1 开始, 对应输入中的第几个 N
+    pub n: String,
+    pub p: String,
+    pub q: String,
+    pub plaintext: Option<NumberView>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BatchGcdResult {
+    pub total: usize,
+    pub weak: Vec<WeakModulus>,
+}
+
+/// 批量 GCD: 用乘积树 + 余数树在准线性时间内找出与其他模数共享素因子的 N
+///
+/// c_list 可选, 长度需与 n_list 一致, 空字符串表示该模数没有对应密文。
+#[tauri::command]
+pub fn batch_gcd_attack(
+    n_list: Vec<String>,
+    e: Option<String>,
+    c_list: Option<Vec<String>>,
+) -> Result<BatchGcdResult, String> {
+    let moduli = n_list
+        .iter()
+        .enumerate()
+        .map(|(idx, n)| {
+            let n = parse_biguint(n).map_err(|err| format!("N{}: {}", idx + 1, err))?;
+            if n <= BigUint::one() {
+                return Err(format!("N{} 必须大于 1", idx + 1));
+            }
+            Ok(n)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if moduli.len() < 2 {
+        return Err("需要至少两个模数".into());
+    }
+
+    let ciphers = match c_list {
+        None => vec![None; moduli.len()],
+        Some(list) if list.len() != moduli.len() => {
+            return Err("C 的个数必须与 N 一致".into());
+        }
+        Some(list) => list
+            .iter()
+            .enumerate()
+            .map(|(idx, c)| {
+                if c.trim().is_empty() {
+                    Ok(None)
+                } else {
+                    parse_biguint(c)
+                        .map(Some)
+                        .map_err(|err| format!("C{}: {}", idx + 1, err))
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?,
+    };
+    let e = match e {
+        Some(s) if !s.trim().is_empty() => parse_biguint(&s)?,
+        _ => BigUint::from(65537u32),
+    };
+
+    let gcds = batch_gcd(&moduli);
+    let mut weak = Vec::new();
+    for (idx, (n, g)) in moduli.iter().zip(gcds.iter()).enumerate() {
+        if g.is_one() {
+            continue;
+        }
+        // g == n 说明 n 的所有素因子都在别处出现过, 退回到逐个比较
+        let p = if g == n {
+            match moduli
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != idx)
+                .map(|(_, other)| n.gcd(other))
+                .find(|g| !g.is_one() && g != n)
+            {
+                Some(p) => p,
+                None => continue, // 与另一个模数完全相同, 无法分解
+            }
+        } else {
+            g.clone()
+        };
+        let q = n / &p;
+        let (p, q) = if p <= q { (p, q) } else { (q, p) };
+
+        let key = match &ciphers[idx] {
+            Some(c) if is_probable_prime(&p) && is_probable_prime(&q) => {
+                rsa_from_factors(&[p.clone(), q.clone()], &e, c).ok()
+            }
+            _ => None,
+        };
+        weak.push(WeakModulus {
+            index: idx + 1,
+            n: n.to_string(),
+            p: p.to_string(),
+            q: q.to_string(),
+            plaintext: key.map(|k| k.plaintext),
+        });
+    }
+
+    Ok(BatchGcdResult {
+        total: moduli.len(),
+        weak,
+    })
+}
+
+// ===================== 乘积树 / 余数树 =====================
+
+/// 乘积树, levels[0] 为叶子 (输入本身), 最后一层只有一个元素 ΠN_i
+fn product_tree(moduli: &[BigUint]) -> Vec<Vec<BigUint>> {
+    let mut levels = vec![moduli.to_vec()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .par_chunks(2)
+            .map(|pair| pair.iter().product())
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// 对每个 N_i 计算 gcd(N_i, Π_{j≠i} N_j)
+///
+/// 自顶向下求 P mod N_i², 则 (P mod N_i²) / N_i ≡ Π_{j≠i} N_j (mod N_i)。
+pub fn batch_gcd(moduli: &[BigUint]) -> Vec<BigUint> {
+    let levels = product_tree(moduli);
+    let mut remainders = levels.last().unwrap().clone();
+
+    for level in levels.iter().rev().skip(1) {
+        remainders = level
+            .par_iter()
+            .enumerate()
+            .map(|(i, node)| &remainders[i / 2] % (node * node))
+            .collect();
+    }
+
+    remainders
+        .par_iter()
+        .zip(moduli.par_iter())
+        .map(|(r, n)| {
+            let quotient = r / n;
+            if quotient.is_zero() {
+                n.clone()
+            } else {
+                quotient.gcd(n)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next_prime(mut n: BigUint) -> BigUint {
+        if n.is_even() {
+            n += 1u32;
+        }
+        while !is_probable_prime(&n) {
+            n += 2u32;
+        }
+        n
+    }
+
+    #[test]
+    fn test_batch_gcd_small() {
+        let p = |x: u64| BigUint::from(x);
+        let moduli = vec![
+            p(1000000007) * p(1000000009),
+            p(998244353) * p(2147483647),
+            p(1000000007) * p(2535196823),
+            p(42194962807) * p(1000000021),
+        ];
+        let gcds = batch_gcd(&moduli);
+        assert_eq!(gcds[0], p(1000000007));
+        assert!(gcds[1].is_one());
+        assert_eq!(gcds[2], p(1000000007));
+        assert!(gcds[3].is_one());
+    }
+
+    #[test]
+    fn test_batch_gcd_many_moduli_with_decrypt() {
+        let base = BigUint::one() << 96u32;
+        let primes: Vec<BigUint> = (0..300u32)
+            .map(|i| next_prime(&base + BigUint::from(i) * 1_000_003u32 * 7919u32))
+            .collect();
+        // 150 个模数互不相交, 然后让 #11/#120 共享一个素数, #33/#34/#77 两两共享
+        let mut moduli: Vec<BigUint> = primes.chunks(2).map(|c| &c[0] * &c[1]).collect();
+        moduli[119] = &primes[20] * next_prime(&base * 3u32);
+        moduli[76] = &primes[65] * &primes[67];
+
+        let e = BigUint::from(65537u32);
+        let m = BigUint::from_bytes_be(b"shared!");
+        let c_list: Vec<String> = moduli
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                if i == 119 {
+                    m.modpow(&e, n).to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .collect();
+
+        let res = batch_gcd_attack(
+            moduli.iter().map(|n| n.to_string()).collect(),
+            None,
+            Some(c_list),
+        )
+        .unwrap();
+        assert_eq!(res.total, 150);
+        let indices: Vec<usize> = res.weak.iter().map(|w| w.index).collect();
+        assert_eq!(indices, vec![11, 33, 34, 77, 120]);
+
+        let w = res.weak.iter().find(|w| w.index == 120).unwrap();
+        assert_eq!(
+            w.plaintext.as_ref().and_then(|p| p.utf8.as_deref()),
+            Some("shared!")
+        );
+        assert!(res
+            .weak
+            .iter()
+            .find(|w| w.index == 11)
+            .unwrap()
+            .plaintext
+            .is_none());
+
+        // #77 = p65·p67, 两个素因子都被 #33/#34 覆盖, 需要走逐个比较的回退路径
+        let w = res.weak.iter().find(|w| w.index == 77).unwrap();
+        assert_eq!(w.p, primes[65].to_string());
+        assert_eq!(w.q, primes[67].to_string());
+    }
+}
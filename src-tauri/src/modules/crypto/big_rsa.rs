@@ -2,16 +2,19 @@ use num_bigint::BigInt;
 use num_integer::Integer;
 use num_traits::{One, Zero};
 
+use super::codec::{parse_biguint, render_number, NumberView};
+
 #[tauri::command]
 pub fn solve_multi_layer_rsa(
     n_list: Vec<String>,
     e_str: String,
     c_str: String,
-) -> Result<String, String> {
+) -> Result<NumberView, String> {
     let mut b_n_list = Vec::new();
     for (idx, n_s) in n_list.iter().enumerate() {
-        let parsed = BigInt::parse_bytes(n_s.trim().as_bytes(), 10)
-            .ok_or(format!("N{} 格式错误", idx + 1))?;
+        let parsed = BigInt::from(
+            parse_biguint(n_s).map_err(|err| format!("N{} 格式错误: {}", idx + 1, err))?,
+        );
         if parsed.is_zero() {
             return Err(format!("N{} 不能为零", idx + 1));
         }
@@ -22,8 +25,9 @@ pub fn solve_multi_layer_rsa(
         return Err("需要至少两个模数".into());
     }
 
-    let e = BigInt::parse_bytes(e_str.trim().as_bytes(), 10).ok_or("E 格式错误")?;
-    let mut current_m = BigInt::parse_bytes(c_str.trim().as_bytes(), 10).ok_or("C 格式错误")?;
+    let e = BigInt::from(parse_biguint(&e_str).map_err(|err| format!("E 格式错误: {}", err))?);
+    let mut current_m =
+        BigInt::from(parse_biguint(&c_str).map_err(|err| format!("C 格式错误: {}", err))?);
 
     for i in (0..b_n_list.len()).rev() {
         let n_current = &b_n_list[i];
@@ -58,7 +62,7 @@ pub fn solve_multi_layer_rsa(
         current_m = current_m.modpow(&d, n_current);
     }
 
-    Ok(format!("{:x}", current_m))
+    Ok(render_number(&current_m.to_biguint().unwrap()))
 }
 
 #[cfg(test)]
@@ -80,8 +84,8 @@ mod tests {
         let c3 = c2.modpow(&e_bi, &n3);
 
         let n_list = vec![n1.to_string(), n2.to_string(), n3.to_string()];
-        let result_hex = solve_multi_layer_rsa(n_list, e, c3.to_string()).expect("应该解密成功");
-        assert_eq!(result_hex, "2a");
+        let result = solve_multi_layer_rsa(n_list, e, c3.to_string()).expect("应该解密成功");
+        assert_eq!(result.hex, "2a");
     }
 
     #[test]
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::language::language_model;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CrackResult {
     pub label: String, // 算法名称
@@ -7,20 +9,6 @@ pub struct CrackResult {
     pub score: f64, // 评分
 }
 
-// 简单的英文频率打分
-fn score_text(text: &str) -> f64 {
-    let english_freq = "etaoinshrdlcumwfgypbvkjxqz";
-    let text_lower = text.to_lowercase();
-    let mut score = 0.0;
-
-    for c in text_lower.chars() {
-        if let Some(idx) = english_freq.find(c) {
-            score += (26 - idx) as f64;
-        }
-    }
-    score
-}
-
 // --- 核心算法实现 ---
 
 // 标准凯撒 (支持可选的数字偏移)
@@ -119,17 +107,19 @@ pub fn caesar_transform(
 pub fn caesar_crack(
     input: String,
     keyword: Option<String>,
-    scope: String, // "common" | "full"
+    scope: String,            // "common" | "full"
+    language: Option<String>, // 评分所用语言模型, 默认英文
 ) -> Result<Vec<CrackResult>, String> {
     if input.is_empty() {
         return Err("输入内容不能为空".into());
     }
+    let model = language_model(language.as_deref())?;
 
     let mut results = Vec::new();
 
     // 辅助闭包：添加结果并评分
     let mut add_result = |label: String, decoded: String| {
-        let mut score = score_text(&decoded);
+        let mut score = model.fitness(&decoded);
         if let Some(ref k) = keyword {
             if !k.is_empty() && decoded.to_lowercase().contains(&k.to_lowercase()) {
                 score += 1000.0; // 命中关键词加分
@@ -166,8 +156,8 @@ pub fn caesar_crack(
         }
     }
 
-    // 按分数排序 (高分在前)
-    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+    // 按分数排序 (高分在前), 同分时保持上面的顺序
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
 
     Ok(results)
 }
@@ -273,7 +263,7 @@ mod tests {
         // 原文: HELLO -> ROT13 -> URYYB
         let cipher = "URYYB";
 
-        let results = caesar_crack(cipher.into(), None, "common".into()).unwrap();
+        let results = caesar_crack(cipher.into(), None, "common".into(), None).unwrap();
 
         // 应该有结果，且排名第一的应该是 ROT13 变回 HELLO
         assert!(!results.is_empty());
@@ -290,16 +280,16 @@ mod tests {
         let expected_plain = "THE";
 
         // 1. 先用 common 跑，应该找不到完美匹配 (ROT3,5,13,18,47 都不对)
-        let common_results = caesar_crack(cipher.into(), None, "common".into()).unwrap();
+        let common_results = caesar_crack(cipher.into(), None, "common".into(), None).unwrap();
         // 检查 common 里是否包含 Shift -7 (不应该包含)
         let found_in_common = common_results.iter().any(|r| r.text == expected_plain);
         assert!(!found_in_common, "Common scope shouldn't include Shift 7");
 
         // 2. 用 full 跑
-        let full_results = caesar_crack(cipher.into(), None, "full".into()).unwrap();
+        let full_results = caesar_crack(cipher.into(), None, "full".into(), None).unwrap();
 
         // 3. 验证结果
-        // 因为 "THE" 包含 T(第2高频), H(高频), E(第1高频)，它的得分应该是极高的，大概率排第一
+        // "THE" 是英文最常见的三元组，它的得分应该是极高的，大概率排第一
         let best_match = &full_results[0];
         // 如果第一名不是 THE，打印出来看看是谁（便于调试），通常 THE 会是第一
         if best_match.text != expected_plain {
@@ -327,10 +317,11 @@ mod tests {
         let cipher = "KHOOR"; // HELLO (+3)
         let keyword = "HELLO";
 
-        let results = caesar_crack(cipher.into(), Some(keyword.into()), "full".into()).unwrap();
+        let results =
+            caesar_crack(cipher.into(), Some(keyword.into()), "full".into(), None).unwrap();
 
-        // 命中的结果分数应该非常高 (> 1000)
-        assert!(results[0].score > 1000.0);
+        // 命中的结果加 1000 分, 远高于其余位移的 n 元组得分
+        assert!(results[0].score > results[1].score + 900.0);
         assert_eq!(results[0].text, "HELLO");
     }
 
@@ -0,0 +1,532 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_bytes, parse_text_or_bytes, render_bytes, BytesView};
+use super::oracle::{reply_bit, OracleConn, OracleTarget};
+use super::padding::{pkcs7_pad, strip_pkcs7};
+use super::task::CryptoTasks;
+
+pub const PADDING_ORACLE_TASK: &str = "padding_oracle";
+
+const DEFAULT_BLOCK_SIZE: usize = 16;
+
+// ===================== Commands =====================
+
+/// 填充 oracle 攻击参数
+///
+/// - `decrypt`: 解密 ciphertext, 未给出 iv 时把密文首块当作 IV
+/// - `encrypt`: 为 plaintext 伪造 IV 与密文 (PKCS#7 填充), 末块可由 ciphertext 指定, 缺省为全零
+///
+/// template 为每次查询发送的内容, 其中 `{hex}`、`{b64}` 为两块消息 X‖C 的编码,
+/// `{iv}`、`{ct}` 为 X、C 各自的十六进制; 缺省为 `{hex}`。
+/// 响应含 valid_marker 视为填充正确, 含 invalid_marker 视为错误, 都未给出时按常见词识别
+#[derive(Deserialize, Default)]
+pub struct PaddingOracleParams {
+    pub target: OracleTarget,
+    pub mode: String,
+    pub block_size: Option<usize>,
+    pub iv: Option<String>,
+    pub ciphertext: Option<String>,
+    pub plaintext: Option<String>,
+    pub template: Option<String>,
+    pub valid_marker: Option<String>,
+    pub invalid_marker: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PaddingOracleProgress {
+    pub progress: f64,
+    pub block: usize,
+    pub blocks: usize,
+    pub partial: BytesView, // 解密时为已还原的明文, 伪造时为已得到的密文
+}
+
+#[derive(Serialize, Debug)]
+pub struct PaddingOracleResult {
+    pub plaintext: Option<BytesView>, // 解密结果 (已去掉合法的 PKCS#7 填充)
+    pub iv: Option<String>,           // 伪造得到的 IV
+    pub ciphertext: Option<String>,   // 伪造得到的密文
+    pub queries: u64,
+    pub cancelled: bool,
+}
+
+#[derive(Deserialize, Default)]
+pub struct CbcBitflipParams {
+    pub ciphertext: String,
+    pub iv: Option<String>, // 未给出时密文首块即 IV
+    pub block_size: Option<usize>,
+    pub offset: usize, // known 在明文中的起始偏移
+    pub known: String,
+    pub target: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CbcBitflipResult {
+    pub iv: Option<String>,
+    pub ciphertext: String,
+    pub mask: String,
+    pub garbled_block: Option<usize>, // 被破坏 (解密为乱码) 的明文块序号
+}
+
+/// Vaudenay CBC 填充 oracle 攻击, 逐字节求出各块的中间值 D(C_i)
+#[tauri::command]
+pub async fn padding_oracle_attack(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: PaddingOracleParams,
+) -> Result<PaddingOracleResult, String> {
+    let handle = tasks.begin(PADDING_ORACLE_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: PaddingOracleProgress| {
+            let _ = window.emit("padding-oracle-progress", progress);
+        };
+        run_padding_oracle(&params, &cancel, &emit)
+    })
+    .await
+    .map_err(|e| format!("填充 oracle 任务异常退出: {}", e))?
+}
+
+/// CBC 翻转: 把明文中 offset 处的 known 改为 target, 需要异或前一个密文块 (或 IV)
+#[tauri::command]
+pub fn cbc_bitflip(params: CbcBitflipParams) -> Result<CbcBitflipResult, String> {
+    let bs = block_size(params.block_size)?;
+    let ct = parse_bytes(&params.ciphertext).map_err(|err| format!("密文格式错误: {}", err))?;
+    let iv = optional_bytes(&params.iv, "IV")?;
+    let known = parse_text_or_bytes(&params.known)?;
+    let target = parse_text_or_bytes(&params.target)?;
+    if known.len() != target.len() || known.is_empty() {
+        return Err("已知明文与目标明文长度必须相同且不为空".into());
+    }
+
+    let mut full = iv.clone().unwrap_or_default();
+    full.extend_from_slice(&ct);
+    if full.len() % bs != 0 || full.len() < 2 * bs {
+        return Err(format!("IV 与密文总长须为块长 {} 的倍数且至少两块", bs));
+    }
+    // 明文长度为 full 去掉首块 (IV 或第一块密文)
+    let end = params
+        .offset
+        .checked_add(known.len())
+        .filter(|&end| end <= full.len() - bs)
+        .ok_or("偏移超出密文范围")?;
+    let block = params.offset / bs;
+    if (end - 1) / bs != block {
+        return Err("要修改的明文必须位于同一块内".into());
+    }
+
+    // 明文块 k 的前驱是 full 中的第 k 块
+    let mask: Vec<u8> = known.iter().zip(&target).map(|(a, b)| a ^ b).collect();
+    let start = block * bs + params.offset % bs;
+    for (i, m) in mask.iter().enumerate() {
+        full[start + i] ^= m;
+    }
+    let (iv, ct) = match iv {
+        Some(_) => (Some(hex::encode(&full[..bs])), hex::encode(&full[bs..])),
+        None => (None, hex::encode(&full)),
+    };
+    Ok(CbcBitflipResult {
+        iv,
+        ciphertext: ct,
+        mask: hex::encode(&mask),
+        garbled_block: block.checked_sub(1),
+    })
+}
+
+fn block_size(size: Option<usize>) -> Result<usize, String> {
+    match size.unwrap_or(DEFAULT_BLOCK_SIZE) {
+        bs @ 1..=255 => Ok(bs),
+        _ => Err("块长必须在 1 到 255 之间".into()),
+    }
+}
+
+fn optional_bytes(value: &Option<String>, name: &str) -> Result<Option<Vec<u8>>, String> {
+    value
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_bytes(s).map_err(|err| format!("{} 格式错误: {}", name, err)))
+        .transpose()
+}
+
+// ===================== 攻击 =====================
+
+pub fn run_padding_oracle(
+    params: &PaddingOracleParams,
+    cancel: &AtomicBool,
+    progress: &dyn Fn(PaddingOracleProgress),
+) -> Result<PaddingOracleResult, String> {
+    let bs = block_size(params.block_size)?;
+    let template = params.template.as_deref().unwrap_or("{hex}");
+    let valid = params.valid_marker.as_deref().filter(|s| !s.is_empty());
+    let invalid = params.invalid_marker.as_deref().filter(|s| !s.is_empty());
+    let iv = optional_bytes(&params.iv, "IV")?;
+    let ciphertext = optional_bytes(&params.ciphertext, "密文")?;
+    let plaintext = params
+        .plaintext
+        .as_deref()
+        .map(parse_text_or_bytes)
+        .transpose()?;
+
+    let mut conn = OracleConn::open(&params.target)?;
+    let mut oracle = |x: &[u8], c: &[u8]| -> Result<bool, String> {
+        let resp = conn.query(&format_query(template, x, c))?;
+        match (valid, invalid) {
+            (Some(marker), _) => Ok(resp.contains(marker)),
+            (None, Some(marker)) => Ok(!resp.contains(marker)),
+            (None, None) => reply_bit(&resp, None),
+        }
+    };
+
+    let mut result = PaddingOracleResult {
+        plaintext: None,
+        iv: None,
+        ciphertext: None,
+        queries: 0,
+        cancelled: false,
+    };
+    match params.mode.as_str() {
+        "decrypt" => {
+            let mut data = iv.unwrap_or_default();
+            data.extend_from_slice(&ciphertext.ok_or("缺少密文")?);
+            if data.len() % bs != 0 || data.len() < 2 * bs {
+                return Err(format!("IV 与密文总长须为块长 {} 的倍数且至少两块", bs));
+            }
+            let plain = decrypt_blocks(&data, bs, cancel, &mut oracle, progress)?;
+            result.cancelled = plain.is_none();
+            result.plaintext = plain.map(|p| render_bytes(strip_pkcs7(&p, bs)));
+        }
+        "encrypt" => {
+            let last = match ciphertext {
+                Some(c) if c.len() == bs => c,
+                Some(_) => return Err(format!("指定的末块长度须为 {}", bs)),
+                None => vec![0; bs],
+            };
+            let forged = forge_blocks(
+                &pkcs7_pad(&plaintext.ok_or("缺少目标明文")?, bs),
+                last,
+                cancel,
+                &mut oracle,
+                progress,
+            )?;
+            result.cancelled = forged.is_none();
+            if let Some(forged) = forged {
+                result.iv = Some(hex::encode(&forged[..bs]));
+                result.ciphertext = Some(hex::encode(&forged[bs..]));
+            }
+        }
+        other => return Err(format!("未知的模式: {}", other)),
+    }
+    result.queries = conn.queries;
+    Ok(result)
+}
+
+fn format_query(template: &str, x: &[u8], c: &[u8]) -> String {
+    let joined = [x, c].concat();
+    template
+        .replace("{hex}", &hex::encode(&joined))
+        .replace("{b64}", &general_purpose::STANDARD.encode(&joined))
+        .replace("{iv}", &hex::encode(x))
+        .replace("{ct}", &hex::encode(c))
+}
+
+/// 逐块解密 data = IV‖C_1‖…‖C_n, 被取消时返回 None
+fn decrypt_blocks<F>(
+    data: &[u8],
+    bs: usize,
+    cancel: &AtomicBool,
+    oracle: &mut F,
+    progress: &dyn Fn(PaddingOracleProgress),
+) -> Result<Option<Vec<u8>>, String>
+where
+    F: FnMut(&[u8], &[u8]) -> Result<bool, String>,
+{
+    let blocks: Vec<&[u8]> = data.chunks(bs).collect();
+    let total = blocks.len() - 1;
+    let mut plain = Vec::with_capacity(data.len() - bs);
+    for i in 1..=total {
+        let report = |found: usize, known: &[u8]| {
+            // 已完成的块加上当前块已确定的尾部字节
+            let mut partial = plain.clone();
+            partial.extend_from_slice(known);
+            progress(PaddingOracleProgress {
+                progress: ((i - 1) * bs + found) as f64 / (total * bs) as f64,
+                block: i,
+                blocks: total,
+                partial: render_bytes(&partial),
+            });
+        };
+        let Some(inter) = intermediate(blocks[i], cancel, oracle, &|found, inter| {
+            let known: Vec<u8> = (bs - found..bs)
+                .map(|k| inter[k] ^ blocks[i - 1][k])
+                .collect();
+            report(found, &known);
+        })?
+        else {
+            return Ok(None);
+        };
+        plain.extend(inter.iter().zip(blocks[i - 1]).map(|(a, b)| a ^ b));
+    }
+    Ok(Some(plain))
+}
+
+/// 由末块开始倒推: C_(i-1) = D(C_i) ⊕ P_i, 返回 IV‖C_1‖…‖C_n
+fn forge_blocks<F>(
+    padded: &[u8],
+    last: Vec<u8>,
+    cancel: &AtomicBool,
+    oracle: &mut F,
+    progress: &dyn Fn(PaddingOracleProgress),
+) -> Result<Option<Vec<u8>>, String>
+where
+    F: FnMut(&[u8], &[u8]) -> Result<bool, String>,
+{
+    let bs = last.len();
+    let total = padded.len() / bs;
+    let mut chain = vec![last];
+    for (done, p) in padded.chunks(bs).rev().enumerate() {
+        let current = chain.last().unwrap().clone();
+        let Some(inter) = intermediate(&current, cancel, oracle, &|found, _| {
+            progress(PaddingOracleProgress {
+                progress: (done * bs + found) as f64 / (total * bs) as f64,
+                block: total - done,
+                blocks: total,
+                partial: render_bytes(&chain.iter().rev().flatten().copied().collect::<Vec<_>>()),
+            });
+        })?
+        else {
+            return Ok(None);
+        };
+        chain.push(inter.iter().zip(p).map(|(a, b)| a ^ b).collect());
+    }
+    Ok(Some(chain.into_iter().rev().flatten().collect()))
+}
+
+/// 求 D(c): 从最后一个字节开始, 构造前驱块 X 使 X‖c 的解密以合法填充结尾
+fn intermediate<F>(
+    c: &[u8],
+    cancel: &AtomicBool,
+    oracle: &mut F,
+    report: &dyn Fn(usize, &[u8]),
+) -> Result<Option<Vec<u8>>, String>
+where
+    F: FnMut(&[u8], &[u8]) -> Result<bool, String>,
+{
+    let bs = c.len();
+    let mut inter = vec![0u8; bs];
+    for pos in (0..bs).rev() {
+        let pad = (bs - pos) as u8;
+        let mut x = vec![0u8; bs];
+        for k in pos + 1..bs {
+            x[k] = inter[k] ^ pad;
+        }
+        let mut found = None;
+        for guess in 0..=255u8 {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+            x[pos] = guess;
+            if !oracle(&x, c)? {
+                continue;
+            }
+            // 最后一个字节可能碰巧构成 \x02\x02 等更长的填充, 改动前一字节后仍合法才是 \x01
+            if pos == bs - 1 && pos > 0 {
+                x[pos - 1] ^= 0xff;
+                let still_valid = oracle(&x, c)?;
+                x[pos - 1] ^= 0xff;
+                if !still_valid {
+                    continue;
+                }
+            }
+            found = Some(guess);
+            break;
+        }
+        let guess = found
+            .ok_or_else(|| format!("第 {} 字节没有使填充合法的取值, oracle 响应可能有误", pos))?;
+        inter[pos] = guess ^ pad;
+        report(bs - pos, &inter);
+    }
+    Ok(Some(inter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const KEY: [u8; 16] = *b"toy-cbc-test-key";
+
+    // 测试用的玩具分组密码 (仿射 S 盒 + 字节轮转), 攻击本身与具体分组密码无关
+    fn toy_encrypt(block: &[u8]) -> Vec<u8> {
+        let mut out: Vec<u8> = block
+            .iter()
+            .zip(KEY)
+            .map(|(b, k)| (b ^ k).wrapping_mul(167).wrapping_add(13))
+            .collect();
+        out.rotate_left(5);
+        out
+    }
+
+    fn toy_decrypt(block: &[u8]) -> Vec<u8> {
+        let mut b = block.to_vec();
+        b.rotate_right(5);
+        // 167 · 23 ≡ 1 (mod 256)
+        b.iter()
+            .zip(KEY)
+            .map(|(b, k)| b.wrapping_sub(13).wrapping_mul(23) ^ k)
+            .collect()
+    }
+
+    fn cbc_encrypt(iv: &[u8], plain: &[u8]) -> Vec<u8> {
+        let mut prev = iv.to_vec();
+        let mut out = Vec::new();
+        for p in pkcs7_pad(plain, 16).chunks(16) {
+            let x: Vec<u8> = p.iter().zip(&prev).map(|(a, b)| a ^ b).collect();
+            prev = toy_encrypt(&x);
+            out.extend_from_slice(&prev);
+        }
+        out
+    }
+
+    /// 本地替身 oracle: 读入十六进制的 IV‖密文, 填充正确回答 "ok", 否则 "padding error"
+    fn spawn_oracle() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            stream.set_nodelay(true).unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            for line in BufReader::new(stream).lines() {
+                let data = hex::decode(line.unwrap().trim()).unwrap();
+                let mut prev = data[..16].to_vec();
+                let mut plain = Vec::new();
+                for c in data[16..].chunks(16) {
+                    plain.extend(toy_decrypt(c).iter().zip(&prev).map(|(a, b)| a ^ b));
+                    prev = c.to_vec();
+                }
+                let ok = strip_pkcs7(&plain, 16).len() != plain.len();
+                let reply = if ok { "ok" } else { "padding error" };
+                if writeln!(writer, "{}", reply).is_err() {
+                    break;
+                }
+            }
+        });
+        port
+    }
+
+    fn target(port: u16) -> OracleTarget {
+        OracleTarget {
+            mode: "tcp".into(),
+            address: Some(port.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_padding_oracle_decrypt_and_forge() {
+        let iv = *b"0123456789abcdef";
+        let ct = cbc_encrypt(&iv, b"flag{vaudenay_2002}");
+        let res = run_padding_oracle(
+            &PaddingOracleParams {
+                target: target(spawn_oracle()),
+                mode: "decrypt".into(),
+                iv: Some(hex::encode(iv)),
+                ciphertext: Some(hex::encode(&ct)),
+                invalid_marker: Some("error".into()),
+                ..Default::default()
+            },
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.plaintext.unwrap().utf8.as_deref(),
+            Some("flag{vaudenay_2002}")
+        );
+
+        let res = run_padding_oracle(
+            &PaddingOracleParams {
+                target: target(spawn_oracle()),
+                mode: "encrypt".into(),
+                plaintext: Some("user=admin;role=root".into()),
+                ..Default::default()
+            },
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        let iv = hex::decode(res.iv.unwrap()).unwrap();
+        let ct = hex::decode(res.ciphertext.unwrap()).unwrap();
+        assert_eq!(ct.len(), 32);
+        assert_eq!(ct, cbc_encrypt(&iv, b"user=admin;role=root"));
+    }
+
+    #[test]
+    fn test_cbc_bitflip() {
+        let iv = *b"IVIVIVIVIVIVIVIV";
+        let plain = b"comment=hello;xxadmin=0;user=guest";
+        let ct = cbc_encrypt(&iv, plain);
+
+        // 修改第 2 块中的 "admin=0", 第 1 块会被破坏
+        let res = cbc_bitflip(CbcBitflipParams {
+            ciphertext: hex::encode(&ct),
+            iv: Some(hex::encode(iv)),
+            offset: 16,
+            known: "admin=0".into(),
+            target: "admin=1".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(res.garbled_block, Some(0));
+        assert_eq!(res.mask, "00000000000001");
+        let mut data = hex::decode(res.iv.unwrap()).unwrap();
+        data.extend(hex::decode(res.ciphertext).unwrap());
+        let mut out = Vec::new();
+        for w in data.chunks(16).collect::<Vec<_>>().windows(2) {
+            out.extend(toy_decrypt(w[1]).iter().zip(w[0]).map(|(a, b)| a ^ b));
+        }
+        assert_eq!(&out[16..34], b"admin=1;user=guest");
+
+        // 首块通过 IV 修改, 不破坏任何明文
+        let res = cbc_bitflip(CbcBitflipParams {
+            ciphertext: hex::encode([iv.as_slice(), &ct].concat()),
+            offset: 8,
+            known: "hello".into(),
+            target: "HELLO".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(res.garbled_block, None);
+        assert!(res.iv.is_none());
+
+        let err = cbc_bitflip(CbcBitflipParams {
+            ciphertext: hex::encode(&ct),
+            iv: Some(hex::encode(iv)),
+            offset: 14,
+            known: "xxad".into(),
+            target: "yyad".into(),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.contains("同一块"));
+
+        for offset in [ct.len(), usize::MAX] {
+            let err = cbc_bitflip(CbcBitflipParams {
+                ciphertext: hex::encode(&ct),
+                iv: Some(hex::encode(iv)),
+                offset,
+                known: "a".into(),
+                target: "b".into(),
+                ..Default::default()
+            })
+            .unwrap_err();
+            assert!(err.contains("超出"));
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use base64::{engine::general_purpose, Engine as _};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::Num;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// 大整数的多种展示形式, 所有 RSA 类命令的明文结果都使用该结构
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct NumberView {
+    pub decimal: String,
+    pub hex: String,
+    pub bytes: String,        // Python 风格的 b'...' 表示
+    pub utf8: Option<String>, // 字节是合法 UTF-8 且不含不可见控制字符时给出
+    pub flag: Option<String>, // 形如 flag{...} 的片段
+}
+
+/// 字节串的多种展示形式, 对称密码类命令的输出使用该结构
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct BytesView {
+    pub hex: String,
+    pub bytes: String,
+    pub utf8: Option<String>,
+    pub flag: Option<String>,
+}
+
+#[tauri::command]
+pub fn convert_number(value: String) -> Result<NumberView, String> {
+    Ok(render_number(&parse_biguint(&value)?))
+}
+
+// ===================== 输入解析 =====================
+
+/// 统一的大整数输入解析, 自动识别:
+///
+/// - 十进制 (可带 Python 2 的 `L` 后缀)、`0x` 十六进制、`0o` 八进制、`0b` 二进制
+/// - 不带前缀的十六进制 (十进制优先)
+/// - `b'...'` / `"..."` 形式的字节串, 按 bytes_to_long 转换
+/// - Base64 (或显式的 `b64:` / `hex:` 前缀)
+pub fn parse_biguint(s: &str) -> Result<BigUint, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("输入为空".into());
+    }
+
+    if let Some(bytes) = parse_quoted_bytes(s)? {
+        return Ok(BigUint::from_bytes_be(&bytes));
+    }
+    if let Some(rest) =
+        strip_prefix_ignore_case(s, "b64:").or(strip_prefix_ignore_case(s, "base64:"))
+    {
+        return decode_base64(rest, false)
+            .map(|b| BigUint::from_bytes_be(&b))
+            .ok_or_else(|| format!("无效 Base64: {}", s));
+    }
+
+    // 数字形式允许中间夹杂空白、换行与下划线
+    let compact: String = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect();
+
+    if let Some(rest) = strip_prefix_ignore_case(&compact, "hex:") {
+        return BigUint::from_str_radix(rest, 16).map_err(|_| format!("无效十六进制: {}", s));
+    }
+    for (prefix, radix, name) in [
+        ("0x", 16, "十六进制"),
+        ("0o", 8, "八进制"),
+        ("0b", 2, "二进制"),
+    ] {
+        if let Some(rest) = strip_prefix_ignore_case(&compact, prefix) {
+            // 0b 开头也可能是不带前缀的十六进制 (如 0b3f...)
+            if radix == 2 && !rest.chars().all(|c| c == '0' || c == '1') {
+                continue;
+            }
+            return BigUint::from_str_radix(rest, radix)
+                .map_err(|_| format!("无效{}: {}", name, s));
+        }
+    }
+
+    let digits = compact
+        .strip_suffix(['L', 'l'])
+        .filter(|d| !d.is_empty() && d.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(&compact);
+    if digits.chars().all(|c| c.is_ascii_digit()) {
+        return BigUint::from_str_radix(digits, 10).map_err(|_| format!("无法解析为整数: {}", s));
+    }
+    if compact.chars().all(|c| c.is_ascii_hexdigit()) {
+        return BigUint::from_str_radix(&compact, 16).map_err(|_| format!("无效十六进制: {}", s));
+    }
+    // 自动识别时只接受带补齐的标准 Base64, 避免把普通的错误输入误判为 Base64
+    let b64: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(bytes) = decode_base64(&b64, true) {
+        return Ok(BigUint::from_bytes_be(&bytes));
+    }
+
+    Err(format!("无法解析为整数: {}", s))
+}
+
+/// 允许带正负号的整数输入, 绝对值部分按 parse_biguint 解析
+pub fn parse_bigint(s: &str) -> Result<BigInt, String> {
+    let s = s.trim();
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => (Sign::Minus, rest),
+        None => (Sign::Plus, s.strip_prefix('+').unwrap_or(s)),
+    };
+    Ok(BigInt::from_biguint(sign, parse_biguint(rest)?))
+}
+
+/// 字节串输入解析: `b'...'` 字面量、`hex:` / `0x` / 纯十六进制 (偶数长度)、Base64
+pub fn parse_bytes(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("输入为空".into());
+    }
+    if let Some(bytes) = parse_quoted_bytes(s)? {
+        return Ok(bytes);
+    }
+    if let Some(rest) =
+        strip_prefix_ignore_case(s, "b64:").or(strip_prefix_ignore_case(s, "base64:"))
+    {
+        return decode_base64(rest, false).ok_or_else(|| format!("无效 Base64: {}", s));
+    }
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let hex_part = strip_prefix_ignore_case(&compact, "hex:")
+        .or(strip_prefix_ignore_case(&compact, "0x"))
+        .unwrap_or(&compact);
+    if let Ok(bytes) = hex::decode(hex_part) {
+        return Ok(bytes);
+    }
+    decode_base64(&compact, true).ok_or_else(|| format!("无法解析为字节串: {}", s))
+}
+
+/// 明文类输入: 带引号或 `hex:` / `b64:` 前缀时按 parse_bytes 解析, 否则按 UTF-8 原样使用
+pub fn parse_text_or_bytes(s: &str) -> Result<Vec<u8>, String> {
+    let t = s.trim();
+    let explicit = ["hex:", "b64:", "base64:"]
+        .iter()
+        .any(|p| strip_prefix_ignore_case(t, p).is_some());
+    if explicit || parse_quoted_bytes(t).is_ok_and(|b| b.is_some()) {
+        return parse_bytes(t);
+    }
+    Ok(s.as_bytes().to_vec())
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+fn decode_base64(s: &str, strict: bool) -> Option<Vec<u8>> {
+    let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if s.is_empty() {
+        return None;
+    }
+    if strict {
+        return general_purpose::STANDARD
+            .decode(&s)
+            .ok()
+            .filter(|b| !b.is_empty());
+    }
+    general_purpose::STANDARD
+        .decode(&s)
+        .or_else(|_| general_purpose::STANDARD_NO_PAD.decode(&s))
+        .or_else(|_| general_purpose::URL_SAFE.decode(&s))
+        .or_else(|_| general_purpose::URL_SAFE_NO_PAD.decode(&s))
+        .ok()
+        .filter(|b| !b.is_empty())
+}
+
+/// 解析 Python 风格的字节串字面量: b'..', b"..", '..', ".."
+fn parse_quoted_bytes(s: &str) -> Result<Option<Vec<u8>>, String> {
+    let body = s.strip_prefix(['b', 'B']).unwrap_or(s);
+    let quote = match body.chars().next() {
+        Some(q @ ('\'' | '"')) => q,
+        _ => return Ok(None),
+    };
+    let Some(inner) = body[1..].strip_suffix(quote) else {
+        return Err(format!("字节串缺少结束引号: {}", s));
+    };
+
+    let mut out = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0u8; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let esc = chars.next().ok_or("字节串以单个反斜杠结尾")?;
+        match esc {
+            'n' => out.push(b'\n'),
+            'r' => out.push(b'\r'),
+            't' => out.push(b'\t'),
+            'a' => out.push(0x07),
+            'b' => out.push(0x08),
+            'f' => out.push(0x0c),
+            'v' => out.push(0x0b),
+            '\\' | '\'' | '"' => out.push(esc as u8),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let b = u8::from_str_radix(&hex, 16)
+                    .map_err(|_| format!("无效的转义序列: \\x{}", hex))?;
+                out.push(b);
+            }
+            '0'..='7' => {
+                let mut value = esc.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) => {
+                            value = value * 8 + d;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                out.push((value & 0xff) as u8);
+            }
+            other => {
+                // Python 对未知转义保留反斜杠
+                out.push(b'\\');
+                let mut buf = [0u8; 4];
+                out.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+    Ok(Some(out))
+}
+
+// ===================== 结果展示 =====================
+
+pub fn render_number(value: &BigUint) -> NumberView {
+    let bytes = value.to_bytes_be();
+    NumberView {
+        decimal: value.to_string(),
+        hex: format!("{:x}", value),
+        bytes: python_bytes_repr(&bytes),
+        utf8: printable_utf8(&bytes),
+        flag: guess_flag(&bytes),
+    }
+}
+
+pub fn render_bytes(bytes: &[u8]) -> BytesView {
+    BytesView {
+        hex: hex::encode(bytes),
+        bytes: python_bytes_repr(bytes),
+        utf8: printable_utf8(bytes),
+        flag: guess_flag(bytes),
+    }
+}
+
+/// 与 Python repr(bytes) 一致的转义形式
+fn python_bytes_repr(bytes: &[u8]) -> String {
+    let mut out = String::from("b'");
+    for &b in bytes {
+        match b {
+            b'\\' => out.push_str("\\\\"),
+            b'\'' => out.push_str("\\'"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7e => out.push(b as char),
+            _ => out.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+fn printable_utf8(bytes: &[u8]) -> Option<String> {
+    let s = std::str::from_utf8(bytes).ok()?;
+    if s.is_empty()
+        || s.chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        return None;
+    }
+    Some(s.to_string())
+}
+
+// flag 识别所用的正则, 候选明文较多时 (如有限域开方) 会被反复调用, 因此只编译一次
+static FLAG_PATTERNS: LazyLock<[Regex; 2]> = LazyLock::new(|| {
+    [
+        Regex::new(r"(?i)[a-z0-9_\-]{0,16}(flag|ctf)[a-z0-9_\-]{0,16}\{[^{}\x00-\x1f]{1,256}\}")
+            .unwrap(),
+        Regex::new(r"[A-Za-z0-9_]{2,16}\{[\x20-\x7c\x7e]{1,256}?\}").unwrap(),
+    ]
+});
+
+/// 在明文中寻找 flag 片段 (明文可能带有 PKCS#1 填充等前缀, 因此按字节有损解码后搜索)
+fn guess_flag(bytes: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(bytes);
+    FLAG_PATTERNS
+        .iter()
+        .find_map(|re| re.find(&text).map(|m| m.as_str().to_string()))
+}
+
+/// 粗略判断一段字节是否像明文: 可打印 UTF-8 或包含 flag 片段
+pub fn looks_like_plaintext(bytes: &[u8]) -> bool {
+    printable_utf8(bytes).is_some() || guess_flag(bytes).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number_formats() {
+        let expect = BigUint::from(0x666c6167u32); // "flag"
+        for input in [
+            "1718378855",
+            "1718378855L",
+            "0x666c6167",
+            "0X666C 6167",
+            "666c6167",
+            "0o14633060547",
+            "0b1100110011011000110000101100111",
+            "b'flag'",
+            "\"flag\"",
+            "b'\\x66la\\147'",
+            "ZmxhZw==",
+            "b64:ZmxhZw",
+        ] {
+            assert_eq!(parse_biguint(input).unwrap(), expect, "{}", input);
+        }
+        assert!(parse_biguint("").is_err());
+        assert!(parse_biguint("0xzz").is_err());
+        assert!(parse_biguint("b'abc").is_err());
+        assert!(parse_biguint("not a number!").is_err());
+        assert!(parse_biguint("not_a_number").is_err());
+        assert_eq!(parse_bigint("-0x10").unwrap(), BigInt::from(-16));
+        assert_eq!(parse_bigint(" +42 ").unwrap(), BigInt::from(42));
+
+        assert_eq!(parse_bytes("0x0102ff").unwrap(), vec![1, 2, 0xff]);
+        assert_eq!(parse_bytes("b'ab\\x00'").unwrap(), b"ab\x00".to_vec());
+        assert_eq!(parse_bytes("ZmxhZw==").unwrap(), b"flag".to_vec());
+        assert!(parse_bytes("xyz").is_err());
+        assert_eq!(parse_text_or_bytes("admin=1").unwrap(), b"admin=1".to_vec());
+        assert_eq!(parse_text_or_bytes("hex:4142").unwrap(), b"AB".to_vec());
+    }
+
+    #[test]
+    fn test_render_number() {
+        let v = render_number(&BigUint::from_bytes_be(b"NSSCTF{it's\n}"));
+        assert_eq!(v.utf8.as_deref(), Some("NSSCTF{it's\n}"));
+        assert_eq!(v.bytes, "b'NSSCTF{it\\'s\\n}'");
+        assert!(v.hex.starts_with("4e5353435446"));
+
+        // PKCS#1 v1.5 填充后的明文: 非 UTF-8, 但能找到 flag
+        let mut padded = vec![0x02, 0xff, 0x13, 0x00];
+        padded.extend_from_slice(b"flag{padded}");
+        let v = render_number(&BigUint::from_bytes_be(&padded));
+        assert!(v.utf8.is_none());
+        assert_eq!(v.flag.as_deref(), Some("flag{padded}"));
+        assert!(v.bytes.starts_with("b'\\x02\\xff\\x13\\x00flag"));
+
+        let v = render_bytes(b"\x00flag{raw}");
+        assert_eq!(v.hex, "00666c61677b7261777d");
+        assert_eq!(v.flag.as_deref(), Some("flag{raw}"));
+
+        let v = render_number(&BigUint::from(42u32));
+        assert_eq!((v.decimal.as_str(), v.hex.as_str()), ("42", "2a"));
+        assert_eq!(v.utf8.as_deref(), Some("*"));
+        assert!(v.flag.is_none());
+    }
+}
@@ -1,27 +1,90 @@
-use num_bigint::BigUint;
-use num_traits::{Num, One, ToPrimitive, Zero};
-use std::str::FromStr;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use serde::Serialize;
 
-pub fn extended_gcd(a: &BigUint, b: &BigUint) -> (BigUint, i64, i64) {
-    if b.is_zero() {
-        (a.clone(), 1, 0)
-    } else {
-        let (g, x1, y1) = extended_gcd(b, &(a % b));
-        let x = y1;
-        let y = x1 - ((a / b).to_i64().unwrap_or(0)) * y1;
-        (g, x, y)
+use super::codec::{looks_like_plaintext, parse_biguint, render_number, NumberView};
+use super::modular_root::{combine_roots, nth_roots_mod_factors};
+use super::number_theory::exact_nth_root;
+
+// m^g 不足以直接开方时, 尝试 m^g + k·N 的 k 上限
+const ROOT_SEARCH_LIMIT: u32 = 10_000;
+
+#[derive(Serialize, Debug)]
+pub struct CommonModulusResult {
+    pub g: String,           // 所用指数组合的最大公约数
+    pub used: Vec<usize>,    // 参与组合的 (e, c) 序号, 从 1 开始
+    pub skipped: Vec<usize>, // c 与 N 不互质而被跳过的序号
+    pub method: String,
+    pub plaintext: NumberView,
+}
+
+/// 共模攻击的内部结果
+pub struct CommonModulusOutcome {
+    pub g: BigUint,
+    pub used: Vec<usize>,
+    pub skipped: Vec<usize>,
+    pub method: &'static str,
+    pub m: BigUint,
+}
+
+/// 任意多组 (e_i, c_i) 的共模攻击, factors 为可选的 N 的素因子 (用于模开方)
+#[tauri::command]
+pub fn common_modulus_attack_multi(
+    n: String,
+    e_list: Vec<String>,
+    c_list: Vec<String>,
+    factors: Option<Vec<String>>,
+) -> Result<CommonModulusResult, String> {
+    if e_list.len() != c_list.len() {
+        return Err("E 与 C 的个数不一致".into());
+    }
+    if e_list.len() < 2 {
+        return Err("至少需要两组 (e, c)".into());
+    }
+    let n = parse_biguint(&n)?;
+    if n <= BigUint::one() {
+        return Err("N 必须大于 1".into());
     }
+    let mut pairs = Vec::with_capacity(e_list.len());
+    for (idx, (e, c)) in e_list.iter().zip(c_list.iter()).enumerate() {
+        let e = parse_biguint(e).map_err(|err| format!("e{}: {}", idx + 1, err))?;
+        let c = parse_biguint(c).map_err(|err| format!("c{}: {}", idx + 1, err))?;
+        pairs.push((e, c));
+    }
+    let factors = factors
+        .unwrap_or_default()
+        .iter()
+        .filter(|f| !f.trim().is_empty())
+        .map(|f| parse_biguint(f))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let outcome = common_modulus_recover(&n, &pairs, &factors)?;
+    Ok(CommonModulusResult {
+        g: outcome.g.to_string(),
+        used: outcome.used.iter().map(|i| i + 1).collect(),
+        skipped: outcome.skipped.iter().map(|i| i + 1).collect(),
+        method: outcome.method.into(),
+        plaintext: render_number(&outcome.m),
+    })
 }
 
-pub fn parse_biguint(s: &str) -> Result<BigUint, String> {
-    let s = s.trim();
-    if s.starts_with("0x") || s.starts_with("0X") {
-        BigUint::from_str_radix(&s[2..], 16).map_err(|_| format!("无效十六进制: {}", s))
+/// 扩展欧几里得: 返回 (g, x, y), 满足 a·x + b·y = g
+pub fn extended_gcd(a: &BigUint, b: &BigUint) -> (BigUint, BigInt, BigInt) {
+    let a = BigInt::from_biguint(Sign::Plus, a.clone());
+    let b = BigInt::from_biguint(Sign::Plus, b.clone());
+    let egcd = a.extended_gcd(&b);
+    (egcd.gcd.to_biguint().unwrap(), egcd.x, egcd.y)
+}
+
+/// 带符号指数的模幂, 指数为负时先求逆元
+fn modpow_signed(base: &BigUint, exp: &BigInt, modulus: &BigUint) -> Option<BigUint> {
+    let base = if exp.is_negative() {
+        base.modinv(modulus)?
     } else {
-        BigUint::from_str(s)
-            .or_else(|_| BigUint::from_str_radix(s, 16))
-            .map_err(|_| format!("无法解析为整数: {}", s))
-    }
+        base.clone()
+    };
+    Some(base.modpow(exp.magnitude(), modulus))
 }
 
 pub fn recover_plaintext(
@@ -31,34 +94,124 @@ pub fn recover_plaintext(
     e2: &BigUint,
     c2: &BigUint,
 ) -> Result<Vec<u8>, String> {
-    let (g, s, t) = extended_gcd(e1, e2);
-    if !g.is_one() {
-        return Err("e1 与 e2 不互质，无法进行共模攻击!".into());
-    }
+    let pairs = [(e1.clone(), c1.clone()), (e2.clone(), c2.clone())];
+    let outcome = common_modulus_recover(n, &pairs, &[])?;
+    Ok(outcome.m.to_bytes_be())
+}
 
-    let modulus = n;
-    let c1_inv = c1.modinv(modulus).ok_or("c1 在模 N 下不可逆!")?;
-    let c2_inv = c2.modinv(modulus).ok_or("c2 在模 N 下不可逆!")?;
+/// 共模攻击核心: 依次用扩展欧几里得合并各组, 得到 m^g (g 为可达到的最小公约数),
+/// 再按 g = 1 / 整数开方 / 已知因子时模开方 的顺序恢复 m
+pub fn common_modulus_recover(
+    n: &BigUint,
+    pairs: &[(BigUint, BigUint)],
+    factors: &[BigUint],
+) -> Result<CommonModulusOutcome, String> {
+    let mut acc: Option<(BigUint, BigUint)> = None;
+    let mut used = Vec::new();
+    let mut skipped = Vec::new();
 
-    let part1 = if s >= 0 {
-        c1.modpow(&BigUint::from(s as u64), modulus)
-    } else {
-        c1_inv.modpow(&BigUint::from((-s) as u64), modulus)
+    for (idx, (e, c)) in pairs.iter().enumerate() {
+        if e.is_zero() {
+            continue;
+        }
+        // c 与 N 不互质时无法求逆 (此时 gcd(c, N) 本身就是 N 的因子)
+        if !c.gcd(n).is_one() {
+            skipped.push(idx);
+            continue;
+        }
+        acc = match acc {
+            None => {
+                used.push(idx);
+                Some((e.clone(), c.clone()))
+            }
+            Some((e_acc, c_acc)) => {
+                let (g, s, t) = extended_gcd(&e_acc, e);
+                if g == e_acc {
+                    // 该组不能让公约数继续变小
+                    Some((e_acc, c_acc))
+                } else {
+                    used.push(idx);
+                    let part1 = modpow_signed(&c_acc, &s, n).ok_or("模逆不存在")?;
+                    let part2 = modpow_signed(c, &t, n).ok_or("模逆不存在")?;
+                    Some((g, part1 * part2 % n))
+                }
+            }
+        };
+    }
+
+    let (g, m_g) = match acc {
+        Some(acc) if used.len() >= 2 => acc,
+        _ => {
+            return Err(if skipped.is_empty() {
+                "可用的 (e, c) 不足两组".into()
+            } else {
+                "可用的 (e, c) 不足两组, 部分密文与 N 不互质 (gcd(c, N) 即为 N 的因子)".into()
+            })
+        }
     };
 
-    let part2 = if t >= 0 {
-        c2.modpow(&BigUint::from(t as u64), modulus)
-    } else {
-        c2_inv.modpow(&BigUint::from((-t) as u64), modulus)
+    let outcome = |method, m| {
+        Ok(CommonModulusOutcome {
+            g: g.clone(),
+            used: used.clone(),
+            skipped: skipped.clone(),
+            method,
+            m,
+        })
     };
 
-    let m = (part1 * part2) % modulus;
-    Ok(m.to_bytes_be())
+    if g.is_one() {
+        return outcome("扩展欧几里得", m_g);
+    }
+
+    let k = g
+        .to_u32()
+        .ok_or_else(|| format!("各组 e 的公约数过大 (g = {})", g))?;
+
+    // m^g 未超过 N 太多时, 直接在整数上开 g 次方
+    let mut value = m_g.clone();
+    for _ in 0..ROOT_SEARCH_LIMIT {
+        if let Some(m) = exact_nth_root(&value, k) {
+            return outcome("扩展欧几里得 + 整数开方", m);
+        }
+        value += n;
+    }
+
+    if !factors.is_empty() {
+        let m = root_mod_factors(&m_g, k, n, factors)?;
+        return outcome("扩展欧几里得 + 模 p 开方", m);
+    }
+
+    Err(format!(
+        "已得到 m^{} mod N, 但整数开方失败; 请提供 N 的素因子以进行模开方",
+        g
+    ))
+}
+
+/// 已知 N 的素因子时, 在每个素数下求出全部 k 次方根 (AMM), CRT 合并后优先选取像明文的结果
+fn root_mod_factors(
+    c: &BigUint,
+    k: u32,
+    n: &BigUint,
+    factors: &[BigUint],
+) -> Result<BigUint, String> {
+    if factors.iter().product::<BigUint>() != *n {
+        return Err("所给素因子的乘积不等于 N".into());
+    }
+    let lists = nth_roots_mod_factors(c, &BigUint::from(k), factors)?;
+    let roots = combine_roots(&lists, |_| true)?;
+    roots
+        .iter()
+        .find(|m| looks_like_plaintext(&m.to_bytes_be()))
+        .or(roots.first())
+        .cloned()
+        .ok_or_else(|| "模开方无解".into())
 }
 
 // 单元测试
 #[cfg(test)]
 mod tests {
+    use super::super::codec::parse_biguint;
     use super::*;
 
     #[test]
@@ -98,7 +251,8 @@ mod tests {
 
         assert!(result.is_err());
         let error_msg = result.unwrap_err();
-        assert!(error_msg.contains("e1 与 e2 不互质"));
+        // c1、c2 都与 N 不互质, 无法组合
+        assert!(error_msg.contains("不足两组"));
     }
 
     #[test]
@@ -108,14 +262,14 @@ mod tests {
         let b = BigUint::zero();
         let (g, x, y) = extended_gcd(&a, &b);
         assert_eq!(g, a);
-        assert_eq!(x, 1);
-        assert_eq!(y, 0);
+        assert_eq!(x, BigInt::from(1));
+        assert_eq!(y, BigInt::from(0));
 
         // Verify identity: a*x + b*y = g
         // Calculate a*x (x is positive)
-        let ax = &a * BigUint::from(x.unsigned_abs());
+        let ax = &a * x.magnitude();
         // Calculate b*y (y is 0, so result is 0)
-        let by = &b * BigUint::from(y.unsigned_abs());
+        let by = &b * y.magnitude();
         let calculated_g = &ax + &by; // Use reference to avoid moving a/b
         assert_eq!(calculated_g, g);
 
@@ -124,14 +278,14 @@ mod tests {
         let b = BigUint::from(15u32);
         let (g, x, y) = extended_gcd(&a, &b);
         assert_eq!(g, b);
-        assert_eq!(x, 0);
-        assert_eq!(y, 1);
+        assert_eq!(x, BigInt::from(0));
+        assert_eq!(y, BigInt::from(1));
 
         // Verify identity: a*x + b*y = g
         // Calculate a*x (a is 0, so result is 0)
-        let ax = &a * BigUint::from(x.unsigned_abs());
+        let ax = &a * x.magnitude();
         // Calculate b*y (y is positive)
-        let by = &b * BigUint::from(y.unsigned_abs());
+        let by = &b * y.magnitude();
         let calculated_g = &ax + &by;
         assert_eq!(calculated_g, g);
 
@@ -140,15 +294,76 @@ mod tests {
         let b = BigUint::zero();
         let (g, x, y) = extended_gcd(&a, &b);
         assert_eq!(g, BigUint::zero());
-        assert_eq!(x, 1); // As per your function's output for (0,0)
-        assert_eq!(y, 0); // As per your function's output for (0,0)
+        assert_eq!(x, BigInt::from(1)); // As per your function's output for (0,0)
+        assert_eq!(y, BigInt::from(0)); // As per your function's output for (0,0)
 
         // Verify identity: a*x + b*y = g
         // Calculate a*x (a is 0, x is positive)
-        let ax = &a * BigUint::from(x.unsigned_abs());
+        let ax = &a * x.magnitude();
         // Calculate b*y (b is 0, y is 0)
-        let by = &b * BigUint::from(y.unsigned_abs());
+        let by = &b * y.magnitude();
         let calculated_g = &ax + &by;
         assert_eq!(calculated_g, g); // Should be 0 + 0 = 0
     }
+
+    const N_256: &str =
+        "47619075096357399156465346975177966827852045162582961608582994196333069965049";
+
+    #[test]
+    fn test_non_coprime_exponents_integer_root() {
+        let n = parse_biguint(N_256).unwrap();
+        let m = BigUint::from_bytes_be(b"flag{gcd_is_2}");
+        let (e1, e2) = (BigUint::from(6u32), BigUint::from(10u32));
+        let c1 = m.modpow(&e1, &n);
+        let c2 = m.modpow(&e2, &n);
+
+        let bytes = recover_plaintext(&n, &e1, &c1, &e2, &c2).unwrap();
+        assert_eq!(bytes, b"flag{gcd_is_2}");
+    }
+
+    #[test]
+    fn test_many_pairs_picks_combination() {
+        let n = parse_biguint(N_256).unwrap();
+        let m = BigUint::from_bytes_be(b"ctf{m^3}");
+        let es = [12u32, 18, 65537, 33, 196611];
+        let mut c_list: Vec<String> = es
+            .iter()
+            .map(|&e| m.modpow(&BigUint::from(e), &n).to_string())
+            .collect();
+        c_list[2] = "0".into(); // 与 N 不互质, 应被跳过
+
+        let res = common_modulus_attack_multi(
+            N_256.into(),
+            es.iter().map(|e| e.to_string()).collect(),
+            c_list,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.g, "3");
+        assert_eq!(res.used, vec![1, 2, 4]);
+        assert_eq!(res.skipped, vec![3]);
+        assert_eq!(res.plaintext.utf8.as_deref(), Some("ctf{m^3}"));
+    }
+
+    #[test]
+    fn test_modular_root_with_factors() {
+        let (p, q) = (2535196823u64, 42194962807u64);
+        let n = BigUint::from(p) * BigUint::from(q);
+        let m = BigUint::from(0x0123_4567_89ab_cdefu64);
+        let c1 = m.modpow(&BigUint::from(35u32), &n);
+        let c2 = m.modpow(&BigUint::from(55u32), &n);
+        let args = |factors| {
+            common_modulus_attack_multi(
+                n.to_string(),
+                vec!["35".into(), "55".into()],
+                vec![c1.to_string(), c2.to_string()],
+                factors,
+            )
+        };
+
+        assert!(args(None).is_err());
+        let res = args(Some(vec![p.to_string(), q.to_string()])).unwrap();
+        assert_eq!(res.g, "5");
+        assert_eq!(res.plaintext.decimal, m.to_string());
+    }
 }
@@ -0,0 +1,324 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::codec::{parse_bigint, parse_biguint, render_number, NumberView};
+use super::factor::{rsa_from_factors, RsaKeyResult};
+use super::lattice::{coppersmith_shifts, small_roots};
+use super::poly::affine_pow;
+
+// 未指定 ε 时依次尝试的取值, ε 越小格维数越大, 可求的根界越大
+const EPSILON_LADDER: [f64; 5] = [1.0 / 8.0, 1.0 / 12.0, 1.0 / 16.0, 1.0 / 24.0, 1.0 / 32.0];
+// 手动指定 ε 的下限与格维数上限, 防止构造出无法约化的超大格
+const MIN_EPSILON: f64 = 1.0 / 128.0;
+const MAX_LATTICE_DIM: usize = 96;
+
+/// Coppersmith 参数, 按 mode 使用其中的一部分字段
+///
+/// - `prefix`: 已知明文高位, m = known·2^unknown_bits + x, 需要 n、e、c
+/// - `p_msb`: 已知 p 的高位, p = known·2^unknown_bits + x, 需要 n (可选 e、c 解密)
+/// - `poly`: 任意一元多项式 f(x) ≡ 0 (mod b), b >= n^beta, 系数按升幂给出
+#[derive(Deserialize, Default)]
+pub struct CoppersmithParams {
+    pub mode: String,
+    pub n: String,
+    pub e: Option<String>,
+    pub c: Option<String>,
+    pub known: Option<String>,
+    pub unknown_bits: Option<u32>,
+    pub coeffs: Option<Vec<String>>,
+    pub beta: Option<f64>,
+    pub epsilon: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CoppersmithResult {
+    pub roots: Vec<String>,
+    pub epsilon: f64, // 实际求出根时所用的 ε
+    pub p: Option<String>,
+    pub q: Option<String>,
+    pub key: Option<RsaKeyResult>,
+    pub plaintext: Option<NumberView>,
+}
+
+#[tauri::command]
+pub fn coppersmith_attack(params: CoppersmithParams) -> Result<CoppersmithResult, String> {
+    let n = parse_biguint(&params.n).map_err(|err| format!("N 格式错误: {}", err))?;
+    if n <= BigUint::one() {
+        return Err("N 必须大于 1".into());
+    }
+    let field = |value: &Option<String>, name: &str| -> Result<BigUint, String> {
+        let value = value.as_deref().ok_or_else(|| format!("缺少 {}", name))?;
+        parse_biguint(value).map_err(|err| format!("{} 格式错误: {}", name, err))
+    };
+    let unknown_bits = || params.unknown_bits.ok_or("缺少未知部分的位数");
+
+    match params.mode.as_str() {
+        "prefix" => {
+            let e = field(&params.e, "e")?;
+            let c = field(&params.c, "c")?;
+            let known = field(&params.known, "已知明文")?;
+            let k = unknown_bits()?;
+            let e_small = u32::try_from(&e)
+                .ok()
+                .filter(|&e| (2..=64).contains(&e))
+                .ok_or("已知明文高位攻击要求 2 <= e <= 64")?;
+            let beta = params.beta.unwrap_or(1.0);
+            check_bound(&n, k, beta, e_small as usize)?;
+
+            // f(x) = (K·2^k + x)^e - c, 按二项式展开
+            let n_int = to_int(&n);
+            let base = to_int(&(known << k));
+            let mut coeffs = affine_pow(&BigInt::one(), &base, e_small, &n_int);
+            coeffs[0] = (&coeffs[0] - to_int(&c)).mod_floor(&n_int);
+
+            let (roots, epsilon) = search_roots(&coeffs, &n_int, k, beta, params.epsilon)?;
+            let x = roots
+                .iter()
+                .find(|r| r.sign() != Sign::Minus)
+                .ok_or("未找到非负的小根")?;
+            let m = (&base + x).to_biguint().unwrap();
+            Ok(CoppersmithResult {
+                roots: roots.iter().map(|r| r.to_string()).collect(),
+                epsilon,
+                p: None,
+                q: None,
+                key: None,
+                plaintext: Some(render_number(&m)),
+            })
+        }
+        "p_msb" => {
+            let known = field(&params.known, "p 的高位")?;
+            let k = unknown_bits()?;
+            let beta = params.beta.unwrap_or(0.5);
+            check_bound(&n, k, beta, 1)?;
+
+            // f(x) = P·2^k + x ≡ 0 (mod p), p ≈ N^(1/2)
+            let n_int = to_int(&n);
+            let coeffs = vec![to_int(&(known << k)), BigInt::one()];
+            let (roots, epsilon) = search_roots(&coeffs, &n_int, k, beta, params.epsilon)?;
+            let p = roots
+                .iter()
+                .filter_map(|r| (&coeffs[0] + r).to_biguint())
+                .find(|p| *p > BigUint::one() && *p < n && (&n % p).is_zero())
+                .ok_or("求得的小根不能给出 N 的因子")?;
+            let q = &n / &p;
+            let key = match (&params.e, &params.c) {
+                (Some(_), Some(_)) => Some(rsa_from_factors(
+                    &[p.clone(), q.clone()],
+                    &field(&params.e, "e")?,
+                    &field(&params.c, "c")?,
+                )?),
+                _ => None,
+            };
+            Ok(CoppersmithResult {
+                roots: roots.iter().map(|r| r.to_string()).collect(),
+                epsilon,
+                p: Some(p.to_string()),
+                q: Some(q.to_string()),
+                key,
+                plaintext: None,
+            })
+        }
+        "poly" => {
+            let coeffs = params
+                .coeffs
+                .as_ref()
+                .filter(|c| c.len() >= 2)
+                .ok_or("至少需要一次多项式的两个系数")?
+                .iter()
+                .enumerate()
+                .map(|(i, s)| parse_bigint(s).map_err(|err| format!("x^{} 系数: {}", i, err)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let k = unknown_bits()?;
+            let n_int = to_int(&n);
+            let coeffs: Vec<BigInt> = coeffs.iter().map(|c| c.mod_floor(&n_int)).collect();
+            let (roots, epsilon) = search_roots(
+                &coeffs,
+                &n_int,
+                k,
+                params.beta.unwrap_or(1.0),
+                params.epsilon,
+            )?;
+            Ok(CoppersmithResult {
+                roots: roots.iter().map(|r| r.to_string()).collect(),
+                epsilon,
+                p: None,
+                q: None,
+                key: None,
+                plaintext: None,
+            })
+        }
+        other => Err(format!("未知的模式: {}", other)),
+    }
+}
+
+/// 检查 beta 的取值与根界 unknown_bits < log2(N)·β²/deg, 须在按 unknown_bits 移位之前调用
+fn check_bound(n: &BigUint, unknown_bits: u32, beta: f64, degree: usize) -> Result<(), String> {
+    if !(beta > 0.0 && beta <= 1.0) {
+        return Err("beta 必须在 (0, 1] 内".into());
+    }
+    let limit = n.bits() as f64 * beta * beta / degree as f64;
+    if unknown_bits as f64 >= limit {
+        return Err(format!(
+            "未知部分 {} 位超过 Coppersmith 界 (约 {:.0} 位)",
+            unknown_bits, limit
+        ));
+    }
+    Ok(())
+}
+
+/// 按 ε 由大到小依次构造格, 跳过根界明显超出 N^(β²/deg - ε) 的取值
+pub fn search_roots(
+    coeffs: &[BigInt],
+    n: &BigInt,
+    unknown_bits: u32,
+    beta: f64,
+    epsilon: Option<f64>,
+) -> Result<(Vec<BigInt>, f64), String> {
+    check_bound(n.magnitude(), unknown_bits, beta, coeffs.len() - 1)?;
+    let degree = (coeffs.len() - 1) as f64;
+    let x_bound = BigInt::one() << unknown_bits;
+
+    let ladder: Vec<f64> = match epsilon {
+        Some(eps) if eps >= MIN_EPSILON => vec![eps],
+        Some(_) => return Err(format!("epsilon 不能小于 1/{}", 1.0 / MIN_EPSILON)),
+        None => EPSILON_LADDER
+            .iter()
+            .copied()
+            .filter(|eps| unknown_bits as f64 <= n.bits() as f64 * (beta * beta / degree - eps))
+            .collect(),
+    };
+    for eps in ladder.iter().copied() {
+        let (m, t) = coppersmith_shifts(coeffs.len() - 1, beta, eps);
+        let dim = (coeffs.len() - 1) * m + t;
+        if dim > MAX_LATTICE_DIM {
+            return Err(format!(
+                "格维数 {} 超过上限 {} (请增大 epsilon 或 beta)",
+                dim, MAX_LATTICE_DIM
+            ));
+        }
+        let roots = small_roots(coeffs, n, &x_bound, beta, eps);
+        if !roots.is_empty() {
+            return Ok((roots, eps));
+        }
+    }
+    Err("Coppersmith 未找到满足条件的小根 (可尝试减小 epsilon)".into())
+}
+
+fn to_int(v: &BigUint) -> BigInt {
+    BigInt::from_biguint(Sign::Plus, v.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N_256: &str =
+        "51530020546182678294198851407836155777005267024504313283698683227788490135697";
+
+    #[test]
+    fn test_known_prefix_plaintext() {
+        let n: BigUint = N_256.parse().unwrap();
+        let m = BigUint::from_bytes_be(b"flag{stereo:XXXXXX}");
+        let c = m.modpow(&BigUint::from(3u32), &n);
+        let res = coppersmith_attack(CoppersmithParams {
+            mode: "prefix".into(),
+            n: N_256.into(),
+            e: Some("3".into()),
+            c: Some(c.to_string()),
+            known: Some("b'flag{stereo:'".into()),
+            unknown_bits: Some(56),
+            ..Default::default()
+        })
+        .unwrap();
+        let plain = res.plaintext.unwrap();
+        assert_eq!(plain.utf8.as_deref(), Some("flag{stereo:XXXXXX}"));
+
+        // 超大的 unknown_bits 要在移位之前拒绝, 不能先分配 2^k 大小的整数
+        for mode in ["prefix", "p_msb"] {
+            let err = coppersmith_attack(CoppersmithParams {
+                mode: mode.into(),
+                n: N_256.into(),
+                e: Some("3".into()),
+                c: Some(c.to_string()),
+                known: Some("b'flag{'".into()),
+                unknown_bits: Some(u32::MAX),
+                ..Default::default()
+            })
+            .unwrap_err();
+            assert!(err.contains("Coppersmith 界"), "{}", mode);
+        }
+    }
+
+    #[test]
+    fn test_known_msb_of_p() {
+        let p: BigUint = "351078539220351560940160484423438804497".parse().unwrap();
+        let q: BigUint = "264368716054775866813995788202255419117".parse().unwrap();
+        let n = &p * &q;
+        let e = BigUint::from(65537u32);
+        let m = BigUint::from_bytes_be(b"partial p");
+        let c = m.modpow(&e, &n);
+
+        let res = coppersmith_attack(CoppersmithParams {
+            mode: "p_msb".into(),
+            n: n.to_string(),
+            e: Some(e.to_string()),
+            c: Some(c.to_string()),
+            known: Some((&p >> 40u32).to_string()),
+            unknown_bits: Some(40),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(res.p, Some(p.to_string()));
+        assert_eq!(
+            res.key.unwrap().plaintext.utf8.as_deref(),
+            Some("partial p")
+        );
+
+        // 超过 N^(1/4) 的未知位数直接拒绝
+        let err = coppersmith_attack(CoppersmithParams {
+            mode: "p_msb".into(),
+            n: n.to_string(),
+            known: Some((&p >> 70u32).to_string()),
+            unknown_bits: Some(70),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.contains("Coppersmith 界"));
+
+        // ε 过小或 beta 越界都在构造格之前拒绝
+        for (beta, epsilon) in [(None, Some(1e-9)), (Some(1.5), None), (Some(0.05), None)] {
+            assert!(coppersmith_attack(CoppersmithParams {
+                mode: "p_msb".into(),
+                n: n.to_string(),
+                known: Some((&p >> 8u32).to_string()),
+                unknown_bits: Some(8),
+                beta,
+                epsilon,
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_generic_polynomial() {
+        // f(x) = x^2 + a·x - b, 其中 x0 = 0x1234567 是小根
+        let n: BigInt = N_256.parse().unwrap();
+        let x0 = BigInt::from(0x1234567u32);
+        let a = BigInt::from(7u32) << 180u32;
+        let fx: BigInt = &x0 * &x0 + &a * &x0;
+        let b = fx.mod_floor(&n);
+        let res = coppersmith_attack(CoppersmithParams {
+            mode: "poly".into(),
+            n: N_256.into(),
+            coeffs: Some(vec![format!("-{}", b), a.to_string(), "1".into()]),
+            unknown_bits: Some(32),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(res.roots.contains(&x0.to_string()));
+    }
+}
@@ -0,0 +1,22 @@
+#!/usr/bin/env python3
+"""测试用的本地替身 oracle: python3 parity_oracle.py N D [hang]
+
+先输出一行欢迎信息, 之后每行读入十进制密文, 回答明文的奇偶性。
+带 hang 参数时读入查询后不再回答, 用于测试读取超时。
+"""
+import sys
+
+
+def main():
+    n, d = int(sys.argv[1]), int(sys.argv[2])
+    hang = len(sys.argv) > 3 and sys.argv[3] == "hang"
+    print("welcome to the parity oracle", flush=True)
+    for line in sys.stdin:
+        if hang:
+            continue
+        m = pow(int(line.strip()), d, n)
+        print("parity:", "odd" if m & 1 else "even", flush=True)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,559 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_biguint, render_number, NumberView};
+use super::factor::{factorize, FactorContext, FactorProgress, Method};
+use super::number_theory::{crt, is_probable_prime};
+use super::task::CryptoTasks;
+
+pub const DLOG_TASK: &str = "dlog";
+
+const DEFAULT_TIME_LIMIT_SECS: u64 = 120;
+const BSGS_LIMIT: u64 = 1 << 32; // 子群阶不超过该值时用 BSGS, 否则用 Pollard rho
+const CHECK_INTERVAL: u64 = 4096; // 每隔多少步检查一次取消标志
+
+// ===================== 数据结构 =====================
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DlogProgress {
+    pub stage: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DlogResult {
+    pub x: Option<String>,
+    pub modulus: Option<String>, // x 在模该数意义下唯一 (即实际求解的子群阶)
+    pub order: String,           // 分解的群阶 (p - 1 或给定的 g 的阶)
+    pub factors: Vec<String>,    // 群阶的素因子分解, 形如 "q^k"
+    pub remaining: Vec<String>,  // 未能分解的部分, 非空时只能求出部分结果
+    pub verified: bool,          // g^x ≡ h 是否成立
+    pub cancelled: bool,
+    pub plaintext: Option<NumberView>, // 给出 ElGamal 密文时的解密结果
+}
+
+/// 一次离散对数求解的共享上下文
+pub struct DlogContext<'a> {
+    pub cancel: &'a AtomicBool,
+    pub deadline: Instant,
+    pub progress: &'a (dyn Fn(DlogProgress) + Sync),
+}
+
+impl DlogContext<'_> {
+    fn halted(&self, found: &AtomicBool) -> bool {
+        found.load(Ordering::Relaxed)
+            || self.cancel.load(Ordering::Relaxed)
+            || Instant::now() > self.deadline
+    }
+
+    pub fn report(&self, stage: &str, message: String) {
+        (self.progress)(DlogProgress {
+            stage: stage.into(),
+            message,
+        });
+    }
+}
+
+// ===================== Commands =====================
+
+/// 离散对数参数, order 为可选的 g 的阶 (缺省为 p - 1), c1、c2 为可选的 ElGamal 密文
+#[derive(Deserialize, Default)]
+pub struct DlogParams {
+    pub p: String,
+    pub g: String,
+    pub h: String,
+    pub order: Option<String>,
+    pub c1: Option<String>,
+    pub c2: Option<String>,
+    pub time_limit: Option<u64>,
+}
+
+/// 求 g^x ≡ h (mod p): 分解群阶后用 Pohlig-Hellman 拆成素数阶子群, 子群内用 BSGS 或 Pollard rho
+///
+/// 给出 c1、c2 时按 ElGamal 解密 m = c2·c1^(-x)
+#[tauri::command]
+pub async fn discrete_log(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: DlogParams,
+) -> Result<DlogResult, String> {
+    let p = parse_biguint(&params.p).map_err(|err| format!("p 格式错误: {}", err))?;
+    let g = parse_biguint(&params.g).map_err(|err| format!("g 格式错误: {}", err))?;
+    let h = parse_biguint(&params.h).map_err(|err| format!("h 格式错误: {}", err))?;
+    let optional = |v: Option<String>, name: &str| {
+        v.filter(|s| !s.trim().is_empty())
+            .map(|s| parse_biguint(&s).map_err(|err| format!("{} 格式错误: {}", name, err)))
+            .transpose()
+    };
+    let order = optional(params.order, "阶")?;
+    let c1 = optional(params.c1, "c1")?;
+    let c2 = optional(params.c2, "c2")?;
+    if p <= BigUint::from(2u32) || !is_probable_prime(&p) {
+        return Err("p 必须是大于 2 的素数".into());
+    }
+    if (&g % &p).is_zero() || (&h % &p).is_zero() {
+        return Err("g 与 h 不能是 p 的倍数".into());
+    }
+    if order.as_ref().is_some_and(Zero::is_zero) {
+        return Err("阶必须大于 0".into());
+    }
+    let limit = Duration::from_secs(params.time_limit.unwrap_or(DEFAULT_TIME_LIMIT_SECS));
+
+    let handle = tasks.begin(DLOG_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: DlogProgress| {
+            let _ = window.emit("dlog-progress", progress);
+        };
+        let ctx = DlogContext {
+            cancel: &cancel,
+            deadline: Instant::now() + limit,
+            progress: &emit,
+        };
+        let order = order.unwrap_or_else(|| &p - 1u32);
+        let mut result = solve_dlog(&p, &g, &h, &order, &ctx)?;
+
+        if let (Some(c1), Some(c2), Some(x)) = (&c1, &c2, &result.x) {
+            if !result.verified {
+                return Err("离散对数未能完整求出, 无法解密 ElGamal 密文".into());
+            }
+            let m = elgamal_decrypt(&p, &parse_biguint(x)?, c1, c2)?;
+            result.plaintext = Some(render_number(&m));
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("离散对数任务异常退出: {}", e))?
+}
+
+// ===================== 核心算法 =====================
+
+/// 循环群的基本运算, Pohlig-Hellman、BSGS 与 Pollard rho 只依赖这些操作
+pub trait Group: Sync {
+    type E: Clone + Eq + Hash + Send + Sync + Debug;
+
+    fn identity(&self) -> Self::E;
+    fn op(&self, a: &Self::E, b: &Self::E) -> Self::E;
+    fn pow(&self, a: &Self::E, k: &BigUint) -> Self::E;
+    fn inverse(&self, a: &Self::E) -> Result<Self::E, String>;
+
+    /// Pollard rho 的三划分, 默认按哈希值划分
+    fn partition(&self, a: &Self::E) -> u8 {
+        let mut hasher = DefaultHasher::new();
+        a.hash(&mut hasher);
+        (hasher.finish() % 3) as u8
+    }
+}
+
+/// Z_p^* 乘法群
+pub struct MulModP<'a>(pub &'a BigUint);
+
+impl Group for MulModP<'_> {
+    type E = BigUint;
+
+    fn identity(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn op(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % self.0
+    }
+
+    fn pow(&self, a: &BigUint, k: &BigUint) -> BigUint {
+        a.modpow(k, self.0)
+    }
+
+    fn inverse(&self, a: &BigUint) -> Result<BigUint, String> {
+        a.modinv(self.0)
+            .ok_or_else(|| format!("{} 在模 {} 下不可逆", a, self.0))
+    }
+
+    fn partition(&self, a: &BigUint) -> u8 {
+        (a % 3u32).to_u8().unwrap_or(0)
+    }
+}
+
+/// 分解群阶并求解, 群阶只能部分分解时在光滑部分的子群内求出 x 的部分信息
+pub fn solve_dlog(
+    p: &BigUint,
+    g: &BigUint,
+    h: &BigUint,
+    order: &BigUint,
+    ctx: &DlogContext,
+) -> Result<DlogResult, String> {
+    if !g.modpow(order, p).is_one() {
+        return Err("g 的阶不整除给定的群阶".into());
+    }
+
+    let (factors, remaining) = factor_order(order, ctx);
+    let mut result = DlogResult {
+        x: None,
+        modulus: None,
+        order: order.to_string(),
+        factors: factors
+            .iter()
+            .map(|(q, k)| format!("{}^{}", q, k))
+            .collect(),
+        remaining: remaining.iter().map(|r| r.to_string()).collect(),
+        verified: false,
+        cancelled: false,
+        plaintext: None,
+    };
+    if ctx.cancel.load(Ordering::Relaxed) {
+        result.cancelled = true;
+        return Ok(result);
+    }
+
+    let group = MulModP(p);
+    match solve_in_group(&group, g, h, &factors, &remaining, ctx)? {
+        Some((x, modulus)) => {
+            result.verified = g.modpow(&x, p) == h % p;
+            result.x = Some(x.to_string());
+            result.modulus = Some(modulus.to_string());
+        }
+        None => result.cancelled = true,
+    }
+    Ok(result)
+}
+
+/// 分解群阶, 返回 (素数幂分解, 未能分解的合数)
+pub fn factor_order(order: &BigUint, ctx: &DlogContext) -> (Vec<(BigUint, u32)>, Vec<BigUint>) {
+    let factor_progress = |fp: FactorProgress| ctx.report("分解群阶", fp.message);
+    let factor_ctx = FactorContext {
+        cancel: ctx.cancel,
+        deadline: ctx.deadline,
+        progress: &factor_progress,
+    };
+    let (primes, remaining) = factorize(order, &Method::ALL, &factor_ctx);
+    let factors = primes
+        .chunk_by(|a, b| a == b)
+        .map(|chunk| (chunk[0].clone(), chunk.len() as u32))
+        .collect();
+    (factors, remaining)
+}
+
+/// 在任意循环群中求 g^x = h: 未分解的部分通过 g^rest 投影到光滑子群
+///
+/// 返回 (x, x 的模数), 被取消时返回 Ok(None)
+pub fn solve_in_group<G: Group>(
+    group: &G,
+    g: &G::E,
+    h: &G::E,
+    factors: &[(BigUint, u32)],
+    remaining: &[BigUint],
+    ctx: &DlogContext,
+) -> Result<Option<(BigUint, BigUint)>, String> {
+    let rest: BigUint = remaining.iter().product();
+    let g_s = group.pow(g, &rest);
+    let h_s = group.pow(h, &rest);
+    let (sub_order, sub_factors) = element_order(group, &g_s, factors);
+    if group.pow(&h_s, &sub_order) != group.identity() {
+        return Err("h 不在 g 生成的子群中, 无解".into());
+    }
+    ctx.report("Pohlig-Hellman", format!("子群阶 {}", sub_order));
+    Ok(pohlig_hellman(group, &g_s, &h_s, &sub_order, &sub_factors, ctx)?.map(|x| (x, sub_order)))
+}
+
+/// ElGamal 解密: m = c2·(c1^x)^(-1) mod p
+pub fn elgamal_decrypt(
+    p: &BigUint,
+    x: &BigUint,
+    c1: &BigUint,
+    c2: &BigUint,
+) -> Result<BigUint, String> {
+    let s = c1.modpow(x, p).modinv(p).ok_or("c1 与 p 不互质")?;
+    Ok(c2 * s % p)
+}
+
+/// 由群阶的分解求 g 的精确阶, 返回 (阶, 阶的分解)
+pub fn element_order<G: Group>(
+    group: &G,
+    g: &G::E,
+    factors: &[(BigUint, u32)],
+) -> (BigUint, Vec<(BigUint, u32)>) {
+    let mut order: BigUint = factors.iter().map(|(q, k)| q.pow(*k)).product();
+    let mut reduced = Vec::new();
+    for (q, k) in factors {
+        let mut k = *k;
+        while k > 0 && group.pow(g, &(&order / q)) == group.identity() {
+            order /= q;
+            k -= 1;
+        }
+        if k > 0 {
+            reduced.push((q.clone(), k));
+        }
+    }
+    (order, reduced)
+}
+
+/// Pohlig-Hellman: 各素数幂子群并行求解后 CRT 合并, 被取消时返回 Ok(None)
+pub fn pohlig_hellman<G: Group>(
+    group: &G,
+    g: &G::E,
+    h: &G::E,
+    order: &BigUint,
+    factors: &[(BigUint, u32)],
+    ctx: &DlogContext,
+) -> Result<Option<BigUint>, String> {
+    let residues = factors
+        .par_iter()
+        .map(|(q, k)| {
+            let Some(r) = dlog_prime_power(group, g, h, order, q, *k, ctx)? else {
+                return Ok(None);
+            };
+            ctx.report("Pohlig-Hellman", format!("x ≡ {} (mod {}^{})", r, q, k));
+            Ok(Some((r, q.pow(*k))))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let Some(residues) = residues.into_iter().collect::<Option<Vec<_>>>() else {
+        return Ok(None);
+    };
+    if residues.is_empty() {
+        return Ok(Some(BigUint::zero()));
+    }
+    Ok(crt(&residues).ok().map(|(x, _)| x))
+}
+
+/// 在 q^k 阶子群中逐位求 x mod q^k
+fn dlog_prime_power<G: Group>(
+    group: &G,
+    g: &G::E,
+    h: &G::E,
+    order: &BigUint,
+    q: &BigUint,
+    k: u32,
+    ctx: &DlogContext,
+) -> Result<Option<BigUint>, String> {
+    let gamma = group.pow(g, &(order / q)); // q 阶元素
+    let g_inv = group.inverse(g)?;
+    let mut x = BigUint::zero();
+    let mut q_j = BigUint::one();
+    for j in 0..k {
+        // h_j = (g^(-x)·h)^(order / q^(j+1))
+        let t = group.op(h, &group.pow(&g_inv, &x));
+        let h_j = group.pow(&t, &(order / (&q_j * q)));
+        let Some(d) = dlog_prime(group, &gamma, &h_j, q, ctx)? else {
+            return Ok(None);
+        };
+        x += d * &q_j;
+        q_j *= q;
+        if j + 1 < k {
+            ctx.report("Pohlig-Hellman", format!("x mod {}^{} = {}", q, j + 1, x));
+        }
+    }
+    Ok(Some(x))
+}
+
+/// 素数 q 阶子群内的离散对数
+fn dlog_prime<G: Group>(
+    group: &G,
+    gamma: &G::E,
+    beta: &G::E,
+    q: &BigUint,
+    ctx: &DlogContext,
+) -> Result<Option<BigUint>, String> {
+    if *beta == group.identity() {
+        return Ok(Some(BigUint::zero()));
+    }
+    match q.to_u64().filter(|&q| q <= BSGS_LIMIT) {
+        Some(q) => Ok(bsgs(group, gamma, beta, q, ctx)?.map(BigUint::from)),
+        None => Ok(pollard_rho_dlog(group, gamma, beta, q, ctx)),
+    }
+}
+
+/// Baby-step giant-step
+fn bsgs<G: Group>(
+    group: &G,
+    gamma: &G::E,
+    beta: &G::E,
+    q: u64,
+    ctx: &DlogContext,
+) -> Result<Option<u64>, String> {
+    let never = AtomicBool::new(false);
+    let m = (q as f64).sqrt().ceil() as u64;
+    let mut table = HashMap::with_capacity(m as usize);
+    let mut cur = group.identity();
+    for j in 0..m {
+        if j.is_multiple_of(CHECK_INTERVAL) && ctx.halted(&never) {
+            return Ok(None);
+        }
+        table.entry(cur.clone()).or_insert(j);
+        cur = group.op(&cur, gamma);
+    }
+
+    let factor = group.inverse(&group.pow(gamma, &BigUint::from(m)))?;
+    let mut y = beta.clone();
+    for i in 0..m {
+        if let Some(&j) = table.get(&y) {
+            return Ok(Some((i * m + j) % q));
+        }
+        if i.is_multiple_of(CHECK_INTERVAL) && ctx.halted(&never) {
+            return Ok(None);
+        }
+        y = group.op(&y, &factor);
+    }
+    Ok(None)
+}
+
+/// Pollard rho (Floyd 判圈), 多个线程从不同起点并行游走, 任一线程碰撞成功即停止
+fn pollard_rho_dlog<G: Group>(
+    group: &G,
+    gamma: &G::E,
+    beta: &G::E,
+    q: &BigUint,
+    ctx: &DlogContext,
+) -> Option<BigUint> {
+    let found = AtomicBool::new(false);
+    let walkers = rayon::current_num_threads().max(2) as u64;
+    for round in 0u64.. {
+        let result = (0..walkers).into_par_iter().find_map_any(|w| {
+            let seed = round * walkers + w + 1;
+            let d = rho_walk(group, gamma, beta, q, seed, ctx, &found)?;
+            found.store(true, Ordering::Relaxed);
+            Some(d)
+        });
+        if result.is_some() || ctx.halted(&found) {
+            return result;
+        }
+        ctx.report(
+            "Pollard rho",
+            format!("第 {} 轮游走均退化, 更换起点", round + 1),
+        );
+    }
+    None
+}
+
+fn rho_walk<G: Group>(
+    group: &G,
+    gamma: &G::E,
+    beta: &G::E,
+    q: &BigUint,
+    seed: u64,
+    ctx: &DlogContext,
+    found: &AtomicBool,
+) -> Option<BigUint> {
+    // 按划分选择 x·β / x² / x·γ, 同时维护 x = γ^a·β^b
+    let step = |x: &mut G::E, a: &mut BigUint, b: &mut BigUint| match group.partition(x) {
+        0 => {
+            *x = group.op(x, beta);
+            *b = (&*b + 1u32) % q;
+        }
+        1 => {
+            *x = group.op(x, x);
+            *a = (&*a << 1) % q;
+            *b = (&*b << 1) % q;
+        }
+        _ => {
+            *x = group.op(x, gamma);
+            *a = (&*a + 1u32) % q;
+        }
+    };
+
+    let a0 = BigUint::from(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15)) % q;
+    let b0 = BigUint::from(seed.wrapping_mul(0xc2b2_ae3d_27d4_eb4f) | 1) % q;
+    let x0 = group.op(&group.pow(gamma, &a0), &group.pow(beta, &b0));
+    let (mut x, mut a, mut b) = (x0.clone(), a0.clone(), b0.clone());
+    let (mut y, mut ya, mut yb) = (x0, a0, b0);
+
+    let mut next_report = CHECK_INTERVAL * 256;
+    for i in 1u64.. {
+        step(&mut x, &mut a, &mut b);
+        step(&mut y, &mut ya, &mut yb);
+        step(&mut y, &mut ya, &mut yb);
+        if x == y {
+            // γ^a·β^b = γ^ya·β^yb  =>  d·(b - yb) ≡ ya - a (mod q)
+            let r = (&b + q - &yb) % q;
+            let inv = r.modinv(q)?;
+            let d = (&ya + q - &a) % q * inv % q;
+            return (group.pow(gamma, &d) == *beta).then_some(d);
+        }
+        if i.is_multiple_of(CHECK_INTERVAL) && ctx.halted(found) {
+            return None;
+        }
+        if seed == 1 && i >= next_report {
+            next_report *= 2;
+            ctx.report("Pollard rho", format!("子群 {} 已迭代 {} 次", q, i));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(p: &BigUint, g: &BigUint, h: &BigUint, cancel: bool) -> Result<DlogResult, String> {
+        let cancel = AtomicBool::new(cancel);
+        let progress = |_: DlogProgress| {};
+        let ctx = DlogContext {
+            cancel: &cancel,
+            deadline: Instant::now() + Duration::from_secs(60),
+            progress: &progress,
+        };
+        solve_dlog(p, g, h, &(p - 1u32), &ctx)
+    }
+
+    #[test]
+    fn test_pohlig_hellman_with_rho() {
+        // p - 1 = 2^3·3·5·…·47·69229043137, 最大的子群需要 Pollard rho
+        let p: BigUint = "170272925133276893370575812681".parse().unwrap();
+        let g = BigUint::from(59u32);
+        let x: BigUint = "41922059009485275684914649706".parse().unwrap();
+        let h = g.modpow(&x, &p);
+
+        let res = run(&p, &g, &h, false).unwrap();
+        assert!(res.verified);
+        assert_eq!(res.x, Some(x.to_string()));
+        assert!(res.factors.contains(&"69229043137^1".to_string()));
+
+        let res = run(&p, &g, &h, true).unwrap();
+        assert!(res.cancelled && res.x.is_none());
+    }
+
+    #[test]
+    fn test_subgroup_and_elgamal_key() {
+        // 在 2^3·3·5 阶子群中, x 只能确定到模子群阶
+        let p: BigUint = "170272925133276893370575812681".parse().unwrap();
+        let g = BigUint::from(59u32).modpow(&((&p - 1u32) / 120u32), &p);
+        let h = g.modpow(&BigUint::from(77u32), &p);
+        let res = run(&p, &g, &h, false).unwrap();
+        assert_eq!(res.x.as_deref(), Some("77"));
+        assert_eq!(res.modulus.as_deref(), Some("120"));
+
+        // h 不在 g 生成的子群中
+        let err = run(&p, &g, &BigUint::from(59u32), false).unwrap_err();
+        assert!(err.contains("子群"));
+
+        // ElGamal: m = c2·c1^(-x)
+        let m = BigUint::from_bytes_be(b"dlog!");
+        let y = BigUint::from(987_654_321u64);
+        let c1 = g.modpow(&y, &p);
+        let c2 = &m * h.modpow(&y, &p) % &p;
+        let x = parse_biguint(res.x.as_deref().unwrap()).unwrap();
+        assert_eq!(elgamal_decrypt(&p, &x, &c1, &c2).unwrap(), m);
+    }
+
+    #[test]
+    fn test_non_invertible_element() {
+        // 合数模数下不可逆的元素报错, 而不是当作 0 参与运算
+        let n = BigUint::from(15u32);
+        let group = MulModP(&n);
+        assert_eq!(group.inverse(&BigUint::from(2u32)), Ok(BigUint::from(8u32)));
+        assert!(group
+            .inverse(&BigUint::from(6u32))
+            .unwrap_err()
+            .contains("不可逆"));
+    }
+}
@@ -0,0 +1,359 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use base64::{engine::general_purpose, Engine as _};
+use num_integer::Integer;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_bytes, render_bytes, BytesView};
+use super::oracle::{OracleConn, OracleTarget};
+use super::task::CryptoTasks;
+
+pub const ECB_ORACLE_TASK: &str = "ecb_oracle";
+
+const MAX_BLOCK_SIZE: usize = 64;
+const MAX_SECRET_LEN: usize = 4096;
+const ALIGN_TRIES_PER_BYTE: usize = 16; // 随机前缀时每个块长位置平均需要的重试次数
+const LENGTH_SAMPLES: usize = 16; // 判断前缀长度是否随机 (并推断块长) 的查询次数
+
+// 对齐填充、标记块与探测填充使用不同字节, 避免彼此混淆
+const ALIGN_BYTE: u8 = b'Z';
+const MARKER_BYTE: u8 = b'M';
+const FILLER_BYTE: u8 = b'A';
+
+/// ECB 逐字节攻击参数: oracle 返回 E(prefix ‖ input ‖ secret), 求 secret
+///
+/// input_encoding 为发送输入的格式: `hex` (默认) / `b64` / `raw` (原样文本, 只能猜测可打印字符);
+/// template 中的 `{input}` 会被替换为编码后的输入。响应取最后一个词按十六进制或 Base64 解析
+#[derive(Deserialize, Default)]
+pub struct EcbOracleParams {
+    pub target: OracleTarget,
+    pub block_size: Option<usize>,
+    pub input_encoding: Option<String>,
+    pub template: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct EcbOracleProgress {
+    pub found: usize,
+    pub secret: BytesView,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EcbOracleResult {
+    pub block_size: usize,
+    pub prefix_len: Option<usize>, // 前缀长度随机时为 None
+    pub secret: BytesView,
+    pub queries: u64,
+    pub cancelled: bool,
+}
+
+/// ECB byte-at-a-time: 检测块长与 ECB 模式, 用标记块对齐前缀后逐字节字典匹配
+#[tauri::command]
+pub async fn ecb_oracle_attack(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: EcbOracleParams,
+) -> Result<EcbOracleResult, String> {
+    let handle = tasks.begin(ECB_ORACLE_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: EcbOracleProgress| {
+            let _ = window.emit("ecb-oracle-progress", progress);
+        };
+        run_ecb_oracle(&params, &cancel, &emit)
+    })
+    .await
+    .map_err(|e| format!("ECB oracle 任务异常退出: {}", e))?
+}
+
+pub fn run_ecb_oracle(
+    params: &EcbOracleParams,
+    cancel: &AtomicBool,
+    progress: &dyn Fn(EcbOracleProgress),
+) -> Result<EcbOracleResult, String> {
+    let template = params.template.as_deref().unwrap_or("{input}");
+    let encoding = params.input_encoding.as_deref().unwrap_or("hex");
+    if !matches!(encoding, "hex" | "b64" | "raw") {
+        return Err(format!("未知的输入格式: {}", encoding));
+    }
+    let raw = encoding == "raw";
+    let mut conn = OracleConn::open(&params.target)?;
+    let mut oracle = |input: &[u8]| -> Result<Vec<u8>, String> {
+        let encoded = match encoding {
+            "hex" => hex::encode(input),
+            "b64" => general_purpose::STANDARD.encode(input),
+            _ => String::from_utf8_lossy(input).into_owned(),
+        };
+        let resp = conn.query(&template.replace("{input}", &encoded))?;
+        let token = resp.split_whitespace().last().unwrap_or("");
+        parse_bytes(token).map_err(|err| format!("无法解析 oracle 返回的密文: {}", err))
+    };
+
+    if params
+        .block_size
+        .is_some_and(|bs| !(1..=MAX_BLOCK_SIZE).contains(&bs))
+    {
+        return Err(format!("块长必须在 1 到 {} 之间", MAX_BLOCK_SIZE));
+    }
+    // 指定块长时同样要判断前缀长度是否随机
+    let samples = (0..LENGTH_SAMPLES)
+        .map(|_| oracle(&[]).map(|c| c.len()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let random_prefix = samples.iter().any(|&len| len != samples[0]);
+    let bs = match params.block_size {
+        Some(bs) => bs,
+        None => detect_block_size(&samples, random_prefix, &mut oracle)?,
+    };
+    let aligner = Aligner::detect(bs, &mut oracle)?;
+    let prefix_len = (!random_prefix).then_some(aligner.prefix_len);
+
+    // oracle 按行交互, raw 模式不能发送换行等控制字符
+    let candidates: Vec<u8> = if raw {
+        (0x20..0x7f).collect()
+    } else {
+        (0..=255).collect()
+    };
+    let mut secret = Vec::new();
+    let mut cancelled = false;
+    while secret.len() < MAX_SECRET_LEN {
+        if cancel.load(Ordering::Relaxed) {
+            cancelled = true;
+            break;
+        }
+        let k = secret.len() % bs;
+        let block = secret.len() / bs;
+        let filler = vec![FILLER_BYTE; bs - 1 - k];
+        let resp = aligner.query(&filler, &mut oracle)?;
+        let Some(target) = resp.chunks(bs).nth(block) else {
+            break;
+        };
+
+        // 所有候选拼在一次查询里: 每块为 (filler ‖ secret) 的末 bs-1 字节加一个候选字节
+        let mut known = filler.clone();
+        known.extend_from_slice(&secret);
+        let tail = &known[known.len() + 1 - bs..];
+        let dict: Vec<u8> = candidates
+            .iter()
+            .flat_map(|&g| tail.iter().copied().chain([g]))
+            .collect();
+        let resp = aligner.query(&dict, &mut oracle)?;
+        let Some(i) = resp
+            .chunks(bs)
+            .take(candidates.len())
+            .position(|c| c == target)
+        else {
+            break;
+        };
+        secret.push(candidates[i]);
+        progress(EcbOracleProgress {
+            found: secret.len(),
+            secret: render_bytes(&secret),
+        });
+    }
+    // 末尾匹配到的 \x01 是 PKCS#7 填充
+    if !cancelled && secret.last() == Some(&1) {
+        secret.pop();
+    }
+    Ok(EcbOracleResult {
+        block_size: bs,
+        prefix_len,
+        secret: render_bytes(&secret),
+        queries: conn.queries,
+        cancelled,
+    })
+}
+
+/// 逐字节增长输入直到密文长度跳变, 跳变量即块长; 长度不稳定 (随机前缀) 时取多次长度的公约数
+fn detect_block_size<F>(
+    samples: &[usize],
+    random_prefix: bool,
+    oracle: &mut F,
+) -> Result<usize, String>
+where
+    F: FnMut(&[u8]) -> Result<Vec<u8>, String>,
+{
+    if random_prefix {
+        let g = samples.iter().fold(0, |g, len| g.gcd(len));
+        return match g {
+            2..=MAX_BLOCK_SIZE => Ok(g),
+            _ => Err("前缀长度随机, 无法推断块长, 请手动指定".into()),
+        };
+    }
+    let base = samples[0];
+    for n in 1..=MAX_BLOCK_SIZE {
+        let len = oracle(&vec![FILLER_BYTE; n])?.len();
+        if len > base {
+            return Ok(len - base);
+        }
+    }
+    Err("输入增长到最大块长仍未引起密文长度变化".into())
+}
+
+/// 在输入前放置两个相同的标记块, 以它们在密文中的位置定位输入的起点
+struct Aligner {
+    bs: usize,
+    pad: usize,
+    marker: Vec<u8>, // 标记块的密文
+    prefix_len: usize,
+}
+
+impl Aligner {
+    /// 尝试各种对齐填充长度, 出现两个相邻相同密文块即说明是 ECB
+    fn detect<F>(bs: usize, oracle: &mut F) -> Result<Self, String>
+    where
+        F: FnMut(&[u8]) -> Result<Vec<u8>, String>,
+    {
+        for _ in 0..ALIGN_TRIES_PER_BYTE {
+            for pad in 0..bs {
+                let mut input = vec![ALIGN_BYTE; pad];
+                input.extend(vec![MARKER_BYTE; 2 * bs]);
+                let resp = oracle(&input)?;
+                let blocks: Vec<&[u8]> = resp.chunks(bs).collect();
+                // 标记块之前至少有 pad 个字节
+                if let Some(i) = (pad.div_ceil(bs)..blocks.len().saturating_sub(1))
+                    .find(|&i| blocks[i] == blocks[i + 1])
+                {
+                    return Ok(Aligner {
+                        bs,
+                        pad,
+                        marker: blocks[i].to_vec(),
+                        prefix_len: i * bs - pad,
+                    });
+                }
+            }
+        }
+        Err("未发现重复的密文块, 不是 ECB 模式或块长有误".into())
+    }
+
+    /// 发送 对齐填充 ‖ 标记块 × 2 ‖ payload, 返回 payload 起对应的密文; 前缀随机时重试直到对齐
+    fn query<F>(&self, payload: &[u8], oracle: &mut F) -> Result<Vec<u8>, String>
+    where
+        F: FnMut(&[u8]) -> Result<Vec<u8>, String>,
+    {
+        let mut input = vec![ALIGN_BYTE; self.pad];
+        input.extend(vec![MARKER_BYTE; 2 * self.bs]);
+        input.extend_from_slice(payload);
+        for _ in 0..ALIGN_TRIES_PER_BYTE * self.bs {
+            let resp = oracle(&input)?;
+            let blocks: Vec<&[u8]> = resp.chunks(self.bs).collect();
+            if let Some(i) = blocks
+                .windows(2)
+                .position(|w| w[0] == self.marker && w[1] == self.marker)
+            {
+                return Ok(resp[(i + 2) * self.bs..].to_vec());
+            }
+        }
+        Err("多次查询都未能对齐前缀".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const SECRET: &[u8] = b"flag{ecb_is_not_a_mode}\n\x00tail";
+
+    // 测试用的 16 字节玩具分组密码, 只要求确定性与块间独立
+    fn toy_encrypt(block: &[u8]) -> Vec<u8> {
+        let mut state = 0x9e37_79b9_u32;
+        block
+            .iter()
+            .map(|&b| {
+                let out = (state >> 13) as u8 ^ b;
+                state = state.rotate_left(7) ^ (b as u32).wrapping_mul(0x85eb_ca6b);
+                out
+            })
+            .collect()
+    }
+
+    /// 本地替身 oracle: E(prefix ‖ input ‖ SECRET), prefix 长度固定或每次随机
+    fn spawn_oracle(random_prefix: bool) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            stream.set_nodelay(true).unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut seed = 12345u32;
+            for line in BufReader::new(stream).lines() {
+                let input = hex::decode(line.unwrap().trim()).unwrap();
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let prefix_len = if random_prefix {
+                    (seed >> 16) as usize % 40
+                } else {
+                    7
+                };
+                let mut data: Vec<u8> = (0..prefix_len).map(|i| (i * 37 + 11) as u8).collect();
+                data.extend_from_slice(&input);
+                data.extend_from_slice(SECRET);
+                let pad = 16 - data.len() % 16;
+                data.resize(data.len() + pad, pad as u8);
+                let ct: Vec<u8> = data.chunks(16).flat_map(toy_encrypt).collect();
+                if writeln!(writer, "ciphertext: {}", hex::encode(ct)).is_err() {
+                    break;
+                }
+            }
+        });
+        port
+    }
+
+    fn params(port: u16) -> EcbOracleParams {
+        EcbOracleParams {
+            target: OracleTarget {
+                mode: "tcp".into(),
+                address: Some(port.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ecb_fixed_prefix() {
+        let found = std::sync::Mutex::new(0);
+        let progress = |p: EcbOracleProgress| *found.lock().unwrap() = p.found;
+        let res = run_ecb_oracle(
+            &params(spawn_oracle(false)),
+            &AtomicBool::new(false),
+            &progress,
+        )
+        .unwrap();
+        assert_eq!(res.block_size, 16);
+        assert_eq!(res.prefix_len, Some(7));
+        assert_eq!(res.secret.hex, hex::encode(SECRET));
+        assert!(*found.lock().unwrap() >= SECRET.len());
+    }
+
+    #[test]
+    fn test_ecb_random_prefix() {
+        let res = run_ecb_oracle(
+            &params(spawn_oracle(true)),
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(res.block_size, 16);
+        assert_eq!(res.prefix_len, None);
+        assert_eq!(res.secret.flag.as_deref(), Some("flag{ecb_is_not_a_mode}"));
+        assert_eq!(res.secret.hex, hex::encode(SECRET));
+
+        // 手动指定块长时仍能识别随机前缀
+        let res = run_ecb_oracle(
+            &EcbOracleParams {
+                block_size: Some(16),
+                ..params(spawn_oracle(true))
+            },
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(res.prefix_len, None);
+        assert_eq!(res.secret.hex, hex::encode(SECRET));
+    }
+}
@@ -0,0 +1,973 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_bigint, parse_biguint};
+use super::dlog::{element_order, factor_order, solve_in_group, DlogContext, DlogProgress, Group};
+use super::modular_root::sqrt_mod_prime;
+use super::number_theory::is_probable_prime;
+use super::task::CryptoTasks;
+
+pub const ECDLP_TASK: &str = "ecdlp";
+
+const DEFAULT_TIME_LIMIT_SECS: u64 = 120;
+const SMART_LIFTS: u32 = 4; // Smart 攻击提升到 Z_p 时尝试的不同 a' = a + r·p 个数
+const MOV_TRIES: u32 = 64; // MOV 中寻找使配对非退化的辅助点的次数
+
+// ===================== 有限域与曲线 =====================
+
+/// 有限域 (或 Z/mZ) 的基本运算
+pub trait Field: Sync {
+    type E: Clone + Eq + Hash + Send + Sync + Debug;
+
+    fn zero(&self) -> Self::E;
+    fn one(&self) -> Self::E;
+    fn embed(&self, v: &BigUint) -> Self::E;
+    fn add(&self, a: &Self::E, b: &Self::E) -> Self::E;
+    fn sub(&self, a: &Self::E, b: &Self::E) -> Self::E;
+    fn mul(&self, a: &Self::E, b: &Self::E) -> Self::E;
+    fn inv(&self, a: &Self::E) -> Option<Self::E>;
+
+    fn neg(&self, a: &Self::E) -> Self::E {
+        self.sub(&self.zero(), a)
+    }
+
+    fn pow(&self, a: &Self::E, k: &BigUint) -> Self::E {
+        let mut acc = self.one();
+        for i in (0..k.bits()).rev() {
+            acc = self.mul(&acc, &acc);
+            if k.bit(i) {
+                acc = self.mul(&acc, a);
+            }
+        }
+        acc
+    }
+}
+
+/// Z/mZ, m 为素数时即 F_p
+pub struct Zmod(pub BigUint);
+
+impl Field for Zmod {
+    type E = BigUint;
+
+    fn zero(&self) -> BigUint {
+        BigUint::zero()
+    }
+
+    fn one(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn embed(&self, v: &BigUint) -> BigUint {
+        v % &self.0
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + b) % &self.0
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + &self.0 - b % &self.0) % &self.0
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % &self.0
+    }
+
+    fn inv(&self, a: &BigUint) -> Option<BigUint> {
+        a.modinv(&self.0)
+    }
+
+    fn pow(&self, a: &BigUint, k: &BigUint) -> BigUint {
+        a.modpow(k, &self.0)
+    }
+}
+
+/// F_p² = F_p[t] / (t² - beta), beta 为最小的二次非剩余, 元素表示为 (c0, c1) = c0 + c1·t
+pub struct Fp2 {
+    base: Zmod,
+    beta: BigUint,
+}
+
+impl Fp2 {
+    pub fn new(p: &BigUint) -> Self {
+        let beta = (2u32..)
+            .map(BigUint::from)
+            .find(|z| sqrt_mod_prime(z, p).is_none())
+            .unwrap();
+        Fp2 {
+            base: Zmod(p.clone()),
+            beta,
+        }
+    }
+
+    /// 平方根, 非平方元素返回 None
+    ///
+    /// F_p 中的元素在 F_p² 中总有平方根 (非二次剩余时落在 t·F_p 上);
+    /// 一般元素先求范数 N = c0² - beta·c1² 的平方根 n, 再由 x0² = (c0 ± n)/2, x1 = c1/(2·x0) 得出
+    fn sqrt(&self, a: &(BigUint, BigUint)) -> Option<(BigUint, BigUint)> {
+        let f = &self.base;
+        let p = &f.0;
+        if a.1.is_zero() {
+            return Some(match sqrt_mod_prime(&a.0, p) {
+                Some(r) => (r, BigUint::zero()),
+                None => {
+                    let r = sqrt_mod_prime(&f.mul(&a.0, &f.inv(&self.beta)?), p)?;
+                    (BigUint::zero(), r)
+                }
+            });
+        }
+        let norm = f.sub(&f.mul(&a.0, &a.0), &f.mul(&self.beta, &f.mul(&a.1, &a.1)));
+        let n = sqrt_mod_prime(&norm, p)?;
+        let half = f.inv(&BigUint::from(2u32))?;
+        [f.add(&a.0, &n), f.sub(&a.0, &n)]
+            .iter()
+            .filter_map(|c| sqrt_mod_prime(&f.mul(c, &half), p))
+            .filter(|x0| !x0.is_zero())
+            .map(|x0| {
+                let x1 = f.mul(&a.1, &f.inv(&f.add(&x0, &x0)).unwrap());
+                (x0, x1)
+            })
+            .find(|r| self.mul(r, r) == *a)
+    }
+}
+
+impl Field for Fp2 {
+    type E = (BigUint, BigUint);
+
+    fn zero(&self) -> Self::E {
+        (BigUint::zero(), BigUint::zero())
+    }
+
+    fn one(&self) -> Self::E {
+        (BigUint::one(), BigUint::zero())
+    }
+
+    fn embed(&self, v: &BigUint) -> Self::E {
+        (v % &self.base.0, BigUint::zero())
+    }
+
+    fn add(&self, a: &Self::E, b: &Self::E) -> Self::E {
+        (self.base.add(&a.0, &b.0), self.base.add(&a.1, &b.1))
+    }
+
+    fn sub(&self, a: &Self::E, b: &Self::E) -> Self::E {
+        (self.base.sub(&a.0, &b.0), self.base.sub(&a.1, &b.1))
+    }
+
+    fn mul(&self, a: &Self::E, b: &Self::E) -> Self::E {
+        let f = &self.base;
+        let c0 = f.add(&f.mul(&a.0, &b.0), &f.mul(&self.beta, &f.mul(&a.1, &b.1)));
+        let c1 = f.add(&f.mul(&a.0, &b.1), &f.mul(&a.1, &b.0));
+        (c0, c1)
+    }
+
+    fn inv(&self, a: &Self::E) -> Option<Self::E> {
+        // (c0 + c1·t)^(-1) = (c0 - c1·t) / (c0² - beta·c1²)
+        let f = &self.base;
+        let norm = f.sub(&f.mul(&a.0, &a.0), &f.mul(&self.beta, &f.mul(&a.1, &a.1)));
+        let inv = f.inv(&norm)?;
+        Some((f.mul(&a.0, &inv), f.mul(&f.neg(&a.1), &inv)))
+    }
+}
+
+/// 域的乘法群
+pub struct Units<'a, F: Field>(pub &'a F);
+
+impl<F: Field> Group for Units<'_, F> {
+    type E = F::E;
+
+    fn identity(&self) -> F::E {
+        self.0.one()
+    }
+
+    fn op(&self, a: &F::E, b: &F::E) -> F::E {
+        self.0.mul(a, b)
+    }
+
+    fn pow(&self, a: &F::E, k: &BigUint) -> F::E {
+        self.0.pow(a, k)
+    }
+
+    fn inverse(&self, a: &F::E) -> Result<F::E, String> {
+        self.0.inv(a).ok_or_else(|| "域中的 0 元素不可逆".into())
+    }
+}
+
+/// 仿射点, None 为无穷远点 O
+pub type Point<E> = Option<(E, E)>;
+
+/// 短 Weierstrass 曲线 y² = x³ + a·x + b
+pub struct Curve<F: Field> {
+    pub field: F,
+    pub a: F::E,
+    pub b: F::E,
+}
+
+impl<F: Field> Curve<F> {
+    pub fn contains(&self, pt: &Point<F::E>) -> bool {
+        let f = &self.field;
+        match pt {
+            None => true,
+            Some((x, y)) => f.mul(y, y) == self.rhs(x),
+        }
+    }
+
+    /// x³ + a·x + b
+    fn rhs(&self, x: &F::E) -> F::E {
+        let f = &self.field;
+        let x3 = f.mul(&f.mul(x, x), x);
+        f.add(&f.add(&x3, &f.mul(&self.a, x)), &self.b)
+    }
+
+    /// 过 p1、p2 的直线斜率, 竖直线 (p1 = -p2) 时返回 None
+    fn slope(&self, p1: &(F::E, F::E), p2: &(F::E, F::E)) -> Option<F::E> {
+        let f = &self.field;
+        if p1.0 == p2.0 {
+            if f.add(&p1.1, &p2.1) == f.zero() {
+                return None;
+            }
+            let three = f.embed(&BigUint::from(3u32));
+            let num = f.add(&f.mul(&three, &f.mul(&p1.0, &p1.0)), &self.a);
+            return Some(f.mul(&num, &f.inv(&f.add(&p1.1, &p1.1))?));
+        }
+        Some(f.mul(&f.sub(&p2.1, &p1.1), &f.inv(&f.sub(&p2.0, &p1.0))?))
+    }
+
+    fn chord(&self, p1: &(F::E, F::E), p2: &(F::E, F::E), lambda: &F::E) -> (F::E, F::E) {
+        let f = &self.field;
+        let x3 = f.sub(&f.sub(&f.mul(lambda, lambda), &p1.0), &p2.0);
+        let y3 = f.sub(&f.mul(lambda, &f.sub(&p1.0, &x3)), &p1.1);
+        (x3, y3)
+    }
+
+    pub fn add(&self, p1: &Point<F::E>, p2: &Point<F::E>) -> Point<F::E> {
+        match (p1, p2) {
+            (None, _) => p2.clone(),
+            (_, None) => p1.clone(),
+            (Some(a), Some(b)) => self.slope(a, b).map(|l| self.chord(a, b, &l)),
+        }
+    }
+
+    pub fn neg(&self, pt: &Point<F::E>) -> Point<F::E> {
+        pt.as_ref().map(|(x, y)| (x.clone(), self.field.neg(y)))
+    }
+
+    pub fn mul(&self, pt: &Point<F::E>, k: &BigUint) -> Point<F::E> {
+        let mut acc = None;
+        for i in (0..k.bits()).rev() {
+            acc = self.add(&acc, &acc);
+            if k.bit(i) {
+                acc = self.add(&acc, pt);
+            }
+        }
+        acc
+    }
+}
+
+impl<F: Field> Group for Curve<F> {
+    type E = Point<F::E>;
+
+    fn identity(&self) -> Self::E {
+        None
+    }
+
+    fn op(&self, a: &Self::E, b: &Self::E) -> Self::E {
+        self.add(a, b)
+    }
+
+    fn pow(&self, a: &Self::E, k: &BigUint) -> Self::E {
+        self.mul(a, k)
+    }
+
+    fn inverse(&self, a: &Self::E) -> Result<Self::E, String> {
+        Ok(self.neg(a))
+    }
+}
+
+// ===================== Commands =====================
+
+#[derive(Deserialize, Default)]
+pub struct EccCurveParams {
+    pub p: String,
+    pub a: String,
+    pub b: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct EccPointParams {
+    pub x: String,
+    pub y: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EccPointView {
+    pub infinity: bool,
+    pub x: Option<String>,
+    pub y: Option<String>,
+}
+
+/// ECDLP 参数, order 为 G 的阶 (未给出时只能尝试 Smart 攻击与奇异曲线)
+///
+/// method: `auto` (默认) / `ph` / `smart` / `mov`, 奇异曲线总是走奇异映射
+#[derive(Deserialize, Default)]
+pub struct EcdlpParams {
+    pub curve: EccCurveParams,
+    pub g: EccPointParams,
+    pub q: EccPointParams,
+    pub order: Option<String>,
+    pub method: Option<String>,
+    pub time_limit: Option<u64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EcdlpResult {
+    pub k: Option<String>,
+    pub modulus: Option<String>, // k 在模该数意义下唯一
+    pub method: String,
+    pub checks: Vec<String>, // 依次做过的判定与检查
+    pub verified: bool,      // k·G = Q 是否成立
+    pub cancelled: bool,
+}
+
+#[tauri::command]
+pub fn ecc_point_add(
+    curve: EccCurveParams,
+    p1: EccPointParams,
+    p2: EccPointParams,
+) -> Result<EccPointView, String> {
+    let curve = parse_curve(&curve)?;
+    let p1 = Some(parse_point(&curve, &p1, "P1")?);
+    let p2 = Some(parse_point(&curve, &p2, "P2")?);
+    Ok(point_view(&curve.add(&p1, &p2)))
+}
+
+/// k·P, k 可以为负数
+#[tauri::command]
+pub fn ecc_scalar_mul(
+    curve: EccCurveParams,
+    point: EccPointParams,
+    k: String,
+) -> Result<EccPointView, String> {
+    let curve = parse_curve(&curve)?;
+    let pt = Some(parse_point(&curve, &point, "P")?);
+    let k = parse_bigint(&k).map_err(|err| format!("k 格式错误: {}", err))?;
+    let res = curve.mul(&pt, k.magnitude());
+    Ok(point_view(&match k.sign() {
+        Sign::Minus => curve.neg(&res),
+        _ => res,
+    }))
+}
+
+/// 求 k·G = Q: 奇异曲线映射到 F_p 的加法或乘法群, 异常曲线用 Smart 攻击,
+/// 嵌入度 <= 2 时用 MOV 约化到 F_p², 其余情况在曲线上做 Pohlig-Hellman
+#[tauri::command]
+pub async fn ecc_discrete_log(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: EcdlpParams,
+) -> Result<EcdlpResult, String> {
+    let curve = parse_curve(&params.curve)?;
+    let g = parse_point(&curve, &params.g, "G")?;
+    let q = parse_point(&curve, &params.q, "Q")?;
+    let order = params
+        .order
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_biguint(&s).map_err(|err| format!("阶格式错误: {}", err)))
+        .transpose()?;
+    let method = params.method.unwrap_or_else(|| "auto".into());
+    let limit = Duration::from_secs(params.time_limit.unwrap_or(DEFAULT_TIME_LIMIT_SECS));
+
+    let handle = tasks.begin(ECDLP_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: DlogProgress| {
+            let _ = window.emit("ecdlp-progress", progress);
+        };
+        let ctx = DlogContext {
+            cancel: &cancel,
+            deadline: Instant::now() + limit,
+            progress: &emit,
+        };
+        solve_ecdlp(&curve, &g, &q, order.as_ref(), &method, &ctx)
+    })
+    .await
+    .map_err(|e| format!("ECDLP 任务异常退出: {}", e))?
+}
+
+fn parse_curve(params: &EccCurveParams) -> Result<Curve<Zmod>, String> {
+    let p = parse_biguint(&params.p).map_err(|err| format!("p 格式错误: {}", err))?;
+    let a = parse_bigint(&params.a).map_err(|err| format!("a 格式错误: {}", err))?;
+    let b = parse_bigint(&params.b).map_err(|err| format!("b 格式错误: {}", err))?;
+    if p <= BigUint::from(3u32) || !is_probable_prime(&p) {
+        return Err("p 必须是大于 3 的素数".into());
+    }
+    let p_int = BigInt::from_biguint(Sign::Plus, p.clone());
+    let reduce = |v: BigInt| v.mod_floor(&p_int).to_biguint().unwrap();
+    Ok(Curve {
+        a: reduce(a),
+        b: reduce(b),
+        field: Zmod(p),
+    })
+}
+
+fn parse_point(
+    curve: &Curve<Zmod>,
+    params: &EccPointParams,
+    name: &str,
+) -> Result<(BigUint, BigUint), String> {
+    let x = parse_biguint(&params.x).map_err(|err| format!("{}.x 格式错误: {}", name, err))?;
+    let y = parse_biguint(&params.y).map_err(|err| format!("{}.y 格式错误: {}", name, err))?;
+    let pt = (curve.field.embed(&x), curve.field.embed(&y));
+    if !curve.contains(&Some(pt.clone())) {
+        return Err(format!("{} 不在曲线上", name));
+    }
+    Ok(pt)
+}
+
+fn point_view(pt: &Point<BigUint>) -> EccPointView {
+    EccPointView {
+        infinity: pt.is_none(),
+        x: pt.as_ref().map(|(x, _)| x.to_string()),
+        y: pt.as_ref().map(|(_, y)| y.to_string()),
+    }
+}
+
+// ===================== 求解 =====================
+
+pub fn solve_ecdlp(
+    curve: &Curve<Zmod>,
+    g: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+    order: Option<&BigUint>,
+    method: &str,
+    ctx: &DlogContext,
+) -> Result<EcdlpResult, String> {
+    let f = &curve.field;
+    let p = &f.0;
+    let mut result = EcdlpResult {
+        k: None,
+        modulus: None,
+        method: String::new(),
+        checks: vec!["p 为素数, G、Q 在曲线上".into()],
+        verified: false,
+        cancelled: false,
+    };
+
+    let a3 = f.pow(&curve.a, &BigUint::from(3u32));
+    let disc = f.add(
+        &f.mul(&BigUint::from(4u32), &a3),
+        &f.mul(&BigUint::from(27u32), &f.mul(&curve.b, &curve.b)),
+    );
+    let solved = if disc.is_zero() {
+        result.checks.push("判别式 4a³ + 27b² ≡ 0, 曲线奇异".into());
+        singular_dlog(curve, g, q, ctx, &mut result)?
+    } else {
+        result.checks.push("判别式非零, 曲线非奇异".into());
+        let anomalous = order.is_none_or(|n| n == p);
+        if method == "smart" || (method == "auto" && anomalous) {
+            result.method = "Smart 攻击".into();
+            ctx.report("Smart", "提升到 Z/p³ 计算 p 进椭圆对数".into());
+            if let Some(k) = smart_attack(curve, g, q, ctx) {
+                result
+                    .checks
+                    .push("p·G 落在 E1 中, #E = p (异常曲线)".into());
+                finish(curve, g, q, Some((k, p.clone())), &mut result);
+                return Ok(result);
+            }
+            result
+                .checks
+                .push("Smart 攻击失败, 曲线不是异常曲线".into());
+            if method == "smart" {
+                return Err("Smart 攻击失败, 曲线不是异常曲线".into());
+            }
+        }
+        let n = order.ok_or("未给出 G 的阶且曲线不是异常曲线, 无法继续")?;
+        if n.is_zero() {
+            return Err("阶不能为 0".into());
+        }
+        if curve.mul(&Some(g.clone()), n).is_some() {
+            return Err("n·G ≠ O, 给定的阶有误".into());
+        }
+        result.checks.push("n·G = O".into());
+
+        let (factors, remaining) = factor_order(n, ctx);
+        let mut desc: Vec<String> = factors
+            .iter()
+            .map(|(q, k)| match k {
+                1 => q.to_string(),
+                _ => format!("{}^{}", q, k),
+            })
+            .collect();
+        desc.extend(remaining.iter().map(|r| format!("{} (未分解)", r)));
+        result.checks.push(format!("n = {}", desc.join(" · ")));
+
+        let degree = (1..=2u32).find(|k| (p.pow(*k) % n).is_one());
+        match degree {
+            Some(k) => result
+                .checks
+                .push(format!("嵌入度 k = {} (n | p^{} - 1), MOV 约化适用", k, k)),
+            None => result.checks.push("嵌入度 > 2, MOV 约化不适用".into()),
+        }
+        match (method, degree) {
+            ("mov", None) => return Err("嵌入度大于 2, 无法做 MOV 约化".into()),
+            ("mov", Some(_)) | ("auto", Some(_)) => {
+                result.method = "MOV 约化 + Pohlig-Hellman (F_p²)".into();
+                match mov_dlog(curve, g, q, n, &factors, &remaining, ctx, &mut result) {
+                    Ok(solved) => solved,
+                    Err(err) if method == "auto" => {
+                        result.checks.push(format!("{}, 改为在曲线上求解", err));
+                        result.method = "Pohlig-Hellman".into();
+                        curve_dlog(curve, g, q, &factors, &remaining, ctx)?
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            ("ph", _) | ("auto", None) => {
+                result.method = "Pohlig-Hellman".into();
+                curve_dlog(curve, g, q, &factors, &remaining, ctx)?
+            }
+            (other, _) => return Err(format!("未知的方法: {}", other)),
+        }
+    };
+    finish(curve, g, q, solved, &mut result);
+    Ok(result)
+}
+
+fn curve_dlog(
+    curve: &Curve<Zmod>,
+    g: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+    factors: &[(BigUint, u32)],
+    remaining: &[BigUint],
+    ctx: &DlogContext,
+) -> Result<Option<(BigUint, BigUint)>, String> {
+    let (g, q) = (Some(g.clone()), Some(q.clone()));
+    solve_in_group(curve, &g, &q, factors, remaining, ctx)
+}
+
+fn finish(
+    curve: &Curve<Zmod>,
+    g: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+    solved: Option<(BigUint, BigUint)>,
+    result: &mut EcdlpResult,
+) {
+    let Some((k, modulus)) = solved else {
+        result.cancelled = true;
+        return;
+    };
+    result.verified = curve.mul(&Some(g.clone()), &k) == Some(q.clone());
+    result.checks.push(if result.verified {
+        "k·G = Q 验证通过".into()
+    } else {
+        "k·G ≠ Q, 验证失败".into()
+    });
+    result.k = Some(k.to_string());
+    result.modulus = Some(modulus.to_string());
+}
+
+/// 奇异曲线: 尖点时 (x, y) ↦ x/y 映射到 F_p 加法群,
+/// 结点时 (x, y) ↦ (y + s(x - α)) / (y - s(x - α)) 映射到 F_p^* 或 F_p² 的范数 1 子群
+fn singular_dlog(
+    curve: &Curve<Zmod>,
+    g: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+    ctx: &DlogContext,
+    result: &mut EcdlpResult,
+) -> Result<Option<(BigUint, BigUint)>, String> {
+    let f = &curve.field;
+    let p = &f.0;
+    if curve.a.is_zero() {
+        // y² = x³, 奇点为 (0, 0)
+        result.method = "奇异曲线 (尖点) → F_p 加法群".into();
+        result.checks.push("尖点 y² = x³, 映射 (x, y) ↦ x/y".into());
+        let t = |(x, y): &(BigUint, BigUint)| f.inv(y).map(|inv| f.mul(x, &inv));
+        let (tg, tq) = (t(g), t(q));
+        let (Some(tg), Some(tq)) = (tg, tq) else {
+            return Err("G 或 Q 是奇点".into());
+        };
+        let inv = f.inv(&tg).ok_or("G 映射为 0, 无法求解")?;
+        return Ok(Some((f.mul(&tq, &inv), p.clone())));
+    }
+
+    // y² = (x - α)²(x - β), α = -3b/(2a), β = 3b/a
+    let three_b = f.mul(&BigUint::from(3u32), &curve.b);
+    let alpha = f.neg(&f.mul(&three_b, &f.inv(&f.add(&curve.a, &curve.a)).unwrap()));
+    let beta = f.mul(&three_b, &f.inv(&curve.a).unwrap());
+    if g.0 == alpha || q.0 == alpha {
+        return Err("G 或 Q 是奇点".into());
+    }
+    let d = f.sub(&alpha, &beta);
+    result.checks.push(format!(
+        "结点 (α, 0), α = {}, 切线斜率平方 α - β = {}",
+        alpha, d
+    ));
+
+    let ext = Fp2::new(p);
+    let s = ext.sqrt(&ext.embed(&d)).unwrap();
+    let phi = |(x, y): &(BigUint, BigUint)| {
+        let u = ext.mul(&s, &ext.embed(&f.sub(x, &alpha)));
+        let y = ext.embed(y);
+        ext.mul(&ext.add(&y, &u), &ext.inv(&ext.sub(&y, &u)).unwrap())
+    };
+    let (hg, hq) = (phi(g), phi(q));
+    let order = if s.1.is_zero() {
+        result.method = "奇异曲线 (分裂结点) → F_p^*".into();
+        result
+            .checks
+            .push("α - β 为二次剩余, 映射到 F_p^*, 群阶 p - 1".into());
+        p - 1u32
+    } else {
+        result.method = "奇异曲线 (非分裂结点) → F_p² 范数 1 子群".into();
+        result
+            .checks
+            .push("α - β 为二次非剩余, 映射到 F_p², 群阶 p + 1".into());
+        p + 1u32
+    };
+    let (factors, remaining) = factor_order(&order, ctx);
+    solve_in_group(&Units(&ext), &hg, &hq, &factors, &remaining, ctx)
+}
+
+/// Smart 攻击: 把 G、Q 提升到 Z/p³ 上的曲线, p·P 落在 E1 中, 其 p 进椭圆对数给出 F_p 加法群中的同构像
+fn smart_attack(
+    curve: &Curve<Zmod>,
+    g: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+    ctx: &DlogContext,
+) -> Option<BigUint> {
+    let p = &curve.field.0;
+    let ring = Zmod(p.pow(3));
+    // a' = a 时提升可能恰为典范提升 (攻击失效), 依次换用 a + r·p
+    for r in 0..SMART_LIFTS {
+        let a = &curve.a + p * r;
+        if let (Some(tg), Some(tq)) = (
+            p_adic_log(&ring, p, &a, &curve.b, g),
+            p_adic_log(&ring, p, &a, &curve.b, q),
+        ) {
+            if let Some(inv) = tg.modinv(p) {
+                return Some(tq * inv % p);
+            }
+        }
+        ctx.report("Smart", format!("a' = a + {}·p 的提升退化, 换用下一个", r));
+    }
+    None
+}
+
+/// Hensel 提升 y 后计算 p·P = (X : Y : Z), 返回 -x/y 除以 p 后模 p 的值 (即 X·(Z/p)/Y, 省略符号)
+fn p_adic_log(
+    ring: &Zmod,
+    p: &BigUint,
+    a: &BigUint,
+    b: &BigUint,
+    pt: &(BigUint, BigUint),
+) -> Option<BigUint> {
+    let (x, mut y) = pt.clone();
+    let rhs = ring.add(
+        &ring.mul(&ring.mul(&x, &x), &x),
+        &ring.add(&ring.mul(a, &x), b),
+    );
+    // 牛顿迭代两次, 精度 p → p² → p⁴
+    for _ in 0..2 {
+        let inv = ring.inv(&ring.add(&y, &y))?;
+        y = ring.sub(&y, &ring.mul(&ring.sub(&ring.mul(&y, &y), &rhs), &inv));
+    }
+    let (xx, yy, zz) = jacobian_mul(ring, a, &(x, y, BigUint::one()), p);
+    if zz.is_zero() || !(&zz % p).is_zero() {
+        return None;
+    }
+    let t = xx * (&zz / p) % p;
+    Some(t * (yy % p).modinv(p)? % p)
+}
+
+type Jacobian = (BigUint, BigUint, BigUint);
+
+fn jacobian_double(r: &Zmod, a: &BigUint, (x, y, z): &Jacobian) -> Jacobian {
+    if z.is_zero() || y.is_zero() {
+        return (BigUint::one(), BigUint::one(), BigUint::zero());
+    }
+    let yy = r.mul(y, y);
+    let zz = r.mul(z, z);
+    let s = r.mul(&BigUint::from(4u32), &r.mul(x, &yy));
+    let m = r.add(
+        &r.mul(&BigUint::from(3u32), &r.mul(x, x)),
+        &r.mul(a, &r.mul(&zz, &zz)),
+    );
+    let x3 = r.sub(&r.mul(&m, &m), &r.add(&s, &s));
+    let y3 = r.sub(
+        &r.mul(&m, &r.sub(&s, &x3)),
+        &r.mul(&BigUint::from(8u32), &r.mul(&yy, &yy)),
+    );
+    let z3 = r.mul(&r.add(y, y), z);
+    (x3, y3, z3)
+}
+
+fn jacobian_add(r: &Zmod, a: &BigUint, p1: &Jacobian, p2: &Jacobian) -> Jacobian {
+    if p1.2.is_zero() {
+        return p2.clone();
+    }
+    if p2.2.is_zero() {
+        return p1.clone();
+    }
+    let z1z1 = r.mul(&p1.2, &p1.2);
+    let z2z2 = r.mul(&p2.2, &p2.2);
+    let u1 = r.mul(&p1.0, &z2z2);
+    let u2 = r.mul(&p2.0, &z1z1);
+    let s1 = r.mul(&p1.1, &r.mul(&p2.2, &z2z2));
+    let s2 = r.mul(&p2.1, &r.mul(&p1.2, &z1z1));
+    let h = r.sub(&u2, &u1);
+    let rr = r.sub(&s2, &s1);
+    if h.is_zero() {
+        if rr.is_zero() {
+            return jacobian_double(r, a, p1);
+        }
+        return (BigUint::one(), BigUint::one(), BigUint::zero());
+    }
+    let hh = r.mul(&h, &h);
+    let hhh = r.mul(&hh, &h);
+    let v = r.mul(&u1, &hh);
+    let x3 = r.sub(&r.sub(&r.mul(&rr, &rr), &hhh), &r.add(&v, &v));
+    let y3 = r.sub(&r.mul(&rr, &r.sub(&v, &x3)), &r.mul(&s1, &hhh));
+    let z3 = r.mul(&h, &r.mul(&p1.2, &p2.2));
+    (x3, y3, z3)
+}
+
+fn jacobian_mul(r: &Zmod, a: &BigUint, pt: &Jacobian, k: &BigUint) -> Jacobian {
+    let mut acc = (BigUint::one(), BigUint::one(), BigUint::zero());
+    for i in (0..k.bits()).rev() {
+        acc = jacobian_double(r, a, &acc);
+        if k.bit(i) {
+            acc = jacobian_add(r, a, &acc, pt);
+        }
+    }
+    acc
+}
+
+/// MOV: 用约化 Tate 配对 e(·, R) 把 <G> 嵌入 F_p² 的 n 阶子群, 再在其中做 Pohlig-Hellman
+#[allow(clippy::too_many_arguments)]
+fn mov_dlog(
+    curve: &Curve<Zmod>,
+    g: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+    n: &BigUint,
+    factors: &[(BigUint, u32)],
+    remaining: &[BigUint],
+    ctx: &DlogContext,
+    result: &mut EcdlpResult,
+) -> Result<Option<(BigUint, BigUint)>, String> {
+    let f = &curve.field;
+    let p = &f.0;
+    let lifted = Curve {
+        a: (curve.a.clone(), BigUint::zero()),
+        b: (curve.b.clone(), BigUint::zero()),
+        field: Fp2::new(p),
+    };
+    let ext = &lifted.field;
+    let embed = |(x, y): &(BigUint, BigUint)| (ext.embed(x), ext.embed(y));
+    let (g2, q2) = (embed(g), embed(q));
+    let exponent = (p * p - 1u32) / n;
+    let units = Units(ext);
+    // 未分解部分按一次因子参与阶的判定
+    let all_factors: Vec<(BigUint, u32)> = factors
+        .iter()
+        .cloned()
+        .chain(remaining.iter().map(|r| (r.clone(), 1)))
+        .collect();
+
+    // 辅助点 R 取 x = k + t ∈ F_p² \ F_p, 使其在 E(F_p²)/nE(F_p²) 中足够一般
+    for x in (1..=MOV_TRIES).map(|k| (BigUint::from(k), BigUint::one())) {
+        let Some(y) = ext.sqrt(&lifted.rhs(&x)) else {
+            continue;
+        };
+        let r = (x, y);
+        let Some(alpha) = tate_pairing(&lifted, &g2, &r, n, &exponent) else {
+            continue;
+        };
+        if element_order(&units, &alpha, &all_factors).0 != *n {
+            continue;
+        }
+        let Some(beta) = tate_pairing(&lifted, &q2, &r, n, &exponent) else {
+            continue;
+        };
+        result.checks.push(format!(
+            "e(G, R) = {} + {}·t, e(Q, R) = {} + {}·t (t² = {})",
+            alpha.0, alpha.1, beta.0, beta.1, ext.beta
+        ));
+        ctx.report("MOV", format!("辅助点 x = {} + t, 配对非退化", r.0 .0));
+        return solve_in_group(&units, &alpha, &beta, factors, remaining, ctx);
+    }
+    Err("未找到使配对非退化的辅助点".into())
+}
+
+/// 约化 Tate 配对 f_{n,P}(R)^((p² - 1)/n)
+fn tate_pairing<F: Field>(
+    curve: &Curve<F>,
+    pt: &(F::E, F::E),
+    r: &(F::E, F::E),
+    n: &BigUint,
+    exponent: &BigUint,
+) -> Option<F::E> {
+    let f = &curve.field;
+    let (mut num, mut den) = (f.one(), f.one());
+    let base = Some(pt.clone());
+    let mut t = base.clone();
+    for i in (0..n.bits() - 1).rev() {
+        num = f.mul(&num, &num);
+        den = f.mul(&den, &den);
+        let (l, v, sum) = miller_line(curve, &t, &t, r);
+        num = f.mul(&num, &l);
+        den = f.mul(&den, &v);
+        t = sum;
+        if n.bit(i) {
+            let (l, v, sum) = miller_line(curve, &t, &base, r);
+            num = f.mul(&num, &l);
+            den = f.mul(&den, &v);
+            t = sum;
+        }
+    }
+    let value = f.mul(&num, &f.inv(&den)?);
+    (value != f.zero()).then(|| f.pow(&value, exponent))
+}
+
+/// 返回 (l_{A,B}(R), v_{A+B}(R), A + B), l 为过 A、B 的直线, v 为过 A + B 的竖直线
+fn miller_line<F: Field>(
+    curve: &Curve<F>,
+    a: &Point<F::E>,
+    b: &Point<F::E>,
+    r: &(F::E, F::E),
+) -> (F::E, F::E, Point<F::E>) {
+    let f = &curve.field;
+    let (Some(pa), Some(pb)) = (a, b) else {
+        return (f.one(), f.one(), curve.add(a, b));
+    };
+    match curve.slope(pa, pb) {
+        None => (f.sub(&r.0, &pa.0), f.one(), None),
+        Some(lambda) => {
+            let sum = curve.chord(pa, pb, &lambda);
+            let l = f.sub(&f.sub(&r.1, &pa.1), &f.mul(&lambda, &f.sub(&r.0, &pa.0)));
+            let v = f.sub(&r.0, &sum.0);
+            (l, v, Some(sum))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    fn curve(p: &str, a: &str, b: &str) -> Curve<Zmod> {
+        parse_curve(&EccCurveParams {
+            p: p.into(),
+            a: a.into(),
+            b: b.into(),
+        })
+        .unwrap()
+    }
+
+    fn point(x: &str, y: &str) -> (BigUint, BigUint) {
+        (x.parse().unwrap(), y.parse().unwrap())
+    }
+
+    fn run(
+        curve: &Curve<Zmod>,
+        g: &(BigUint, BigUint),
+        q: &(BigUint, BigUint),
+        order: Option<&str>,
+        method: &str,
+    ) -> EcdlpResult {
+        let cancel = AtomicBool::new(false);
+        let progress = |_: DlogProgress| {};
+        let ctx = DlogContext {
+            cancel: &cancel,
+            deadline: Instant::now() + Duration::from_secs(60),
+            progress: &progress,
+        };
+        let order = order.map(|n| n.parse::<BigUint>().unwrap());
+        solve_ecdlp(curve, g, q, order.as_ref(), method, &ctx).unwrap()
+    }
+
+    #[test]
+    fn test_pohlig_hellman_and_mov() {
+        // #E = 999672 = 2^3·3·23·1811
+        let c = curve("1000003", "4", "7");
+        let x = (2u32..)
+            .map(BigUint::from)
+            .find(|x| sqrt_mod_prime(&c.rhs(x), &c.field.0).is_some_and(|y| !y.is_zero()))
+            .unwrap();
+        let g = (x.clone(), sqrt_mod_prime(&c.rhs(&x), &c.field.0).unwrap());
+        let q = c.mul(&Some(g.clone()), &BigUint::from(424242u32)).unwrap();
+        let res = run(&c, &g, &q, Some("999672"), "ph");
+        assert!(res.verified);
+        assert_eq!(res.method, "Pohlig-Hellman");
+
+        // 超奇异曲线 y² = x³ + x, p ≡ 3 (mod 4), 嵌入度 2
+        let c = curve("591310435784529511", "1", "0");
+        let g = point("2", "91397621309945854");
+        let q = point("133868705634130676", "528524895349885119");
+        let res = run(&c, &g, &q, Some("147827608946132378"), "auto");
+        assert!(res.method.starts_with("MOV"));
+        assert!(res.checks.iter().any(|s| s.contains("嵌入度 k = 2")));
+        assert_eq!(res.k.as_deref(), Some("104519681039085982"));
+        assert!(res.verified);
+    }
+
+    #[test]
+    fn test_smart_attack() {
+        // 异常曲线 y² = x³ + 5, #E = p
+        let c = curve("15901736353724529545343766757484427", "0", "5");
+        let g = point(
+            "2722863293488911981946111942768520",
+            "1470628261171794266627473356047011",
+        );
+        let q = point(
+            "15387466349226084152722242182981038",
+            "11310340065427171622937173737695568",
+        );
+        let res = run(&c, &g, &q, None, "auto");
+        assert_eq!(res.method, "Smart 攻击");
+        assert_eq!(
+            res.k.as_deref(),
+            Some("13065647104286394111105936610440091")
+        );
+        assert!(res.verified);
+    }
+
+    #[test]
+    fn test_singular_curves() {
+        let p = "1000003";
+        let k = BigUint::from(123_456u32);
+        // 尖点 y² = x³, (u², u³) 在曲线上
+        let c = curve(p, "0", "0");
+        let g = point("49", "343");
+        let q = c.mul(&Some(g.clone()), &k).unwrap();
+        let res = run(&c, &g, &q, None, "auto");
+        assert_eq!(res.k, Some(k.to_string()));
+
+        // 结点 y² = (x - α)²(x + 2α), 即 a = -3α², b = 2α³; α - β = 3α 的二次剩余性决定映射目标
+        for alpha in [5i64, 7] {
+            let c = curve(
+                p,
+                &(-3 * alpha * alpha).to_string(),
+                &(2 * alpha.pow(3)).to_string(),
+            );
+            let x = (alpha as u32 + 1..)
+                .map(BigUint::from)
+                .find(|x| sqrt_mod_prime(&c.rhs(x), &c.field.0).is_some_and(|y| !y.is_zero()))
+                .unwrap();
+            let g = (x.clone(), sqrt_mod_prime(&c.rhs(&x), &c.field.0).unwrap());
+            let q = c.mul(&Some(g.clone()), &k).unwrap();
+            let res = run(&c, &g, &q, None, "auto");
+            assert!(res.method.contains("结点"));
+            assert!(res.verified);
+        }
+    }
+}
@@ -0,0 +1,626 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rayon::prelude::*;
+use serde::Serialize;
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_biguint, render_number, NumberView};
+use super::number_theory::{is_probable_prime, small_primes};
+use super::task::CryptoTasks;
+
+pub const FACTOR_TASK: &str = "factor";
+
+const DEFAULT_TIME_LIMIT_SECS: u64 = 120;
+const TRIAL_DIVISION_LIMIT: u64 = 1_000_000;
+const FERMAT_MAX_STEPS: u64 = 20_000_000;
+const SMOOTH_BOUND: u64 = 2_000_000; // p-1 / p+1 的 B1
+const PPLUS1_SEEDS: [u32; 3] = [3, 5, 7];
+const CHECK_INTERVAL: u64 = 4096; // 每隔多少步检查一次取消标志 / gcd
+
+// ===================== 数据结构 =====================
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FactorProgress {
+    pub method: String,
+    pub message: String,
+    pub found: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RsaKeyResult {
+    pub n: String,
+    pub phi: String,
+    pub d: String,
+    pub plaintext: NumberView,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FactorResult {
+    pub factors: Vec<String>,   // 已确认的素因子 (含重数, 升序)
+    pub remaining: Vec<String>, // 未能分解的合数部分
+    pub complete: bool,
+    pub cancelled: bool,
+    pub key: Option<RsaKeyResult>, // 给出 e 与 c 且分解完整时的解密结果
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    Trial,
+    Fermat,
+    Rho,
+    PMinus1,
+    PPlus1,
+}
+
+impl Method {
+    pub const ALL: [Method; 5] = [
+        Method::Trial,
+        Method::Fermat,
+        Method::Rho,
+        Method::PMinus1,
+        Method::PPlus1,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Trial => "试除法",
+            Method::Fermat => "Fermat",
+            Method::Rho => "Pollard rho",
+            Method::PMinus1 => "Pollard p-1",
+            Method::PPlus1 => "Williams p+1",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Method, String> {
+        match s.trim().to_lowercase().as_str() {
+            "trial" => Ok(Method::Trial),
+            "fermat" => Ok(Method::Fermat),
+            "rho" => Ok(Method::Rho),
+            "pm1" | "p-1" => Ok(Method::PMinus1),
+            "pp1" | "p+1" => Ok(Method::PPlus1),
+            other => Err(format!("未知的分解算法: {}", other)),
+        }
+    }
+}
+
+/// 一次分解过程的共享上下文: 取消标志、截止时间与进度回调
+pub struct FactorContext<'a> {
+    pub cancel: &'a AtomicBool,
+    pub deadline: Instant,
+    pub progress: &'a (dyn Fn(FactorProgress) + Sync),
+}
+
+impl FactorContext<'_> {
+    fn halted(&self, found: &AtomicBool) -> bool {
+        found.load(Ordering::Relaxed)
+            || self.cancel.load(Ordering::Relaxed)
+            || Instant::now() > self.deadline
+    }
+
+    fn report(&self, method: Method, message: String) {
+        (self.progress)(FactorProgress {
+            method: method.name().into(),
+            message,
+            found: None,
+        });
+    }
+}
+
+// ===================== Commands =====================
+
+#[tauri::command]
+pub async fn factorize_rsa(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    n: String,
+    e: Option<String>,
+    c: Option<String>,
+    methods: Option<Vec<String>>,
+    time_limit: Option<u64>,
+) -> Result<FactorResult, String> {
+    let n = parse_biguint(&n)?;
+    if n <= BigUint::one() {
+        return Err("N 必须大于 1".into());
+    }
+    let e = e
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_biguint(&s))
+        .transpose()?;
+    let c = c
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_biguint(&s))
+        .transpose()?;
+    let methods = match methods {
+        Some(list) if !list.is_empty() => list
+            .iter()
+            .map(|m| Method::parse(m))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => Method::ALL.to_vec(),
+    };
+    let limit = Duration::from_secs(time_limit.unwrap_or(DEFAULT_TIME_LIMIT_SECS));
+
+    let handle = tasks.begin(FACTOR_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |p: FactorProgress| {
+            let _ = window.emit("factor-progress", p);
+        };
+        let ctx = FactorContext {
+            cancel: &cancel,
+            deadline: Instant::now() + limit,
+            progress: &emit,
+        };
+
+        let (primes, remaining) = factorize(&n, &methods, &ctx);
+        let complete = remaining.is_empty();
+        let key = match (&e, &c) {
+            (Some(e), Some(c)) if complete => Some(rsa_from_factors(&primes, e, c)?),
+            _ => None,
+        };
+        Ok(FactorResult {
+            factors: primes.iter().map(|p| p.to_string()).collect(),
+            remaining: remaining.iter().map(|r| r.to_string()).collect(),
+            complete,
+            cancelled: cancel.load(Ordering::Relaxed),
+            key,
+        })
+    })
+    .await
+    .map_err(|e| format!("分解任务异常退出: {}", e))?
+}
+
+#[tauri::command]
+pub fn rsa_decrypt_with_factors(
+    factors: Vec<String>,
+    e: String,
+    c: String,
+) -> Result<RsaKeyResult, String> {
+    if factors.is_empty() {
+        return Err("至少需要一个素因子".into());
+    }
+    let factors = factors
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| parse_biguint(f).map_err(|err| format!("因子 {}: {}", idx + 1, err)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(idx) = factors.iter().position(|f| !is_probable_prime(f)) {
+        return Err(format!("因子 {} 不是素数", idx + 1));
+    }
+    let e = parse_biguint(&e)?;
+    let c = parse_biguint(&c)?;
+    rsa_from_factors(&factors, &e, &c)
+}
+
+// ===================== 多素数 RSA =====================
+
+/// 由素因子 (可重复) 计算 φ、d 并解密
+pub fn rsa_from_factors(
+    primes: &[BigUint],
+    e: &BigUint,
+    c: &BigUint,
+) -> Result<RsaKeyResult, String> {
+    let n: BigUint = primes.iter().product();
+    let phi = euler_phi(primes);
+    let d = e.modinv(&phi).ok_or("e 与 phi 不互质, 无法直接求私钥")?;
+    let m = c.modpow(&d, &n);
+    Ok(RsaKeyResult {
+        n: n.to_string(),
+        phi: phi.to_string(),
+        d: d.to_string(),
+        plaintext: render_number(&m),
+    })
+}
+
+/// φ(Π p_i^k_i) = Π p_i^(k_i - 1)·(p_i - 1)
+pub fn euler_phi(primes: &[BigUint]) -> BigUint {
+    let mut sorted = primes.to_vec();
+    sorted.sort();
+    let mut phi = BigUint::one();
+    let mut prev: Option<&BigUint> = None;
+    for p in sorted.iter() {
+        if prev == Some(p) {
+            phi *= p;
+        } else {
+            phi *= p - 1u32;
+        }
+        prev = Some(p);
+    }
+    phi
+}
+
+// ===================== 分解调度 =====================
+
+/// 完全分解: 返回 (素因子列表, 未能分解的合数)
+pub fn factorize(
+    n: &BigUint,
+    methods: &[Method],
+    ctx: &FactorContext,
+) -> (Vec<BigUint>, Vec<BigUint>) {
+    let mut primes = Vec::new();
+    let mut remaining = Vec::new();
+    let mut stack = vec![n.clone()];
+
+    while let Some(m) = stack.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if is_probable_prime(&m) {
+            primes.push(m);
+            continue;
+        }
+        if let Some((root, k)) = perfect_power(&m) {
+            for _ in 0..k {
+                stack.push(root.clone());
+            }
+            continue;
+        }
+        if ctx.cancel.load(Ordering::Relaxed) || Instant::now() > ctx.deadline {
+            remaining.push(m);
+            continue;
+        }
+        match find_factor(&m, methods, ctx) {
+            Some(f) => {
+                (ctx.progress)(FactorProgress {
+                    method: "调度".into(),
+                    message: format!("{} = {} × {}", m, f, &m / &f),
+                    found: Some(f.to_string()),
+                });
+                stack.push(&m / &f);
+                stack.push(f);
+            }
+            None => remaining.push(m),
+        }
+    }
+
+    primes.sort();
+    remaining.sort();
+    (primes, remaining)
+}
+
+/// 并行运行各个算法, 任一算法找到非平凡因子即停止其他算法
+pub fn find_factor(n: &BigUint, methods: &[Method], ctx: &FactorContext) -> Option<BigUint> {
+    let found = AtomicBool::new(false);
+    methods.par_iter().find_map_any(|&method| {
+        let f = match method {
+            Method::Trial => trial_division(n, ctx, &found),
+            Method::Fermat => fermat(n, ctx, &found),
+            Method::Rho => pollard_rho(n, ctx, &found),
+            Method::PMinus1 => pollard_pm1(n, ctx, &found),
+            Method::PPlus1 => williams_pp1(n, ctx, &found),
+        };
+        let f = f.filter(|f| !f.is_one() && f != n)?;
+        found.store(true, Ordering::Relaxed);
+        ctx.report(method, format!("找到因子 {}", f));
+        Some(f)
+    })
+}
+
+fn perfect_power(n: &BigUint) -> Option<(BigUint, u32)> {
+    let max_k = n.bits() as u32;
+    for k in (2..=max_k.min(64)).rev() {
+        let root = n.nth_root(k);
+        if root > BigUint::one() && root.pow(k) == *n {
+            return Some((root, k));
+        }
+    }
+    None
+}
+
+// ===================== 各分解算法 =====================
+
+fn trial_division(n: &BigUint, ctx: &FactorContext, found: &AtomicBool) -> Option<BigUint> {
+    for (i, p) in small_primes(TRIAL_DIVISION_LIMIT).into_iter().enumerate() {
+        if (i as u64).is_multiple_of(CHECK_INTERVAL) && ctx.halted(found) {
+            return None;
+        }
+        if (n % p).is_zero() {
+            return Some(BigUint::from(p));
+        }
+    }
+    ctx.report(
+        Method::Trial,
+        format!("{} 以内无小因子", TRIAL_DIVISION_LIMIT),
+    );
+    None
+}
+
+/// Fermat 分解: 适用于 |p - q| 很小的情形
+fn fermat(n: &BigUint, ctx: &FactorContext, found: &AtomicBool) -> Option<BigUint> {
+    if n.is_even() {
+        return Some(BigUint::from(2u32));
+    }
+    let mut a = n.sqrt();
+    if &a * &a < *n {
+        a += 1u32;
+    }
+    let mut b2 = &a * &a - n;
+
+    for step in 0..FERMAT_MAX_STEPS {
+        if step.is_multiple_of(CHECK_INTERVAL) {
+            if ctx.halted(found) {
+                return None;
+            }
+            if step > 0 && step.is_multiple_of(CHECK_INTERVAL * 256) {
+                ctx.report(Method::Fermat, format!("已尝试 {} 步", step));
+            }
+        }
+        let b = b2.sqrt();
+        if &b * &b == b2 {
+            let f = &a - &b;
+            if !f.is_one() {
+                return Some(f);
+            }
+        }
+        // (a + 1)^2 - n = b2 + 2a + 1
+        b2 += &a << 1;
+        b2 += 1u32;
+        a += 1u32;
+    }
+    None
+}
+
+/// Pollard rho (Brent 变体, 批量累乘后再求 gcd)
+fn pollard_rho(n: &BigUint, ctx: &FactorContext, found: &AtomicBool) -> Option<BigUint> {
+    if n.is_even() {
+        return Some(BigUint::from(2u32));
+    }
+    let batch = 128u64;
+    let mut iterations = 0u64;
+    let mut next_report = CHECK_INTERVAL * 64;
+
+    for c in 1u32.. {
+        let c = BigUint::from(c);
+        let f = |x: &BigUint| (x * x + &c) % n;
+        let mut y = BigUint::from(2u32);
+        let mut r = 1u64;
+        let mut q = BigUint::one();
+        let mut g = BigUint::one();
+        let mut x = y.clone();
+        let mut ys = y.clone();
+
+        while g.is_one() {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && g.is_one() {
+                if ctx.halted(found) {
+                    return None;
+                }
+                ys = y.clone();
+                for _ in 0..batch.min(r - k) {
+                    y = f(&y);
+                    let diff = if x > y { &x - &y } else { &y - &x };
+                    q = q * diff % n;
+                }
+                g = q.gcd(n);
+                k += batch;
+                iterations += batch;
+            }
+            r *= 2;
+            if iterations >= next_report {
+                next_report *= 2;
+                ctx.report(Method::Rho, format!("已迭代 {} 次", iterations));
+            }
+        }
+
+        if g == *n {
+            // 批量乘积越过了因子, 逐步回退
+            loop {
+                ys = f(&ys);
+                let diff = if x > ys { &x - &ys } else { &ys - &x };
+                g = diff.gcd(n);
+                if !g.is_one() {
+                    break;
+                }
+            }
+        }
+        if g != *n {
+            return Some(g);
+        }
+        ctx.report(Method::Rho, "当前多项式失败, 更换常数重试".into());
+    }
+    None
+}
+
+/// Pollard p-1: 适用于 p - 1 光滑的情形
+fn pollard_pm1(n: &BigUint, ctx: &FactorContext, found: &AtomicBool) -> Option<BigUint> {
+    let primes = small_primes(SMOOTH_BOUND);
+    let mut a = BigUint::from(2u32);
+    let mut checkpoint = (0usize, a.clone());
+
+    for (i, &p) in primes.iter().enumerate() {
+        a = a.modpow(&BigUint::from(prime_power(p, SMOOTH_BOUND)), n);
+
+        if (i as u64 + 1).is_multiple_of(CHECK_INTERVAL) || i + 1 == primes.len() {
+            if ctx.halted(found) {
+                return None;
+            }
+            let g = (&a + n - 1u32).gcd(n);
+            if g == *n {
+                // 一次越过所有因子, 从检查点逐个素数回退
+                let mut b = checkpoint.1.clone();
+                for &p in primes[checkpoint.0..=i].iter() {
+                    b = b.modpow(&BigUint::from(prime_power(p, SMOOTH_BOUND)), n);
+                    let g = (&b + n - 1u32).gcd(n);
+                    if !g.is_one() {
+                        return if g == *n { None } else { Some(g) };
+                    }
+                }
+                return None;
+            }
+            if !g.is_one() {
+                return Some(g);
+            }
+            checkpoint = (i + 1, a.clone());
+            if (i as u64 + 1).is_multiple_of(CHECK_INTERVAL * 16) {
+                ctx.report(Method::PMinus1, format!("B1 已推进到 {}", p));
+            }
+        }
+    }
+    ctx.report(
+        Method::PMinus1,
+        format!("B1 = {} 内未找到因子", SMOOTH_BOUND),
+    );
+    None
+}
+
+/// Williams p+1: 适用于 p + 1 光滑的情形, 使用 Lucas 序列 V_k
+fn williams_pp1(n: &BigUint, ctx: &FactorContext, found: &AtomicBool) -> Option<BigUint> {
+    let primes = small_primes(SMOOTH_BOUND);
+    let two = BigUint::from(2u32);
+
+    for &seed in PPLUS1_SEEDS.iter() {
+        let mut v = BigUint::from(seed) % n;
+        for (i, &p) in primes.iter().enumerate() {
+            v = lucas_v(&v, prime_power(p, SMOOTH_BOUND), n);
+            if (i as u64 + 1).is_multiple_of(CHECK_INTERVAL) || i + 1 == primes.len() {
+                if ctx.halted(found) {
+                    return None;
+                }
+                let g = (&v + n - &two).gcd(n);
+                if g == *n {
+                    break;
+                }
+                if !g.is_one() {
+                    return Some(g);
+                }
+            }
+        }
+        ctx.report(Method::PPlus1, format!("种子 A = {} 未找到因子", seed));
+    }
+    None
+}
+
+/// 最大的 p^k <= bound
+fn prime_power(p: u64, bound: u64) -> u64 {
+    let mut pk = p;
+    while pk <= bound / p {
+        pk *= p;
+    }
+    pk
+}
+
+/// 计算 Lucas 序列 V_m(P) mod n: V_0 = 2, V_1 = P, V_{k+1} = P·V_k - V_{k-1}
+fn lucas_v(p: &BigUint, m: u64, n: &BigUint) -> BigUint {
+    let two = BigUint::from(2u32);
+    let mut x = p.clone();
+    let mut y = (p * p + n - &two) % n;
+    for bit in (0..63 - m.leading_zeros()).rev() {
+        if (m >> bit) & 1 == 1 {
+            // (V_k, V_{k+1}) -> (V_{2k+1}, V_{2k+2})
+            x = (&x * &y + n - p) % n;
+            y = (&y * &y + n - &two) % n;
+        } else {
+            // (V_k, V_{k+1}) -> (V_{2k}, V_{2k+1})
+            y = (&x * &y + n - p) % n;
+            x = (&x * &x + n - &two) % n;
+        }
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn big(s: &str) -> BigUint {
+        BigUint::from_str(s).unwrap()
+    }
+
+    fn run(n: &BigUint, methods: &[Method]) -> (Vec<BigUint>, Vec<BigUint>) {
+        let cancel = AtomicBool::new(false);
+        let progress = |_: FactorProgress| {};
+        let ctx = FactorContext {
+            cancel: &cancel,
+            deadline: Instant::now() + Duration::from_secs(60),
+            progress: &progress,
+        };
+        factorize(n, methods, &ctx)
+    }
+
+    #[test]
+    fn test_fermat_close_primes() {
+        let p =
+            big("92245153858902965315636786393032967667310074150971024202483407725665870463471");
+        let q =
+            big("92245153858902965315636786393032967667310074150971024202483407726765382091261");
+        let (primes, remaining) = run(&(&p * &q), &[Method::Fermat]);
+        assert!(remaining.is_empty());
+        assert_eq!(primes, vec![p, q]);
+    }
+
+    #[test]
+    fn test_pollard_pm1_smooth() {
+        let p = big("35831185298363834213609568418907");
+        let q = big("229762150798743005889569295854314433729");
+        let (primes, _) = run(&(&p * &q), &[Method::PMinus1]);
+        assert_eq!(primes, vec![p, q]);
+    }
+
+    #[test]
+    fn test_williams_pp1_smooth() {
+        let p = big("5665609678155427390278944076193");
+        let q = big("302344354755456554697868620206330471119");
+        let (primes, _) = run(&(&p * &q), &[Method::PPlus1]);
+        assert_eq!(primes, vec![p, q]);
+    }
+
+    #[test]
+    fn test_rho_and_trial_multi_prime() {
+        // 2^3 · 7919 · 2535196823 · 42194962807
+        let n = big("106972535654909562161") * 8u32 * 7919u32;
+        let (primes, remaining) = run(&n, &Method::ALL);
+        assert!(remaining.is_empty());
+        assert_eq!(primes.len(), 6);
+        assert_eq!(primes.iter().product::<BigUint>(), n);
+    }
+
+    #[test]
+    fn test_multi_prime_decrypt() {
+        let primes: Vec<BigUint> = ["2535196823", "42194962807", "1000000007", "1000000007"]
+            .iter()
+            .map(|s| big(s))
+            .collect();
+        let n: BigUint = primes.iter().product();
+        let e = BigUint::from(65537u32);
+        let m = BigUint::from_bytes_be(b"multi!");
+        let c = m.modpow(&e, &n);
+
+        let key = rsa_decrypt_with_factors(
+            primes.iter().map(|p| p.to_string()).collect(),
+            "65537".into(),
+            c.to_string(),
+        )
+        .unwrap();
+        assert_eq!(key.plaintext.utf8.as_deref(), Some("multi!"));
+        // φ = (p1 - 1)(p2 - 1) · q(q - 1), q 出现两次
+        let q = big("1000000007");
+        let expected = (&primes[0] - 1u32) * (&primes[1] - 1u32) * &q * (&q - 1u32);
+        assert_eq!(key.phi, expected.to_string());
+    }
+
+    #[test]
+    fn test_cancelled_factorization() {
+        let cancel = AtomicBool::new(true);
+        let progress = |_: FactorProgress| {};
+        let ctx = FactorContext {
+            cancel: &cancel,
+            deadline: Instant::now() + Duration::from_secs(60),
+            progress: &progress,
+        };
+        let p = big("229762150798743005889569295854314433729");
+        let q = big("302344354755456554697868620206330471119");
+        let (primes, remaining) = factorize(&(&p * &q), &Method::ALL, &ctx);
+        assert!(primes.is_empty());
+        assert_eq!(remaining.len(), 1);
+    }
+}
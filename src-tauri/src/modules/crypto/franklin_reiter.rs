@@ -0,0 +1,232 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use serde::Serialize;
+
+use super::codec::{parse_bigint, parse_biguint, render_number, NumberView};
+use super::coppersmith::search_roots;
+use super::poly::{
+    affine_pow, interpolate_mod, poly_degree, poly_gcd_mod, resultant_mod, PolyResult,
+};
+
+// 多项式 GCD 为朴素的 O(e²) 欧几里得算法, e 过大时耗时不可接受
+const MAX_EXPONENT: u32 = 1 << 12;
+// Short Pad 需要次数为 e² 的结式多项式上的 Coppersmith, 只对很小的 e 可行
+const MAX_SHORT_PAD_EXPONENT: u32 = 5;
+
+#[derive(Serialize, Debug)]
+pub struct FranklinReiterResult {
+    pub method: String,
+    pub delta: Option<String>, // Short Pad 求出的 m2 - m1
+    pub m1: NumberView,
+    pub m2: NumberView,
+}
+
+/// 相关消息攻击: 已知 m2 = a·m1 + b 时对 x^e - c1 与 (a·x + b)^e - c2 求公因式
+///
+/// 给出 pad_bits 时改用 Coppersmith Short Pad: m2 = m1 + δ, |δ| < 2^pad_bits 未知
+#[tauri::command]
+pub fn franklin_reiter_attack(
+    n: String,
+    e: String,
+    c1: String,
+    c2: String,
+    a: Option<String>,
+    b: Option<String>,
+    pad_bits: Option<u32>,
+) -> Result<FranklinReiterResult, String> {
+    let n = parse_biguint(&n).map_err(|err| format!("N 格式错误: {}", err))?;
+    if n <= BigUint::one() {
+        return Err("N 必须大于 1".into());
+    }
+    let e = parse_biguint(&e).map_err(|err| format!("e 格式错误: {}", err))?;
+    let c1 = parse_biguint(&c1).map_err(|err| format!("c1 格式错误: {}", err))?;
+    let c2 = parse_biguint(&c2).map_err(|err| format!("c2 格式错误: {}", err))?;
+    let e = u32::try_from(&e)
+        .ok()
+        .filter(|&e| (2..=MAX_EXPONENT).contains(&e))
+        .ok_or_else(|| format!("e 需在 2 到 {} 之间", MAX_EXPONENT))?;
+    let n = BigInt::from_biguint(Sign::Plus, n);
+    let c1 = BigInt::from_biguint(Sign::Plus, c1);
+    let c2 = BigInt::from_biguint(Sign::Plus, c2);
+
+    let (method, a, b, delta) = match pad_bits {
+        Some(bits) => {
+            let delta = short_pad_delta(&n, e, &c1, &c2, bits)?;
+            (
+                "Coppersmith Short Pad + Franklin-Reiter",
+                BigInt::one(),
+                delta.clone(),
+                Some(delta),
+            )
+        }
+        None => {
+            let a = match a.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                Some(a) => parse_bigint(a).map_err(|err| format!("a 格式错误: {}", err))?,
+                None => BigInt::one(),
+            };
+            let b = b
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .ok_or("缺少关系式中的 b")?;
+            let b = parse_bigint(b).map_err(|err| format!("b 格式错误: {}", err))?;
+            if a.mod_floor(&n).is_zero() {
+                return Err("a 不能是 N 的倍数 (m2 与 m1 无关)".into());
+            }
+            ("Franklin-Reiter", a, b, None)
+        }
+    };
+
+    let m1 = related_message(&n, e, &c1, &c2, &a, &b)?;
+    let m2 = (&a * &m1 + &b).mod_floor(&n);
+    Ok(FranklinReiterResult {
+        method: method.into(),
+        delta: delta.map(|d| d.to_string()),
+        m1: render_number(&m1.to_biguint().unwrap()),
+        m2: render_number(&m2.to_biguint().unwrap()),
+    })
+}
+
+/// gcd(x^e - c1, (a·x + b)^e - c2) = x - m1
+pub fn related_message(
+    n: &BigInt,
+    e: u32,
+    c1: &BigInt,
+    c2: &BigInt,
+    a: &BigInt,
+    b: &BigInt,
+) -> Result<BigInt, String> {
+    let g = poly_gcd_mod(&g1(e, c1, n), &g2(e, a, b, c2, n), n).map_err(found_factor)?;
+    match poly_degree(&g) {
+        Some(1) => Ok((-&g[0]).mod_floor(n)),
+        Some(d) => Err(format!("公因式次数为 {}, 关系式或密文可能有误", d)),
+        None => Err("两个多项式恒等, 无法确定明文".into()),
+    }
+}
+
+/// 求 m2 - m1: 结式 Res_x(x^e - c1, (x + y)^e - c2) 是 y 的 e² 次多项式, δ 是它的小根
+fn short_pad_delta(
+    n: &BigInt,
+    e: u32,
+    c1: &BigInt,
+    c2: &BigInt,
+    pad_bits: u32,
+) -> Result<BigInt, String> {
+    if e > MAX_SHORT_PAD_EXPONENT {
+        return Err(format!("Short Pad 仅支持 e <= {}", MAX_SHORT_PAD_EXPONENT));
+    }
+
+    // 在 e² + 1 个点上求结式再插值, 避免二元多项式运算
+    let f = g1(e, c1, n);
+    let points: Vec<BigInt> = (0..=e * e).map(BigInt::from).collect();
+    let values = points
+        .iter()
+        .map(|y| resultant_mod(&f, &g2(e, &BigInt::one(), y, c2, n), n))
+        .collect::<PolyResult<Vec<_>>>()
+        .map_err(found_factor)?;
+    let h = interpolate_mod(&points, &values, n).map_err(found_factor)?;
+
+    let (roots, _) = search_roots(&h, n, pad_bits, 1.0, None)?;
+    roots
+        .into_iter()
+        .filter(|d| !d.is_zero())
+        .min_by_key(|d| d.magnitude().clone())
+        .ok_or_else(|| "只找到 δ = 0, 两条密文对应同一明文".into())
+}
+
+/// x^e - c1
+fn g1(e: u32, c1: &BigInt, n: &BigInt) -> Vec<BigInt> {
+    let mut f = vec![BigInt::zero(); e as usize + 1];
+    f[0] = (-c1).mod_floor(n);
+    f[e as usize] = BigInt::one();
+    f
+}
+
+/// (a·x + b)^e - c2
+fn g2(e: u32, a: &BigInt, b: &BigInt, c2: &BigInt, n: &BigInt) -> Vec<BigInt> {
+    let mut f = affine_pow(a, b, e, n);
+    // a ≡ b ≡ 0 时 (a·x + b)^e 是零多项式
+    if f.is_empty() {
+        f.push(BigInt::zero());
+    }
+    f[0] = (&f[0] - c2).mod_floor(n);
+    f
+}
+
+fn found_factor(g: BigInt) -> String {
+    format!("运算中遇到不可逆元素, gcd = {} (可能是 N 的非平凡因子)", g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N_256: &str =
+        "51530020546182678294198851407836155777005267024504313283698683227788490135697";
+
+    #[test]
+    fn test_franklin_reiter_affine() {
+        let n: BigUint = N_256.parse().unwrap();
+        let m1 = BigUint::from_bytes_be(b"flag{related_msg}");
+        let m2 = (&m1 * 3u32 + 0x1234u32) % &n;
+        for e in [3u32, 17] {
+            let e_big = BigUint::from(e);
+            let res = franklin_reiter_attack(
+                N_256.into(),
+                e.to_string(),
+                m1.modpow(&e_big, &n).to_string(),
+                m2.modpow(&e_big, &n).to_string(),
+                Some("3".into()),
+                Some("0x1234".into()),
+                None,
+            )
+            .unwrap();
+            assert_eq!(res.m1.utf8.as_deref(), Some("flag{related_msg}"));
+            assert_eq!(res.m2.decimal, m2.to_string());
+        }
+    }
+
+    #[test]
+    fn test_degenerate_input() {
+        let attack = |n: &str, a: &str, b: &str| {
+            franklin_reiter_attack(
+                n.into(),
+                "3".into(),
+                "5".into(),
+                "7".into(),
+                Some(a.into()),
+                Some(b.into()),
+                None,
+            )
+        };
+        assert!(attack("0", "1", "1").is_err());
+        assert!(attack(N_256, "0", "0").is_err());
+        assert!(attack(N_256, N_256, "1").is_err());
+        let zero = BigInt::zero();
+        let g = g2(3, &zero, &zero, &BigInt::from(7), &BigInt::from(11));
+        assert_eq!(g, vec![BigInt::from(4)]);
+    }
+
+    #[test]
+    fn test_short_pad() {
+        let n: BigUint = N_256.parse().unwrap();
+        let base = BigUint::from_bytes_be(b"short pad!") << 16u32;
+        let m1 = &base + 0x3a5cu32;
+        let m2 = &base + 0x0f11u32;
+        let e = BigUint::from(3u32);
+        let res = franklin_reiter_attack(
+            N_256.into(),
+            "3".into(),
+            m1.modpow(&e, &n).to_string(),
+            m2.modpow(&e, &n).to_string(),
+            None,
+            None,
+            Some(16),
+        )
+        .unwrap();
+        assert_eq!(res.delta.as_deref(), Some("-11083"));
+        assert_eq!(res.m1.decimal, m1.to_string());
+        assert_eq!(res.m2.decimal, m2.to_string());
+    }
+}
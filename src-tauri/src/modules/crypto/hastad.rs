@@ -0,0 +1,229 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use serde::Serialize;
+
+use super::codec::{parse_biguint, render_number, NumberView};
+use super::lattice::small_roots;
+use super::number_theory::{crt, exact_nth_root};
+
+#[derive(Serialize, Debug)]
+pub struct HastadResult {
+    pub method: String,
+    pub plaintext: NumberView,
+}
+
+// 线性填充变体的 Coppersmith 参数 ε, 依次加大格维数
+const PADDING_EPSILONS: [f64; 3] = [1.0 / 8.0, 1.0 / 12.0, 1.0 / 16.0];
+
+#[tauri::command]
+pub fn hastad_broadcast_attack(
+    n_list: Vec<String>,
+    c_list: Vec<String>,
+    e: String,
+    pad_a: Option<Vec<String>>,
+    pad_b: Option<Vec<String>>,
+) -> Result<HastadResult, String> {
+    if n_list.len() != c_list.len() {
+        return Err("N 与 C 的个数不一致".into());
+    }
+    if n_list.len() < 2 {
+        return Err("至少需要两组 (N, C)".into());
+    }
+    let e = parse_biguint(&e)
+        .map_err(|err| format!("E: {}", err))?
+        .to_u32()
+        .ok_or("E 过大")?;
+    if e < 2 {
+        return Err("E 必须大于 1".into());
+    }
+
+    let mut moduli = Vec::with_capacity(n_list.len());
+    let mut ciphers = Vec::with_capacity(c_list.len());
+    for (idx, (n, c)) in n_list.iter().zip(c_list.iter()).enumerate() {
+        let n = parse_biguint(n).map_err(|err| format!("N{}: {}", idx + 1, err))?;
+        let c = parse_biguint(c).map_err(|err| format!("C{}: {}", idx + 1, err))?;
+        if n <= BigUint::one() {
+            return Err(format!("N{} 必须大于 1", idx + 1));
+        }
+        moduli.push(n);
+        ciphers.push(c);
+    }
+
+    let count = moduli.len();
+    let parse_list = |list: Option<Vec<String>>, default: u32, name: &str| match list {
+        None => Ok(vec![BigUint::from(default); count]),
+        Some(v) if v.is_empty() => Ok(vec![BigUint::from(default); count]),
+        Some(v) if v.len() != count => Err(format!("{} 的个数必须与 N 一致", name)),
+        Some(v) => v
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| parse_biguint(s).map_err(|err| format!("{}{}: {}", name, idx + 1, err)))
+            .collect(),
+    };
+    let pad_a = parse_list(pad_a, 1, "a")?;
+    let pad_b = parse_list(pad_b, 0, "b")?;
+
+    let padded = pad_a.iter().any(|a| !a.is_one()) || pad_b.iter().any(|b| !b.is_zero());
+
+    let (method, m) = if padded {
+        let m = broadcast_with_linear_padding(&moduli, &ciphers, &pad_a, &pad_b, e)?;
+        ("线性填充 (Coppersmith)", m)
+    } else {
+        let m = broadcast_plain(&moduli, &ciphers, e)?;
+        ("CRT + 整数开方", m)
+    };
+
+    Ok(HastadResult {
+        method: method.into(),
+        plaintext: render_number(&m),
+    })
+}
+
+/// 同一明文在 k 个模数下以相同的小 e 加密: CRT 合并后 m^e < ΠN_i, 直接开 e 次方
+pub fn broadcast_plain(moduli: &[BigUint], ciphers: &[BigUint], e: u32) -> Result<BigUint, String> {
+    let pairs: Vec<(BigUint, BigUint)> = ciphers
+        .iter()
+        .cloned()
+        .zip(moduli.iter().cloned())
+        .collect();
+    let (x, _) = crt(&pairs)?;
+    exact_nth_root(&x, e).ok_or_else(|| {
+        format!(
+            "CRT 结果不是完全 {} 次方 (密文数量不足 {} 组或明文带有填充)",
+            e, e
+        )
+    })
+}
+
+/// 带线性填充 c_i = (a_i·m + b_i)^e mod N_i 的广播攻击
+///
+/// 用 CRT 系数 T_i 把各方程合并成模 N = ΠN_i 的一元多项式, 再用 Coppersmith 求小根 m。
+pub fn broadcast_with_linear_padding(
+    moduli: &[BigUint],
+    ciphers: &[BigUint],
+    pad_a: &[BigUint],
+    pad_b: &[BigUint],
+    e: u32,
+) -> Result<BigUint, String> {
+    let to_int = |x: &BigUint| BigInt::from_biguint(Sign::Plus, x.clone());
+
+    let big_n: BigInt = moduli.iter().map(to_int).product();
+    let degree = e as usize;
+    let mut poly = vec![BigInt::zero(); degree + 1];
+
+    for (idx, n_i) in moduli.iter().enumerate() {
+        let n_i = to_int(n_i);
+        let others = &big_n / &n_i;
+        let inv = others
+            .mod_floor(&n_i)
+            .modinv(&n_i)
+            .ok_or_else(|| format!("N{} 与其他模数不互质, 请先尝试共享素数攻击", idx + 1))?;
+        let t_i = (others * inv).mod_floor(&big_n);
+
+        // (a·x + b)^e - c = Σ C(e, j) a^j b^(e-j) x^j - c
+        let a = to_int(&pad_a[idx]);
+        let b = to_int(&pad_b[idx]);
+        let mut binom = BigInt::one();
+        for (j, coeff) in poly.iter_mut().enumerate() {
+            if j > 0 {
+                binom = binom * BigInt::from(degree - j + 1) / BigInt::from(j);
+            }
+            let mut term = &binom * a.pow(j as u32) * b.pow((degree - j) as u32);
+            if j == 0 {
+                term -= to_int(&ciphers[idx]);
+            }
+            *coeff = (&*coeff + &t_i * term).mod_floor(&big_n);
+        }
+    }
+
+    for &epsilon in PADDING_EPSILONS.iter() {
+        let bits = (big_n.bits() as f64 * (1.0 / e as f64 - epsilon)).floor();
+        if bits < 1.0 {
+            continue;
+        }
+        let x_bound = BigInt::one() << (bits as u64);
+        if let Some(m) = small_roots(&poly, &big_n, &x_bound, 1.0, epsilon)
+            .into_iter()
+            .find(|r| *r >= BigInt::zero())
+        {
+            return Ok(m.to_biguint().unwrap());
+        }
+    }
+
+    Err("Coppersmith 未找到满足条件的小根 (明文过大或密文组数不足)".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn moduli() -> Vec<BigUint> {
+        [
+            "47619075096357399156465346975177966827852045162582961608582994196333069965049",
+            "56199225274499878634362989148864852971921390983783381712026428005377948766823",
+            "54883431976771140878154390248147430237744735973711418242594553780835720830453",
+            "62547306627134510306334746402586219855015948050496958602465384473584373709397",
+        ]
+        .iter()
+        .map(|s| BigUint::from_str(s).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn test_plain_broadcast() {
+        let ns = moduli();
+        let m = BigUint::from_bytes_be(b"flag{hastad_e3}");
+        let e = BigUint::from(3u32);
+        let cs: Vec<String> = ns
+            .iter()
+            .take(3)
+            .map(|n| m.modpow(&e, n).to_string())
+            .collect();
+        let ns: Vec<String> = ns.iter().take(3).map(|n| n.to_string()).collect();
+
+        let res =
+            hastad_broadcast_attack(ns.clone(), cs.clone(), "0x3".into(), None, None).unwrap();
+        assert_eq!(res.plaintext.utf8.as_deref(), Some("flag{hastad_e3}"));
+        assert!(hastad_broadcast_attack(ns, cs, "0x100000000".into(), None, None).is_err());
+    }
+
+    #[test]
+    fn test_linear_padding_broadcast() {
+        let ns = moduli();
+        let m = BigUint::from_bytes_be(b"flag{padded!}");
+        let e = BigUint::from(3u32);
+        let a: Vec<BigUint> = (1..=4u32).map(|i| BigUint::from(i * 7 + 1)).collect();
+        let b: Vec<BigUint> = (1..=4u32).map(|i| BigUint::from(i) << 120).collect();
+        let cs: Vec<String> = ns
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (&a[i] * &m + &b[i]).modpow(&e, n).to_string())
+            .collect();
+
+        let res = hastad_broadcast_attack(
+            ns.iter().map(|n| n.to_string()).collect(),
+            cs,
+            "3".into(),
+            Some(a.iter().map(|x| x.to_string()).collect()),
+            Some(b.iter().map(|x| x.to_string()).collect()),
+        )
+        .unwrap();
+        assert_eq!(res.plaintext.flag.as_deref(), Some("flag{padded!}"));
+    }
+
+    #[test]
+    fn test_insufficient_ciphertexts() {
+        let ns = moduli();
+        let m = BigUint::from_bytes_be(&[0x7f; 31]);
+        let e = BigUint::from(3u32);
+        let cs: Vec<String> = ns
+            .iter()
+            .take(2)
+            .map(|n| m.modpow(&e, n).to_string())
+            .collect();
+        let ns: Vec<String> = ns.iter().take(2).map(|n| n.to_string()).collect();
+        assert!(hastad_broadcast_attack(ns, cs, "3".into(), None, None).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::codec::{parse_bigint, render_bytes, BytesView};
+use super::lattice::lll_reduce;
+
+/// 背包密码参数: public_key / ciphertext / private_key 均为以空白或逗号分隔的整数列表
+///
+/// 给出私钥 (超递增序列 w)、模数 q 时直接解密, 乘数 r (b = r·w mod q) 可由公钥推出;
+/// 否则对每个密文和做低密度格攻击, method 为 `cjloss` / `lo`, 留空时依次尝试
+#[derive(Deserialize, Default)]
+pub struct KnapsackParams {
+    pub public_key: String,
+    pub ciphertext: String,
+    pub private_key: Option<String>,
+    pub modulus: Option<String>,
+    pub multiplier: Option<String>,
+    pub method: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KnapsackResult {
+    pub method: String,       // private_key / cjloss / lo
+    pub density: f64,         // n / log2(max b), 低于约 0.94 时 CJLOSS 通常可解
+    pub blocks: Vec<String>,  // 每个密文对应的比特串, 第 i 位对应公钥第 i 个元素
+    pub plaintext: BytesView, // 各块比特依次拼接后按高位在前组成字节
+}
+
+/// Merkle–Hellman 背包: 已知私钥时解密, 否则用 CJLOSS / Lagarias–Odlyzko 格攻击恢复明文比特
+#[tauri::command]
+pub fn knapsack_attack(params: KnapsackParams) -> Result<KnapsackResult, String> {
+    let public = parse_list(&params.public_key, "公钥")?;
+    let sums = parse_list(&params.ciphertext, "密文")?;
+    if sums.is_empty() {
+        return Err("缺少密文".into());
+    }
+    let private = match params.private_key.as_deref().map(str::trim) {
+        Some(s) if !s.is_empty() => Some(parse_list(s, "私钥")?),
+        _ => None,
+    };
+    let density = if public.is_empty() {
+        0.0
+    } else {
+        density(&public)
+    };
+
+    let (method, blocks) = match private {
+        Some(w) => {
+            let q = parse_optional(&params.modulus, "模数")?.ok_or("使用私钥解密需要模数")?;
+            if !q.is_positive() {
+                return Err("模数必须为正整数".into());
+            }
+            if !public.is_empty() && public.len() != w.len() {
+                return Err(format!(
+                    "私钥长度 {} 与公钥长度 {} 不一致",
+                    w.len(),
+                    public.len()
+                ));
+            }
+            let r = match parse_optional(&params.multiplier, "乘数")? {
+                Some(r) => r,
+                None => derive_multiplier(&public, &w, &q)?,
+            };
+            let blocks = sums
+                .iter()
+                .map(|c| decrypt(&w, &q, &r, c))
+                .collect::<Result<Vec<_>, String>>()?;
+            ("private_key", blocks)
+        }
+        None => {
+            if public.len() < 2 {
+                return Err("公钥至少需要 2 个元素".into());
+            }
+            if public.iter().any(|b| !b.is_positive()) {
+                return Err("公钥元素必须为正整数".into());
+            }
+            let methods: &[&str] = match params.method.as_deref().map(str::trim) {
+                Some("cjloss") => &["cjloss"],
+                Some("lo") => &["lo"],
+                Some("") | None => &["cjloss", "lo"],
+                Some(other) => return Err(format!("未知的格构造: {}", other)),
+            };
+            let mut found = None;
+            for &method in methods {
+                let blocks: Option<Vec<Vec<u8>>> = sums
+                    .iter()
+                    .map(|s| lattice_attack(&public, s, method))
+                    .collect();
+                if let Some(blocks) = blocks {
+                    found = Some((method, blocks));
+                    break;
+                }
+            }
+            found
+                .ok_or_else(|| format!("格约化未找到解 (密度 {:.3}), 背包可能密度过高", density))?
+        }
+    };
+
+    let bits: Vec<u8> = blocks.iter().flatten().copied().collect();
+    let bytes: Vec<u8> = bits
+        .chunks_exact(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &b| acc << 1 | b))
+        .collect();
+    Ok(KnapsackResult {
+        method: method.into(),
+        density,
+        blocks: blocks
+            .iter()
+            .map(|b| b.iter().map(|&x| char::from(b'0' + x)).collect())
+            .collect(),
+        plaintext: render_bytes(&bytes),
+    })
+}
+
+// ===================== 输入解析 =====================
+
+fn parse_list(s: &str, name: &str) -> Result<Vec<BigInt>, String> {
+    s.split(|c: char| c.is_whitespace() || c == ',' || c == '[' || c == ']')
+        .filter(|t| !t.is_empty())
+        .map(|t| parse_bigint(t).map_err(|e| format!("{}格式错误: {}", name, e)))
+        .collect()
+}
+
+fn parse_optional(s: &Option<String>, name: &str) -> Result<Option<BigInt>, String> {
+    match s.as_deref().map(str::trim) {
+        Some(t) if !t.is_empty() => parse_bigint(t)
+            .map(Some)
+            .map_err(|e| format!("{}格式错误: {}", name, e)),
+        _ => Ok(None),
+    }
+}
+
+fn density(public: &[BigInt]) -> f64 {
+    let max_bits = public.iter().map(|b| b.bits()).max().unwrap_or(1).max(1);
+    public.len() as f64 / max_bits as f64
+}
+
+// ===================== 私钥解密 =====================
+
+/// b[i] = r·w[i] (mod q), 取第一个与 q 互素的 w[i] 得 r = b[i]·w[i]^-1 (mod q)
+fn derive_multiplier(public: &[BigInt], w: &[BigInt], q: &BigInt) -> Result<BigInt, String> {
+    if public.is_empty() {
+        return Err("缺少乘数, 且没有公钥可供推出".into());
+    }
+    public
+        .iter()
+        .zip(w)
+        .find_map(|(b, wi)| wi.modinv(q).map(|inv| (b * inv).mod_floor(q)))
+        .ok_or_else(|| "私钥各项均与模数不互素, 无法推出乘数".into())
+}
+
+/// c' = c·r^-1 (mod q), 再按超递增序列从大到小贪心取位
+fn decrypt(w: &[BigInt], q: &BigInt, r: &BigInt, c: &BigInt) -> Result<Vec<u8>, String> {
+    let inv = r.modinv(q).ok_or("乘数与模数不互素")?;
+    let mut rest = (c * inv).mod_floor(q);
+    let mut bits = vec![0u8; w.len()];
+    for (i, wi) in w.iter().enumerate().rev() {
+        if &rest >= wi {
+            rest -= wi;
+            bits[i] = 1;
+        }
+    }
+    if !rest.is_zero() {
+        return Err(format!("密文 {} 无法由私钥表示, 请检查私钥与模数", c));
+    }
+    Ok(bits)
+}
+
+// ===================== 低密度攻击 =====================
+
+/// 构造格并约化, 在约化基中寻找满足 Σ m_i·b_i = s 的 0/1 向量
+///
+/// - `lo`: 行 (e_i, N·b_i) 与 (0, .., 0, -N·s), 目标短向量 (m, 0)
+/// - `cjloss`: 行 (2·e_i, N·b_i) 与 (1, .., 1, N·s), 目标短向量 (2m - 1, 0), 各分量为 ±1
+fn lattice_attack(public: &[BigInt], sum: &BigInt, method: &str) -> Option<Vec<u8>> {
+    let n = public.len();
+    // N > sqrt(n) 即可保证最后一列非零的向量足够长
+    let weight = BigInt::from((n as f64).sqrt().ceil() as u64 + 1);
+    let cjloss = method == "cjloss";
+    let mut basis = Vec::with_capacity(n + 1);
+    for (i, b) in public.iter().enumerate() {
+        let mut row = vec![BigInt::zero(); n + 1];
+        row[i] = if cjloss {
+            BigInt::from(2)
+        } else {
+            BigInt::one()
+        };
+        row[n] = &weight * b;
+        basis.push(row);
+    }
+    let mut last = vec![
+        if cjloss {
+            BigInt::one()
+        } else {
+            BigInt::zero()
+        };
+        n
+    ];
+    last.push(if cjloss {
+        &weight * sum
+    } else {
+        -&weight * sum
+    });
+    basis.push(last);
+
+    let total: BigInt = public.iter().sum();
+    let reduced = lll_reduce(&basis).ok()?;
+    for row in reduced {
+        if !row[n].is_zero() {
+            continue;
+        }
+        let values: Option<Vec<i64>> = row[..n].iter().map(ToPrimitive::to_i64).collect();
+        let Some(values) = values else {
+            continue;
+        };
+        for sign in [1i64, -1] {
+            let bits: Option<Vec<u8>> = values
+                .iter()
+                .map(|&v| match (cjloss, v * sign) {
+                    (_, 1) => Some(1),
+                    (true, -1) | (false, 0) => Some(0),
+                    _ => None,
+                })
+                .collect();
+            let Some(bits) = bits else {
+                continue;
+            };
+            let subset: BigInt = public
+                .iter()
+                .zip(&bits)
+                .filter(|(_, &m)| m == 1)
+                .map(|(b, _)| b)
+                .sum();
+            if &subset == sum {
+                return Some(bits);
+            }
+            // LO 格中也可能出现补集 (Σ b - s) 对应的向量
+            if !cjloss && subset == &total - sum {
+                return Some(bits.iter().map(|m| 1 - m).collect());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 以 w[i] = Σ w[..i] + i + 1 构造超递增私钥, 返回 (w, q, r, 公钥)
+    fn keypair(n: usize) -> (Vec<BigInt>, BigInt, BigInt, Vec<BigInt>) {
+        let mut w = Vec::with_capacity(n);
+        let mut total = BigInt::zero();
+        for i in 0..n {
+            let wi = &total + BigInt::from(i as u64 * 7 + 3);
+            total += &wi;
+            w.push(wi);
+        }
+        let q = (&total << 16u32) + BigInt::from(1_000_003u64);
+        let r = BigInt::from(0x1234_5678_9abc_def1u64);
+        let public = w.iter().map(|wi| (wi * &r).mod_floor(&q)).collect();
+        (w, q, r, public)
+    }
+
+    fn encrypt(public: &[BigInt], message: &[u8]) -> Vec<BigInt> {
+        let bits: Vec<u8> = message
+            .iter()
+            .flat_map(|&b| (0..8).rev().map(move |i| b >> i & 1))
+            .collect();
+        bits.chunks(public.len())
+            .map(|chunk| {
+                public
+                    .iter()
+                    .zip(chunk)
+                    .filter(|(_, &m)| m == 1)
+                    .map(|(b, _)| b)
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn join(values: &[BigInt]) -> String {
+        values
+            .iter()
+            .map(BigInt::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[test]
+    fn test_private_key_decrypt() {
+        let (w, q, _, public) = keypair(40);
+        let sums = encrypt(&public, b"flag{superincreasing}");
+        let res = knapsack_attack(KnapsackParams {
+            public_key: join(&public),
+            ciphertext: join(&sums),
+            private_key: Some(join(&w)),
+            modulus: Some(q.to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(res.method, "private_key");
+        assert_eq!(res.plaintext.flag.as_deref(), Some("flag{superincreasing}"));
+    }
+
+    #[test]
+    fn test_private_key_input_errors() {
+        let (w, q, _, public) = keypair(40);
+        let sums = encrypt(&public, b"bad");
+        let attack = |private: &[BigInt], modulus: &str| {
+            knapsack_attack(KnapsackParams {
+                public_key: join(&public),
+                ciphertext: join(&sums),
+                private_key: Some(join(private)),
+                modulus: Some(modulus.into()),
+                ..Default::default()
+            })
+        };
+        assert!(attack(&w, "0").unwrap_err().contains("正整数"));
+        assert!(attack(&w, "-7").is_err());
+        assert!(attack(&w[..39], &q.to_string())
+            .unwrap_err()
+            .contains("不一致"));
+    }
+
+    #[test]
+    fn test_low_density_attack() {
+        let (_, _, _, public) = keypair(48);
+        let sums = encrypt(&public, b"flag{cjloss}");
+        let res = knapsack_attack(KnapsackParams {
+            public_key: join(&public),
+            ciphertext: join(&sums),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(res.method, "cjloss");
+        assert!(res.density < 0.94);
+        assert_eq!(res.blocks.len(), 2);
+        assert_eq!(res.plaintext.utf8.as_deref(), Some("flag{cjloss}"));
+    }
+
+    #[test]
+    fn test_lagarias_odlyzko_and_errors() {
+        let (_, _, _, public) = keypair(24);
+        let sums = encrypt(&public, b"LO!");
+        let res = knapsack_attack(KnapsackParams {
+            public_key: join(&public),
+            ciphertext: join(&sums),
+            method: Some("lo".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(res.method, "lo");
+        assert_eq!(res.plaintext.utf8.as_deref(), Some("LO!"));
+
+        let err = knapsack_attack(KnapsackParams {
+            public_key: join(&public),
+            ciphertext: "1".into(),
+            private_key: Some("1 2 4".into()),
+            ..Default::default()
+        });
+        assert!(err.is_err());
+    }
+}
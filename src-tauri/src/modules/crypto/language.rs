@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use serde::Serialize;
+
+// 英文 2/3/4 元组计数 (取自英文技术文档的正文段落), `#n total` 行给出该阶的总数
+const ENGLISH_NGRAMS: &str = include_str!("data/english_ngrams.txt");
+// 内置的其他语言样本语料, 首次使用时统计
+const GERMAN_CORPUS: &str = include_str!("data/german.txt");
+const PINYIN_CORPUS: &str = include_str!("data/pinyin.txt");
+
+/// 英文字母频率 (A-Z)
+pub const ENGLISH_FREQ: [f64; 26] = [
+    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153,
+    0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056,
+    0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+];
+
+pub const DEFAULT_LANGUAGE: &str = "english";
+const BUILTIN_LANGUAGES: [&str; 3] = ["english", "german", "pinyin"];
+const MAX_ORDER: usize = 4;
+const MIN_CORPUS_LETTERS: usize = 200;
+// 没有字母的文本的适应度
+const NO_LETTERS_FITNESS: f64 = -20.0;
+// 不计入惩罚的常见标点
+const COMMON_PUNCTUATION: &str = ".,;:!?'\"-()";
+
+static MODELS: LazyLock<RwLock<HashMap<String, Arc<LanguageModel>>>> =
+    LazyLock::new(Default::default);
+
+#[derive(Serialize, Debug)]
+pub struct LanguageScore {
+    pub language: String,
+    pub letters: usize,
+    pub ngrams: Vec<Option<f64>>, // 1..=4 元组的平均 log10 概率, 字母数不足时为空
+    pub fitness: f64,             // 破解器排序所用的综合得分, 越大越像该语言
+    pub chi_squared: f64,         // 与该语言单字母频率的卡方距离, 越小越接近
+    pub ic: f64,                  // 重合指数
+    pub expected_ic: f64,         // 该语言的期望重合指数
+}
+
+/// 按指定语言模型给文本打分
+#[tauri::command]
+pub fn language_score(text: String, language: Option<String>) -> Result<LanguageScore, String> {
+    let model = language_model(language.as_deref())?;
+    let letters = letters(&text);
+    Ok(LanguageScore {
+        language: model.name.clone(),
+        letters: letters.len(),
+        ngrams: (1..=MAX_ORDER)
+            .map(|n| model.ngram_score(&letters, n))
+            .collect(),
+        fitness: model.fitness(&text),
+        chi_squared: model.chi_squared(&letters),
+        ic: index_of_coincidence(&letters),
+        expected_ic: model.expected_ic(),
+    })
+}
+
+/// 用一段语料注册 (或替换) 额外的语言模型, 返回全部可用的语言名
+#[tauri::command]
+pub fn language_register(name: String, corpus: String) -> Result<Vec<String>, String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err("语言名不能为空".into());
+    }
+    if BUILTIN_LANGUAGES.contains(&name.as_str()) {
+        return Err(format!("{} 是内置语言, 不能覆盖", name));
+    }
+    let model = LanguageModel::from_corpus(&name, &corpus)?;
+    let mut models = MODELS.write().map_err(|_| "语言模型状态损坏")?;
+    models.insert(name, Arc::new(model));
+    let mut names: Vec<String> = BUILTIN_LANGUAGES.iter().map(|s| s.to_string()).collect();
+    names.extend(
+        models
+            .keys()
+            .filter(|k| !BUILTIN_LANGUAGES.contains(&k.as_str()))
+            .cloned(),
+    );
+    names[BUILTIN_LANGUAGES.len()..].sort();
+    Ok(names)
+}
+
+/// 按名称取语言模型, 缺省为英文; 内置模型首次使用时构建
+pub fn language_model(name: Option<&str>) -> Result<Arc<LanguageModel>, String> {
+    let name = name
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(DEFAULT_LANGUAGE)
+        .to_lowercase();
+    if let Some(model) = MODELS.read().map_err(|_| "语言模型状态损坏")?.get(&name) {
+        return Ok(model.clone());
+    }
+    let model = match name.as_str() {
+        "english" => LanguageModel::english()?,
+        "german" => LanguageModel::from_corpus("german", GERMAN_CORPUS)?,
+        "pinyin" => LanguageModel::from_corpus("pinyin", PINYIN_CORPUS)?,
+        _ => return Err(format!("未知语言: {}", name)),
+    };
+    let mut models = MODELS.write().map_err(|_| "语言模型状态损坏")?;
+    Ok(models.entry(name).or_insert(Arc::new(model)).clone())
+}
+
+/// 文本中的 ASCII 字母, 转为 0..26
+pub fn letters(text: &str) -> Vec<u8> {
+    text.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase() - b'A')
+        .collect()
+}
+
+pub fn index_of_coincidence(letters: &[u8]) -> f64 {
+    let len = letters.len();
+    if len <= 1 {
+        return 0.0;
+    }
+    let counts = letter_counts(letters);
+    let numerator: usize = counts.iter().map(|&n| n * n.saturating_sub(1)).sum();
+    numerator as f64 / (len * (len - 1)) as f64
+}
+
+fn letter_counts(letters: &[u8]) -> [usize; 26] {
+    let mut counts = [0usize; 26];
+    for &l in letters {
+        counts[l as usize] += 1;
+    }
+    counts
+}
+
+// ===================== 语言模型 =====================
+
+/// n 元组的 log10 概率, 按 26 进制下标稠密存放; 未出现的 n 元组取 log10(0.01 / total)
+struct NgramTable {
+    log_probs: Vec<f32>,
+    floor: f64,
+}
+
+impl NgramTable {
+    fn new(n: usize, counts: &HashMap<usize, u64>, total: u64) -> Self {
+        let total = total.max(1) as f64;
+        let floor = (0.01 / total).log10();
+        let mut log_probs = vec![floor as f32; 26usize.pow(n as u32)];
+        for (&idx, &count) in counts {
+            log_probs[idx] = (count as f64 / total).log10() as f32;
+        }
+        NgramTable { log_probs, floor }
+    }
+}
+
+pub struct LanguageModel {
+    pub name: String,
+    freq: [f64; 26],
+    tables: Vec<NgramTable>, // 下标 n - 1
+}
+
+impl LanguageModel {
+    fn english() -> Result<Self, String> {
+        let mut counts = vec![HashMap::new(); MAX_ORDER + 1];
+        let mut totals = vec![0u64; MAX_ORDER + 1];
+        for line in ENGLISH_NGRAMS.lines() {
+            let (gram, count) = line.split_once(' ').ok_or("内置 n 元组表格式错误")?;
+            let count: u64 = count.parse().map_err(|_| "内置 n 元组表格式错误")?;
+            if let Some(order) = gram.strip_prefix('#') {
+                let n: usize = order.parse().map_err(|_| "内置 n 元组表格式错误")?;
+                totals[n] = count;
+            } else {
+                counts[gram.len()].insert(gram_index(&letters(gram)), count);
+            }
+        }
+        // 单字母使用标准频率
+        let scale = 1_000_000f64;
+        counts[1] = (0..26)
+            .map(|i| (i, (ENGLISH_FREQ[i] * scale) as u64))
+            .collect();
+        totals[1] = scale as u64;
+        Ok(Self::from_counts("english", &counts, &totals))
+    }
+
+    /// 由语料统计 1..=4 元组, 德语变音字母按 AE / OE / UE / SS 展开
+    fn from_corpus(name: &str, corpus: &str) -> Result<Self, String> {
+        let normalized = corpus
+            .replace(['ä', 'Ä'], "AE")
+            .replace(['ö', 'Ö'], "OE")
+            .replace(['ü', 'Ü'], "UE")
+            .replace('ß', "SS");
+        let text = letters(&normalized);
+        if text.len() < MIN_CORPUS_LETTERS {
+            return Err(format!("语料至少需要 {} 个字母", MIN_CORPUS_LETTERS));
+        }
+        let mut counts = vec![HashMap::new(); MAX_ORDER + 1];
+        let mut totals = vec![0u64; MAX_ORDER + 1];
+        for n in 1..=MAX_ORDER {
+            for gram in text.windows(n) {
+                *counts[n].entry(gram_index(gram)).or_insert(0) += 1;
+                totals[n] += 1;
+            }
+        }
+        Ok(Self::from_counts(name, &counts, &totals))
+    }
+
+    fn from_counts(name: &str, counts: &[HashMap<usize, u64>], totals: &[u64]) -> Self {
+        let mut freq = [0f64; 26];
+        for (&i, &c) in &counts[1] {
+            freq[i] = c as f64 / totals[1].max(1) as f64;
+        }
+        LanguageModel {
+            name: name.to_string(),
+            freq,
+            tables: (1..=MAX_ORDER)
+                .map(|n| NgramTable::new(n, &counts[n], totals[n]))
+                .collect(),
+        }
+    }
+
+    /// n 元组的平均 log10 概率, 字母数少于 n 时为 None
+    pub fn ngram_score(&self, letters: &[u8], n: usize) -> Option<f64> {
+        if n == 0 || n > MAX_ORDER || letters.len() < n {
+            return None;
+        }
+        let table = &self.tables[n - 1];
+        let grams = letters.len() - n + 1;
+        let sum: f64 = letters
+            .windows(n)
+            .map(|g| table.log_probs[gram_index(g)] as f64)
+            .sum();
+        Some(sum / grams as f64)
+    }
+
+    /// 综合得分: 按字母数取最高可用阶 (至多四元组) 的平均 log10 概率,
+    /// 少见符号 (ROT47 等变换的常见产物) 按未出现的 n 元组计入平均值
+    pub fn fitness(&self, text: &str) -> f64 {
+        let letters = letters(text);
+        if letters.is_empty() {
+            return NO_LETTERS_FITNESS;
+        }
+        let n = letters.len().min(MAX_ORDER);
+        let grams = (letters.len() - n + 1) as f64;
+        let mean = self.ngram_score(&letters, n).unwrap_or(NO_LETTERS_FITNESS);
+        let symbols = text
+            .chars()
+            .filter(|c| c.is_ascii_punctuation() && !COMMON_PUNCTUATION.contains(*c))
+            .count() as f64;
+        (mean * grams + self.tables[n - 1].floor * symbols) / (grams + symbols)
+    }
+
+    /// Σ (观测 - 期望)² / 期望, 期望按该语言的单字母频率
+    pub fn chi_squared(&self, letters: &[u8]) -> f64 {
+        let len = letters.len() as f64;
+        let counts = letter_counts(letters);
+        (0..26)
+            .map(|i| {
+                let expected = self.freq[i].max(1e-5) * len;
+                (counts[i] as f64 - expected).powi(2) / expected
+            })
+            .sum()
+    }
+
+    pub fn expected_ic(&self) -> f64 {
+        self.freq.iter().map(|p| p * p).sum()
+    }
+}
+
+fn gram_index(gram: &[u8]) -> usize {
+    gram.iter().fold(0, |idx, &l| idx * 26 + l as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_scores() {
+        let english =
+            language_score("The quick brown fox jumps over the lazy dog".into(), None).unwrap();
+        let noise = language_score("Xqzv jkwp fgqx ztvb qxjz kvwq".into(), None).unwrap();
+        assert_eq!(english.language, "english");
+        assert!(english.fitness > noise.fitness + 1.0);
+        assert!(english.chi_squared < noise.chi_squared);
+        assert!((english.expected_ic - 0.0655).abs() < 0.001);
+        // 同样的字母夹杂大量符号时得分下降
+        let model = language_model(None).unwrap();
+        assert!(model.fitness("attack at dawn") > model.fitness("a{t}t[a]c#k a@t d$a%w^n"));
+    }
+
+    #[test]
+    fn test_builtin_and_custom_languages() {
+        let german = language_model(Some("German")).unwrap();
+        let english = language_model(Some("english")).unwrap();
+        let text = "Die Sonne scheint und die Kinder spielen im Garten";
+        assert!(german.fitness(text) > english.fitness(text));
+
+        let pinyin = language_model(Some("pinyin")).unwrap();
+        let text = "wo men yi qi qu xue xiao xue xi zhong wen";
+        assert!(pinyin.fitness(text) > english.fitness(text));
+
+        assert!(language_register("english".into(), "abc".into()).is_err());
+        assert!(language_register("tiny".into(), "abc".into()).is_err());
+        let names =
+            language_register("latin".into(), "lorem ipsum dolor sit amet ".repeat(20)).unwrap();
+        assert!(names.contains(&"latin".to_string()));
+        assert!(language_model(Some("klingon")).is_err());
+    }
+
+    #[test]
+    fn test_index_of_coincidence() {
+        assert_eq!(index_of_coincidence(&letters("AAAA")), 1.0);
+        assert_eq!(index_of_coincidence(&letters("ABCD")), 0.0);
+        assert_eq!(index_of_coincidence(&letters("A")), 0.0);
+    }
+}
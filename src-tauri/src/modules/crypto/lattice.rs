@@ -0,0 +1,749 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+// Lovász 条件参数 δ = 99/100 (比经典的 3/4 约化得更充分, Coppersmith 类攻击需要)
+const DELTA_NUM: i64 = 99;
+const DELTA_DEN: i64 = 100;
+
+// 浮点 LLL 的迭代上限, 超过后认为精度不足, 退回整数 LLL
+const FP_MAX_ITERATIONS: usize = 2_000_000;
+const FP_MAX_REDUCE_ROUNDS: usize = 64;
+
+/// LLL 约化: 优先使用浮点 Gram-Schmidt 的 Schnorr-Euchner 算法,
+/// 精度不足时退回精确的整数 LLL。输入的行向量必须线性无关。
+pub fn lll_reduce(basis: &[Vec<BigInt>]) -> Result<Vec<Vec<BigInt>>, String> {
+    match lll_reduce_fp(basis) {
+        Ok(reduced) => Ok(reduced),
+        Err(_) => lll_reduce_exact(basis),
+    }
+}
+
+/// 整数 LLL 约化 (Cohen, Algorithm 2.6.7)
+///
+/// 全程只使用整数运算 (d_i 与 λ_ij 均为整数), 结果精确, 不依赖浮点精度, 但大格上较慢。
+pub fn lll_reduce_exact(basis: &[Vec<BigInt>]) -> Result<Vec<Vec<BigInt>>, String> {
+    let n = basis.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let dim = basis[0].len();
+    if basis.iter().any(|row| row.len() != dim) {
+        return Err("格基向量维度不一致".into());
+    }
+
+    let mut b: Vec<Vec<BigInt>> = basis.to_vec();
+    // d[0] = 1, d[i + 1] 对应 Cohen 记号中的 d_i
+    let mut d: Vec<BigInt> = vec![BigInt::zero(); n + 1];
+    let mut lambda: Vec<Vec<BigInt>> = vec![vec![BigInt::zero(); n]; n];
+
+    d[0] = BigInt::one();
+    d[1] = dot(&b[0], &b[0]);
+    if d[1].is_zero() {
+        return Err("格基中存在零向量".into());
+    }
+    if n == 1 {
+        return Ok(b);
+    }
+
+    let mut k = 1usize;
+    let mut k_max = 0usize;
+
+    while k < n {
+        if k > k_max {
+            k_max = k;
+            for j in 0..=k {
+                let mut u = dot(&b[k], &b[j]);
+                for i in 0..j {
+                    u = (&d[i + 1] * &u - &lambda[k][i] * &lambda[j][i]) / &d[i];
+                }
+                if j < k {
+                    lambda[k][j] = u;
+                } else {
+                    if u.is_zero() {
+                        return Err("格基向量线性相关".into());
+                    }
+                    d[k + 1] = u;
+                }
+            }
+        }
+
+        size_reduce(&mut b, &mut lambda, &d, k, k - 1);
+
+        let lhs = BigInt::from(DELTA_DEN) * &d[k + 1] * &d[k - 1];
+        let rhs = BigInt::from(DELTA_NUM) * &d[k] * &d[k]
+            - BigInt::from(DELTA_DEN) * &lambda[k][k - 1] * &lambda[k][k - 1];
+
+        if lhs < rhs {
+            swap_rows(&mut b, &mut lambda, &mut d, k, k_max);
+            k = if k > 1 { k - 1 } else { 1 };
+        } else {
+            for l in (0..k - 1).rev() {
+                size_reduce(&mut b, &mut lambda, &d, k, l);
+            }
+            k += 1;
+        }
+    }
+
+    Ok(b)
+}
+
+fn dot(a: &[BigInt], b: &[BigInt]) -> BigInt {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+fn size_reduce(
+    b: &mut [Vec<BigInt>],
+    lambda: &mut [Vec<BigInt>],
+    d: &[BigInt],
+    k: usize,
+    l: usize,
+) {
+    let two_lambda: BigInt = &lambda[k][l] << 1;
+    if two_lambda.abs() <= d[l + 1] {
+        return;
+    }
+    // q = round(λ_kl / d_l)
+    let q = (&two_lambda + &d[l + 1]).div_floor(&(&d[l + 1] * 2));
+
+    let row_l = b[l].clone();
+    for (x, y) in b[k].iter_mut().zip(row_l.iter()) {
+        *x -= &q * y;
+    }
+    lambda[k][l] -= &q * &d[l + 1];
+    let lambda_l = lambda[l][..l].to_vec();
+    for (x, y) in lambda[k][..l].iter_mut().zip(lambda_l.iter()) {
+        *x -= &q * y;
+    }
+}
+
+fn swap_rows(
+    b: &mut [Vec<BigInt>],
+    lambda: &mut [Vec<BigInt>],
+    d: &mut [BigInt],
+    k: usize,
+    k_max: usize,
+) {
+    b.swap(k, k - 1);
+    let (head, tail) = lambda.split_at_mut(k);
+    head[k - 1][..k - 1].swap_with_slice(&mut tail[0][..k - 1]);
+
+    let lam = lambda[k][k - 1].clone();
+    let big_b = (&d[k - 1] * &d[k + 1] + &lam * &lam) / &d[k];
+
+    for row in lambda.iter_mut().take(k_max + 1).skip(k + 1) {
+        let t = row[k].clone();
+        row[k] = (&d[k + 1] * &row[k - 1] - &lam * &t) / &d[k];
+        row[k - 1] = (&big_b * &t + &lam * &row[k]) / &d[k + 1];
+    }
+
+    d[k] = big_b;
+}
+
+// ===================== 浮点 LLL =====================
+
+/// 带独立指数的双精度浮点数 m·2^e, 避免大整数转换为 f64 时溢出
+#[derive(Clone, Copy, Debug)]
+struct ExtFloat {
+    m: f64,
+    e: i64,
+}
+
+impl ExtFloat {
+    const ZERO: ExtFloat = ExtFloat { m: 0.0, e: 0 };
+
+    fn new(m: f64, e: i64) -> Self {
+        if m == 0.0 || !m.is_finite() {
+            return Self::ZERO;
+        }
+        // 规范化到 0.5 <= |m| < 1
+        let bits = m.to_bits();
+        let raw_exp = ((bits >> 52) & 0x7ff) as i64;
+        if raw_exp == 0 {
+            return Self::new(m * 2f64.powi(64), e - 64);
+        }
+        let mant = f64::from_bits((bits & !(0x7ff << 52)) | (1022 << 52));
+        ExtFloat {
+            m: mant,
+            e: e + raw_exp - 1022,
+        }
+    }
+
+    fn from_f64(v: f64) -> Self {
+        Self::new(v, 0)
+    }
+
+    fn from_bigint(x: &BigInt) -> Self {
+        let bits = x.bits();
+        if bits <= 64 {
+            return Self::from_f64(x.to_f64().unwrap_or(0.0));
+        }
+        let shift = bits - 64;
+        let top = (x.abs() >> shift).to_f64().unwrap_or(0.0);
+        let top = if x.is_negative() { -top } else { top };
+        Self::new(top, shift as i64)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.m == 0.0
+    }
+
+    fn abs(self) -> Self {
+        ExtFloat {
+            m: self.m.abs(),
+            e: self.e,
+        }
+    }
+
+    /// 与 2^k 比较绝对值大小
+    fn exceeds(&self, threshold: f64) -> bool {
+        !self.is_zero() && (self.abs() - Self::from_f64(threshold)).m > 0.0
+    }
+
+    /// 四舍五入为整数 (可能远超 i64 范围)
+    fn round_to_bigint(&self) -> BigInt {
+        if self.is_zero() || self.e < 0 {
+            return BigInt::zero();
+        }
+        if self.e <= 53 {
+            let v = (self.m * 2f64.powi(self.e as i32)).round();
+            return BigInt::from(v as i64);
+        }
+        let mant = (self.m * 2f64.powi(53)) as i64;
+        BigInt::from(mant) << (self.e - 53) as u64
+    }
+}
+
+impl std::ops::Add for ExtFloat {
+    type Output = ExtFloat;
+    fn add(self, rhs: ExtFloat) -> ExtFloat {
+        if self.is_zero() {
+            return rhs;
+        }
+        if rhs.is_zero() {
+            return self;
+        }
+        let diff = self.e - rhs.e;
+        if diff > 64 {
+            self
+        } else if diff < -64 {
+            rhs
+        } else if diff >= 0 {
+            ExtFloat::new(self.m + rhs.m * 2f64.powi(-diff as i32), self.e)
+        } else {
+            ExtFloat::new(self.m * 2f64.powi(diff as i32) + rhs.m, rhs.e)
+        }
+    }
+}
+
+impl std::ops::Neg for ExtFloat {
+    type Output = ExtFloat;
+    fn neg(self) -> ExtFloat {
+        ExtFloat {
+            m: -self.m,
+            e: self.e,
+        }
+    }
+}
+
+impl std::ops::Sub for ExtFloat {
+    type Output = ExtFloat;
+    fn sub(self, rhs: ExtFloat) -> ExtFloat {
+        self + (-rhs)
+    }
+}
+
+impl std::ops::Mul for ExtFloat {
+    type Output = ExtFloat;
+    fn mul(self, rhs: ExtFloat) -> ExtFloat {
+        ExtFloat::new(self.m * rhs.m, self.e + rhs.e)
+    }
+}
+
+impl std::ops::Div for ExtFloat {
+    type Output = ExtFloat;
+    fn div(self, rhs: ExtFloat) -> ExtFloat {
+        if rhs.is_zero() {
+            return ExtFloat::ZERO;
+        }
+        ExtFloat::new(self.m / rhs.m, self.e - rhs.e)
+    }
+}
+
+fn fp_dot(a: &[ExtFloat], b: &[ExtFloat]) -> ExtFloat {
+    a.iter()
+        .zip(b.iter())
+        .fold(ExtFloat::ZERO, |acc, (x, y)| acc + *x * *y)
+}
+
+/// Schnorr-Euchner 浮点 LLL: 基向量保持精确整数, Gram-Schmidt 系数用 ExtFloat 近似
+fn lll_reduce_fp(basis: &[Vec<BigInt>]) -> Result<Vec<Vec<BigInt>>, String> {
+    let n = basis.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let dim = basis[0].len();
+    if basis.iter().any(|row| row.len() != dim) {
+        return Err("格基向量维度不一致".into());
+    }
+
+    let approx =
+        |row: &[BigInt]| -> Vec<ExtFloat> { row.iter().map(ExtFloat::from_bigint).collect() };
+    let delta = ExtFloat::from_f64(DELTA_NUM as f64 / DELTA_DEN as f64);
+    // 浮点内积相对误差过大时改用精确内积 (Schnorr-Euchner 的 2^(-τ/2) 判据)
+    let cancel = ExtFloat::from_f64(2f64.powi(-26));
+
+    let mut b: Vec<Vec<BigInt>> = basis.to_vec();
+    let mut bf: Vec<Vec<ExtFloat>> = b.iter().map(|row| approx(row)).collect();
+    let mut norms: Vec<ExtFloat> = bf.iter().map(|row| fp_dot(row, row)).collect();
+    let mut r = vec![vec![ExtFloat::ZERO; n]; n];
+    let mut mu = vec![vec![ExtFloat::ZERO; n]; n];
+
+    r[0][0] = norms[0];
+    if r[0][0].is_zero() {
+        return Err("格基中存在零向量".into());
+    }
+
+    let mut k = 1usize;
+    let mut iterations = 0usize;
+    while k < n {
+        iterations += 1;
+        if iterations > FP_MAX_ITERATIONS {
+            return Err("浮点 LLL 未收敛".into());
+        }
+        if k == 1 {
+            r[0][0] = norms[0];
+        }
+
+        let mut rounds = 0usize;
+        loop {
+            rounds += 1;
+            if rounds > FP_MAX_REDUCE_ROUNDS {
+                return Err("浮点 LLL 精度不足".into());
+            }
+
+            // 计算第 k 行的 Gram-Schmidt 系数
+            for j in 0..k {
+                let mut s = fp_dot(&bf[k], &bf[j]);
+                let scale = norms[k] * norms[j];
+                if (s * s - cancel * cancel * scale).m < 0.0 {
+                    s = ExtFloat::from_bigint(&dot(&b[k], &b[j]));
+                }
+                for i in 0..j {
+                    s = s - mu[j][i] * r[k][i];
+                }
+                r[k][j] = s;
+                mu[k][j] = s / r[j][j];
+            }
+
+            // 尺寸约化
+            let mut changed = false;
+            for j in (0..k).rev() {
+                if !mu[k][j].exceeds(0.51) {
+                    continue;
+                }
+                let q = mu[k][j].round_to_bigint();
+                if q.is_zero() {
+                    continue;
+                }
+                let qf = ExtFloat::from_bigint(&q);
+                let row_j = b[j].clone();
+                for (x, y) in b[k].iter_mut().zip(row_j.iter()) {
+                    *x -= &q * y;
+                }
+                let mu_j = mu[j][..j].to_vec();
+                for (x, y) in mu[k][..j].iter_mut().zip(mu_j.iter()) {
+                    *x = *x - qf * *y;
+                }
+                mu[k][j] = mu[k][j] - qf;
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+            bf[k] = approx(&b[k]);
+            norms[k] = fp_dot(&bf[k], &bf[k]);
+        }
+
+        let mut r_kk = norms[k];
+        for j in 0..k {
+            r_kk = r_kk - mu[k][j] * r[k][j];
+        }
+        if norms[k].is_zero() {
+            return Err("格基向量线性相关".into());
+        }
+        // 前 k 个向量已约化, 此时若 r_kk 因抵消变为非正, 说明它远小于 r_(k-1), 直接交换即可
+        if r_kk.m < 0.0 {
+            r_kk = ExtFloat::ZERO;
+        }
+        r[k][k] = r_kk;
+
+        // Lovász 条件
+        let lhs = delta * r[k - 1][k - 1];
+        let rhs = r_kk + mu[k][k - 1] * mu[k][k - 1] * r[k - 1][k - 1];
+        if (lhs - rhs).m > 0.0 {
+            b.swap(k, k - 1);
+            bf.swap(k, k - 1);
+            norms.swap(k, k - 1);
+            k = if k > 1 { k - 1 } else { 1 };
+        } else {
+            k += 1;
+        }
+    }
+
+    Ok(b)
+}
+
+// ===================== 整系数多项式 =====================
+
+/// 计算整系数多项式在 x 处的值, 系数按升幂排列
+pub fn poly_eval(coeffs: &[BigInt], x: &BigInt) -> BigInt {
+    coeffs
+        .iter()
+        .rev()
+        .fold(BigInt::zero(), |acc, c| acc * x + c)
+}
+
+fn poly_derivative(coeffs: &[BigInt]) -> Vec<BigInt> {
+    coeffs
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| c * BigInt::from(i))
+        .collect()
+}
+
+fn trim(coeffs: &[BigInt]) -> Vec<BigInt> {
+    let mut v = coeffs.to_vec();
+    while v.len() > 1 && v.last().is_some_and(|c| c.is_zero()) {
+        v.pop();
+    }
+    v
+}
+
+const ROOT_PRIMES: [u64; 12] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 101, 1009];
+
+/// 求整系数多项式在 |x| <= bound 范围内的全部整数根
+///
+/// 先在若干小素数 p 下穷举根, 再用 Hensel 提升到 p^k > 2·bound,
+/// 最后回到整数上验证。对单个素数上的重根会换下一个素数继续尝试, 整数上的重根通过导数求出。
+pub fn integer_roots(coeffs: &[BigInt], bound: &BigInt) -> Vec<BigInt> {
+    let f = trim(coeffs);
+    let mut roots: Vec<BigInt> = Vec::new();
+    if f.len() <= 1 {
+        return roots;
+    }
+
+    // 去掉 x^k 因子, 记录根 0
+    let mut f = f;
+    if f[0].is_zero() {
+        roots.push(BigInt::zero());
+        while f.len() > 1 && f[0].is_zero() {
+            f.remove(0);
+        }
+        if f.len() <= 1 {
+            return roots;
+        }
+    }
+
+    let df = poly_derivative(&f);
+    let limit: BigInt = (bound.abs() << 1) + 1;
+
+    for &p in ROOT_PRIMES.iter() {
+        let p_big = BigInt::from(p);
+        if (f.last().unwrap() % &p_big).is_zero() {
+            continue;
+        }
+
+        for r in 0..p {
+            let r_big = BigInt::from(r);
+            if !(poly_eval(&f, &r_big) % &p_big).is_zero() {
+                continue;
+            }
+            if (poly_eval(&df, &r_big) % &p_big).is_zero() {
+                continue;
+            }
+
+            // Hensel 提升 (二次收敛): r <- r - f(r) / f'(r) mod p^(2^i)
+            let mut modulus = p_big.clone();
+            let mut root = r_big;
+            while modulus < limit {
+                modulus = &modulus * &modulus;
+                let fr = poly_eval(&f, &root).mod_floor(&modulus);
+                let dfr = poly_eval(&df, &root).mod_floor(&modulus);
+                let inv = match dfr.modinv(&modulus) {
+                    Some(v) => v,
+                    None => break,
+                };
+                root = (&root - fr * inv).mod_floor(&modulus);
+            }
+
+            let half = &modulus >> 1;
+            let candidate = if root > half { root - &modulus } else { root };
+            if candidate.abs() <= bound.abs()
+                && poly_eval(&f, &candidate).is_zero()
+                && !roots.contains(&candidate)
+            {
+                roots.push(candidate);
+            }
+        }
+    }
+
+    // 重根在每个素数下导数都为零, 无法直接提升; 它们同时是 f' 的根, 递归求出后回代验证
+    if f.len() > 2 {
+        for r in integer_roots(&df, bound) {
+            if poly_eval(&f, &r).is_zero() && !roots.contains(&r) {
+                roots.push(r);
+            }
+        }
+    }
+
+    roots.sort();
+    roots
+}
+
+/// 对多项式的系数做内容约化 (除以所有系数的 gcd), 方便后续求根
+pub fn primitive_part(coeffs: &[BigInt]) -> Vec<BigInt> {
+    let g = coeffs.iter().fold(BigInt::zero(), |acc, c| acc.gcd(c));
+    if g.is_zero() || g.is_one() {
+        return coeffs.to_vec();
+    }
+    coeffs.iter().map(|c| c / &g).collect()
+}
+
+/// 用 Bareiss 无分数消元计算整数方阵的行列式
+pub fn determinant(matrix: &[Vec<BigInt>]) -> BigInt {
+    let n = matrix.len();
+    if n == 0 {
+        return BigInt::one();
+    }
+    let mut a: Vec<Vec<BigInt>> = matrix.to_vec();
+    let mut sign = BigInt::one();
+    let mut prev = BigInt::one();
+
+    for k in 0..n - 1 {
+        if a[k][k].is_zero() {
+            match (k + 1..n).find(|&i| !a[i][k].is_zero()) {
+                Some(i) => {
+                    a.swap(k, i);
+                    sign = -sign;
+                }
+                None => return BigInt::zero(),
+            }
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                a[i][j] = (&a[i][j] * &a[k][k] - &a[i][k] * &a[k][j]) / &prev;
+            }
+        }
+        prev = a[k][k].clone();
+    }
+
+    sign * &a[n - 1][n - 1]
+}
+
+// ===================== Coppersmith =====================
+
+fn poly_mul(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    let mut out = vec![BigInt::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    out
+}
+
+/// Howgrave-Graham 构造的参数 (m, t), 格维数为 deg·m + t
+pub fn coppersmith_shifts(delta: usize, beta: f64, epsilon: f64) -> (usize, usize) {
+    let d = delta as f64;
+    let m = (beta * beta / (d * epsilon)).max(7.0 * beta / d).ceil() as usize;
+    let t = (d * m as f64 * (1.0 / beta - 1.0)).floor() as usize;
+    (m, t)
+}
+
+/// 一元 Coppersmith 小根 (Howgrave-Graham 构造)
+///
+/// 求 f(x) ≡ 0 (mod b) 且 |x| <= x_bound 的根, 其中 b 是 modulus 的未知因子且 b >= modulus^beta。
+/// beta = 1 时即为 f(x) ≡ 0 (mod modulus)。epsilon 越小格越大, 可求的根界越接近 modulus^(beta²/deg)。
+pub fn small_roots(
+    f: &[BigInt],
+    modulus: &BigInt,
+    x_bound: &BigInt,
+    beta: f64,
+    epsilon: f64,
+) -> Vec<BigInt> {
+    let f = trim(f);
+    let delta = f.len() - 1;
+    if delta == 0 {
+        return Vec::new();
+    }
+
+    // 首一化
+    let lc_inv = match f[delta].mod_floor(modulus).modinv(modulus) {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+    let f: Vec<BigInt> = f.iter().map(|c| (c * &lc_inv).mod_floor(modulus)).collect();
+
+    let (m, t) = coppersmith_shifts(delta, beta, epsilon);
+
+    let mut f_pows = vec![vec![BigInt::one()]];
+    for i in 1..=m {
+        let next = poly_mul(&f_pows[i - 1], &f);
+        f_pows.push(next);
+    }
+
+    // g_ij(x) = x^j · N^(m-i) · f^i, 以及 h_j(x) = x^j · f^m
+    let mut shifts: Vec<Vec<BigInt>> = Vec::new();
+    for (i, f_i) in f_pows.iter().take(m).enumerate() {
+        let n_pow = modulus.pow((m - i) as u32);
+        for j in 0..delta {
+            let mut g = vec![BigInt::zero(); j];
+            g.extend(f_i.iter().map(|c| c * &n_pow));
+            shifts.push(g);
+        }
+    }
+    for j in 0..t {
+        let mut h = vec![BigInt::zero(); j];
+        h.extend(f_pows[m].iter().cloned());
+        shifts.push(h);
+    }
+
+    let dim = shifts.len();
+    let x_pows: Vec<BigInt> = (0..dim).map(|k| x_bound.pow(k as u32)).collect();
+    let basis: Vec<Vec<BigInt>> = shifts
+        .iter()
+        .map(|g| {
+            (0..dim)
+                .map(|k| {
+                    g.get(k)
+                        .map(|c| c * &x_pows[k])
+                        .unwrap_or_else(BigInt::zero)
+                })
+                .collect()
+        })
+        .collect();
+
+    let reduced = match lll_reduce(&basis) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+
+    // b >= N^beta 的判定用比特长度近似
+    let min_bits = (modulus.bits() as f64 * beta).floor() as u64;
+    let mut roots: Vec<BigInt> = Vec::new();
+    for row in reduced.iter().take(3) {
+        let g: Vec<BigInt> = row.iter().zip(x_pows.iter()).map(|(v, x)| v / x).collect();
+        for r in integer_roots(&g, x_bound) {
+            let value = poly_eval(&f, &r).mod_floor(modulus);
+            let divisor = if value.is_zero() {
+                modulus.clone()
+            } else {
+                value.gcd(modulus)
+            };
+            if divisor.bits() >= min_bits && !roots.contains(&r) {
+                roots.push(r);
+            }
+        }
+        if !roots.is_empty() {
+            break;
+        }
+    }
+    roots.sort();
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(v: &[i64]) -> Vec<BigInt> {
+        v.iter().map(|&x| BigInt::from(x)).collect()
+    }
+
+    #[test]
+    fn test_lll_textbook_basis() {
+        // 经典示例: [[1,1,1],[-1,0,2],[3,5,6]] 约化后为 [[0,1,0],[1,0,1],[-1,0,2]]
+        let basis = vec![row(&[1, 1, 1]), row(&[-1, 0, 2]), row(&[3, 5, 6])];
+        let reduced = lll_reduce(&basis).unwrap();
+        assert_eq!(reduced[0], row(&[0, 1, 0]));
+        assert_eq!(reduced[1], row(&[1, 0, 1]));
+        assert_eq!(reduced[2], row(&[-1, 0, 2]));
+    }
+
+    #[test]
+    fn test_lll_fp_matches_exact() {
+        // 构造一个条目较大的 Coppersmith 风格下三角格, 两种实现的首向量长度应一致
+        let n = BigInt::from(1u64) << 300;
+        let mut basis = Vec::new();
+        for i in 0..6u32 {
+            let mut r = vec![BigInt::zero(); 6];
+            for (j, x) in r.iter_mut().enumerate().take(i as usize) {
+                *x = BigInt::from((j as u64 + 3) * 0x1234_5678_9abc) << (40 * j as u64);
+            }
+            r[i as usize] = &n >> (30 * i as u64);
+            basis.push(r);
+        }
+        let fp = lll_reduce_fp(&basis).unwrap();
+        let exact = lll_reduce_exact(&basis).unwrap();
+        assert_eq!(dot(&fp[0], &fp[0]), dot(&exact[0], &exact[0]));
+    }
+
+    #[test]
+    fn test_lll_dependent_basis() {
+        let basis = vec![row(&[1, 2]), row(&[2, 4])];
+        assert!(lll_reduce(&basis).is_err());
+    }
+
+    #[test]
+    fn test_integer_roots() {
+        // (x - 12345678901)(x + 42)(x - 7) = x^3 - 12345678866x^2 - 518518913... 直接展开验证
+        let r1 = BigInt::from(12345678901i64);
+        let r2 = BigInt::from(-42);
+        let r3 = BigInt::from(7);
+        let mut poly = vec![BigInt::one()];
+        for r in [&r1, &r2, &r3] {
+            let mut next = vec![BigInt::zero(); poly.len() + 1];
+            for (i, c) in poly.iter().enumerate() {
+                next[i + 1] += c;
+                next[i] -= c * r;
+            }
+            poly = next;
+        }
+        let roots = integer_roots(&poly, &BigInt::from(1u64 << 40));
+        assert_eq!(roots, vec![r2, r3, r1]);
+
+        // 整数上的重根: (x - 1000003)^2 (x + 3) = x^3 - 2000003x^2 + 999999999991x + 3000018000027
+        let poly = row(&[3000018000027, 999999999991, -2000003, 1]);
+        assert_eq!(
+            integer_roots(&poly, &BigInt::from(1u64 << 40)),
+            row(&[-3, 1000003])
+        );
+    }
+
+    #[test]
+    fn test_small_roots_modulus() {
+        // f(x) = (x0 + c)^3 - y (mod N), x0 为 48 位小根
+        let n = "51530020546182678294198851407836155777005267024504313283698683227788490135697"
+            .parse::<BigInt>()
+            .unwrap();
+        let x0 = BigInt::from(0xabcd_ef01_2345u64);
+        let c: BigInt = BigInt::one() << 200;
+        let y = (&x0 + &c).modpow(&BigInt::from(3), &n);
+        let f = vec![
+            (c.pow(3) - &y).mod_floor(&n),
+            (c.pow(2) * BigInt::from(3)).mod_floor(&n),
+            (&c * BigInt::from(3)).mod_floor(&n),
+            BigInt::one(),
+        ];
+        let roots = small_roots(&f, &n, &(BigInt::one() << 50), 1.0, 1.0 / 8.0);
+        assert_eq!(roots, vec![x0]);
+    }
+
+    #[test]
+    fn test_determinant() {
+        let m = vec![row(&[2, -3, 1]), row(&[2, 0, -1]), row(&[1, 4, 5])];
+        assert_eq!(determinant(&m), BigInt::from(49));
+    }
+}
@@ -0,0 +1,442 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::codec::parse_bigint;
+use super::lattice::lll_reduce;
+
+const DEFAULT_PREDICT: usize = 10;
+// 预测 / 倒推个数上限
+const MAX_LIST: usize = 10_000;
+// 截断输出时参与格约化的输出个数上限
+const MAX_LATTICE_OUTPUTS: usize = 32;
+// 格方法失败时, 丢弃位数不超过该值则逐一枚举第一个状态的低位
+const MAX_BRUTE_FORCE_BITS: u32 = 20;
+// 乘数方程 t[i+1] ≡ a·t[i] (mod m) 有多解时最多尝试的候选数
+const MAX_MULTIPLIER_CANDIDATES: u64 = 1 << 16;
+
+/// outputs 为以空白或逗号分隔的连续输出, 状态满足 x[i+1] = a·x[i] + c (mod m)
+///
+/// modulus / multiplier / increment 留空则由输出推出;
+/// dropped_bits > 0 表示输出只是状态右移 dropped_bits 位后的高位 (如 Java Random),
+/// 此时三个参数都必须给出
+#[derive(Deserialize, Default)]
+pub struct LcgParams {
+    pub outputs: String,
+    pub modulus: Option<String>,
+    pub multiplier: Option<String>,
+    pub increment: Option<String>,
+    pub dropped_bits: Option<u32>,
+    pub predict: Option<usize>,
+    pub previous: Option<usize>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LcgResult {
+    pub method: String, // direct: 完整输出直接求解; lattice / brute_force: 截断输出
+    pub modulus: String,
+    pub multiplier: String,
+    pub increment: String,
+    pub first_state: String,            // 第一个输出对应的完整状态
+    pub next: Vec<String>,              // 紧接在最后一个输出之后的输出
+    pub previous: Vec<String>,          // 第一个输出之前的输出, 按时间顺序排列
+    pub previous_error: Option<String>, // 要求倒推但乘数不可逆时的原因
+    pub verified: bool,                 // 恢复的参数与状态能否重现所有输出
+}
+
+/// 由连续输出恢复 LCG 参数与状态, 预测之后的输出并倒推之前的输出
+#[tauri::command]
+pub fn lcg_recover(params: LcgParams) -> Result<LcgResult, String> {
+    let outputs = parse_outputs(&params.outputs)?;
+    let modulus = parse_optional(&params.modulus, "模数")?;
+    let multiplier = parse_optional(&params.multiplier, "乘数")?;
+    let increment = parse_optional(&params.increment, "增量")?;
+    let dropped = params.dropped_bits.unwrap_or(0);
+
+    let (method, lcg, first) = if dropped == 0 {
+        let lcg = recover_params(&outputs, modulus, multiplier, increment)?;
+        ("direct", lcg, outputs[0].clone())
+    } else {
+        let (Some(m), Some(a), Some(c)) = (modulus, multiplier, increment) else {
+            return Err("截断输出需要给出模数、乘数与增量".into());
+        };
+        let lcg = Lcg::new(m, a, c)?;
+        let (method, first) = recover_truncated(&lcg, &outputs, dropped)?;
+        (method, lcg, first)
+    };
+
+    let predict = params.predict.unwrap_or(DEFAULT_PREDICT).min(MAX_LIST);
+    let previous = params.previous.unwrap_or(0).min(MAX_LIST);
+
+    let mut last = first.clone();
+    for _ in 1..outputs.len() {
+        last = lcg.next(&last);
+    }
+    let mut next = Vec::with_capacity(predict);
+    for _ in 0..predict {
+        last = lcg.next(&last);
+        next.push((&last >> dropped).to_string());
+    }
+
+    // 无法倒推时仍返回参数与预测结果
+    let mut before = Vec::with_capacity(previous);
+    let mut previous_error = None;
+    let mut x = first.clone();
+    for _ in 0..previous {
+        match lcg.prev(&x) {
+            Ok(prev) => x = prev,
+            Err(err) => {
+                previous_error = Some(err);
+                break;
+            }
+        }
+        before.push((&x >> dropped).to_string());
+    }
+    before.reverse();
+
+    Ok(LcgResult {
+        method: method.into(),
+        modulus: lcg.m.to_string(),
+        multiplier: lcg.a.to_string(),
+        increment: lcg.c.to_string(),
+        verified: consistent(&lcg, &first, &outputs, dropped),
+        first_state: first.to_string(),
+        next,
+        previous: before,
+        previous_error,
+    })
+}
+
+// ===================== 输入解析 =====================
+
+fn parse_outputs(s: &str) -> Result<Vec<BigInt>, String> {
+    let outputs: Vec<BigInt> = s
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '[' || c == ']')
+        .filter(|t| !t.is_empty())
+        .map(|t| parse_bigint(t).map_err(|e| format!("输出格式错误: {}", e)))
+        .collect::<Result<_, String>>()?;
+    if outputs.is_empty() {
+        return Err("没有可用的输出".into());
+    }
+    if outputs.iter().any(Signed::is_negative) {
+        return Err("输出不能为负数".into());
+    }
+    Ok(outputs)
+}
+
+fn parse_optional(s: &Option<String>, name: &str) -> Result<Option<BigInt>, String> {
+    match s.as_deref().map(str::trim) {
+        Some(t) if !t.is_empty() => parse_bigint(t)
+            .map(Some)
+            .map_err(|e| format!("{}格式错误: {}", name, e)),
+        _ => Ok(None),
+    }
+}
+
+// ===================== 发生器 =====================
+
+struct Lcg {
+    m: BigInt,
+    a: BigInt,
+    c: BigInt,
+    a_inv: Option<BigInt>,
+}
+
+impl Lcg {
+    fn new(m: BigInt, a: BigInt, c: BigInt) -> Result<Self, String> {
+        if m <= BigInt::one() {
+            return Err("模数必须大于 1".into());
+        }
+        let a = a.mod_floor(&m);
+        let c = c.mod_floor(&m);
+        let a_inv = a.modinv(&m);
+        Ok(Lcg { m, a, c, a_inv })
+    }
+
+    fn next(&self, x: &BigInt) -> BigInt {
+        (&self.a * x + &self.c).mod_floor(&self.m)
+    }
+
+    fn prev(&self, x: &BigInt) -> Result<BigInt, String> {
+        let inv = self
+            .a_inv
+            .as_ref()
+            .ok_or("乘数与模数不互素, 无法倒推之前的输出")?;
+        Ok((inv * (x - &self.c)).mod_floor(&self.m))
+    }
+}
+
+/// 从 first 开始生成的状态右移 dropped 位后是否与所有输出一致
+fn consistent(lcg: &Lcg, first: &BigInt, outputs: &[BigInt], dropped: u32) -> bool {
+    let mut x = first.clone();
+    for (i, y) in outputs.iter().enumerate() {
+        if i > 0 {
+            x = lcg.next(&x);
+        }
+        if &(&x >> dropped) != y {
+            return false;
+        }
+    }
+    true
+}
+
+// ===================== 完整输出 =====================
+
+/// 依次求模数、乘数、增量, 已给出的参数直接使用
+///
+/// 记 t[i] = x[i+1] - x[i], 则 t[i+2]·t[i] - t[i+1]² ≡ 0 (mod m), 多组取 gcd 即得 m
+/// (输出越多, gcd 中混入额外因子的概率越小)
+fn recover_params(
+    outputs: &[BigInt],
+    modulus: Option<BigInt>,
+    multiplier: Option<BigInt>,
+    increment: Option<BigInt>,
+) -> Result<Lcg, String> {
+    let max = outputs.iter().max().cloned().unwrap_or_default();
+    let diffs: Vec<BigInt> = outputs.windows(2).map(|w| &w[1] - &w[0]).collect();
+
+    let m = match modulus {
+        Some(m) => m,
+        None => {
+            if outputs.len() < 5 {
+                return Err("模数未知时至少需要 5 个连续输出".into());
+            }
+            let m = diffs
+                .windows(3)
+                .map(|t| (&t[2] * &t[0] - &t[1] * &t[1]).abs())
+                .fold(BigInt::zero(), |g, u| g.gcd(&u));
+            if m <= max {
+                return Err("输出不足以确定模数, 请提供更多连续输出".into());
+            }
+            m
+        }
+    };
+    if m <= max {
+        return Err("输出不小于模数".into());
+    }
+
+    let candidates = match multiplier {
+        Some(a) => vec![a],
+        None => multiplier_candidates(&diffs, &m)?,
+    };
+    for a in candidates {
+        let c = match &increment {
+            Some(c) => c.clone(),
+            None if outputs.len() >= 2 => &outputs[1] - &a * &outputs[0],
+            None => return Err("增量未知时至少需要 2 个连续输出".into()),
+        };
+        let lcg = Lcg::new(m.clone(), a, c)?;
+        if consistent(&lcg, &outputs[0], outputs, 0) {
+            return Ok(lcg);
+        }
+    }
+    Err("找不到与输出一致的乘数, 输出可能不来自同一个 LCG".into())
+}
+
+/// 解 t[i+1] ≡ a·t[i] (mod m): 选 gcd(t[i], m) 最小的一组, 共有 gcd 个解
+fn multiplier_candidates(diffs: &[BigInt], m: &BigInt) -> Result<Vec<BigInt>, String> {
+    if diffs.len() < 2 {
+        return Err("乘数未知时至少需要 3 个连续输出".into());
+    }
+    let (g, t0, t1) = diffs
+        .windows(2)
+        .map(|t| {
+            let t0 = t[0].mod_floor(m);
+            (t0.gcd(m), t0, t[1].mod_floor(m))
+        })
+        .filter(|(g, _, t1)| (t1 % g).is_zero())
+        .min_by(|x, y| x.0.cmp(&y.0))
+        .ok_or("乘数方程无解, 输出可能不来自同一个 LCG")?;
+    if g > BigInt::from(MAX_MULTIPLIER_CANDIDATES) {
+        return Err("输出差分与模数的公因子过大, 无法确定乘数".into());
+    }
+    let step = m / &g;
+    let base = if step.is_one() {
+        BigInt::zero()
+    } else {
+        (&t1 / &g) * (&t0 / &g).modinv(&step).ok_or("模逆不存在")?
+    };
+    let base = base.mod_floor(&step);
+    let mut candidates = Vec::new();
+    let mut a = base;
+    while &a < m {
+        candidates.push(a.clone());
+        a += &step;
+    }
+    Ok(candidates)
+}
+
+// ===================== 截断输出 =====================
+
+/// 输出 y[i] = x[i] >> s, 记 x[i] = h[i] + e[i], h[i] 取 y[i]·2^s 加上区间中点, |e[i]| <= 2^(s-1)
+///
+/// x[i] ≡ A[i]·x[0] + C[i], 于是 e[i] ≡ A[i]·e[0] + b[i] (mod m), 其中
+/// b[i] = A[i]·h[0] + C[i] - h[i]; 用 Kannan 嵌入把 (e[0], .., e[n-1], K) 变成格中的短向量
+fn recover_truncated(
+    lcg: &Lcg,
+    outputs: &[BigInt],
+    dropped: u32,
+) -> Result<(&'static str, BigInt), String> {
+    if outputs.len() < 2 {
+        return Err("截断输出至少需要 2 个连续输出".into());
+    }
+    if lcg.m.bits() <= dropped as u64 {
+        return Err("丢弃位数不小于模数位数".into());
+    }
+    if outputs.iter().any(|y| (y << dropped) >= lcg.m) {
+        return Err("输出超出模数右移后的范围".into());
+    }
+
+    let half = BigInt::one() << (dropped - 1);
+    let n = outputs.len().min(MAX_LATTICE_OUTPUTS);
+    let h: Vec<BigInt> = outputs[..n]
+        .iter()
+        .map(|y| (y << dropped) + &half)
+        .collect();
+
+    let mut coeff = Vec::with_capacity(n);
+    let mut offset = Vec::with_capacity(n);
+    let (mut a_i, mut c_i) = (BigInt::one(), BigInt::zero());
+    for _ in 0..n {
+        coeff.push(a_i.clone());
+        offset.push(c_i.clone());
+        a_i = (&a_i * &lcg.a).mod_floor(&lcg.m);
+        c_i = (&c_i * &lcg.a + &lcg.c).mod_floor(&lcg.m);
+    }
+
+    let mut basis = Vec::with_capacity(n + 1);
+    let mut row: Vec<BigInt> = coeff.clone();
+    row.push(BigInt::zero());
+    basis.push(row);
+    for i in 1..n {
+        let mut row = vec![BigInt::zero(); n + 1];
+        row[i] = lcg.m.clone();
+        basis.push(row);
+    }
+    let mut row: Vec<BigInt> = (0..n)
+        .map(|i| (&coeff[i] * &h[0] + &offset[i] - &h[i]).mod_floor(&lcg.m))
+        .collect();
+    row.push(half.clone());
+    basis.push(row);
+
+    for row in lll_reduce(&basis)? {
+        let sign = if row[n] == half {
+            BigInt::one()
+        } else if row[n] == -&half {
+            -BigInt::one()
+        } else {
+            continue;
+        };
+        let first = (&h[0] + &row[0] * sign).mod_floor(&lcg.m);
+        if consistent(lcg, &first, outputs, dropped) {
+            return Ok(("lattice", first));
+        }
+    }
+
+    if dropped <= MAX_BRUTE_FORCE_BITS {
+        let high = &outputs[0] << dropped;
+        for low in 0u64..1 << dropped {
+            let first = &high + low;
+            if consistent(lcg, &first, outputs, dropped) {
+                return Ok(("brute_force", first));
+            }
+        }
+    }
+    Err("未能恢复状态: 输出个数不足或丢弃位数过多".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(outputs: &[u128]) -> LcgParams {
+        LcgParams {
+            outputs: outputs
+                .iter()
+                .map(u128::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            ..Default::default()
+        }
+    }
+
+    fn sequence(m: u128, a: u128, c: u128, seed: u128, count: usize) -> Vec<u128> {
+        let mut x = seed;
+        (0..count)
+            .map(|_| {
+                x = (a * x + c) % m;
+                x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recover_unknown_parameters() {
+        let (m, a, c) = (2_147_483_647u128, 48_271, 12_345);
+        let xs = sequence(m, a, c, 20_240_601, 16);
+        let mut p = params(&xs[3..13]);
+        p.predict = Some(3);
+        p.previous = Some(3);
+        let res = lcg_recover(p).unwrap();
+        assert_eq!(res.method, "direct");
+        assert_eq!(res.modulus, m.to_string());
+        assert_eq!(res.multiplier, a.to_string());
+        assert_eq!(res.increment, c.to_string());
+        assert!(res.verified);
+        let show = |s: &[u128]| s.iter().map(u128::to_string).collect::<Vec<_>>();
+        assert_eq!(res.next, show(&xs[13..16]));
+        assert_eq!(res.previous, show(&xs[..3]));
+    }
+
+    #[test]
+    fn test_truncated_java_random() {
+        // java.util.Random: m = 2^48, 输出为状态的高 32 位
+        let (m, a, c) = (1u128 << 48, 0x5_deec_e66d, 11);
+        let xs = sequence(m, a, c, 0x1234_5678_9abc, 12);
+        let ys: Vec<u128> = xs.iter().map(|x| x >> 16).collect();
+        let mut p = params(&ys[..6]);
+        p.modulus = Some(m.to_string());
+        p.multiplier = Some(a.to_string());
+        p.increment = Some(c.to_string());
+        p.dropped_bits = Some(16);
+        p.predict = Some(6);
+        let res = lcg_recover(p).unwrap();
+        assert_eq!(res.method, "lattice");
+        assert_eq!(res.first_state, xs[0].to_string());
+        assert!(res.verified);
+        let expect: Vec<String> = ys[6..].iter().map(u128::to_string).collect();
+        assert_eq!(res.next, expect);
+    }
+
+    #[test]
+    fn test_known_modulus_and_errors() {
+        // 已知模数时由差分解出乘数与增量
+        let (m, a, c) = (1u128 << 32, 1_664_525, 1_013_904_223);
+        let xs = sequence(m, a, c, 42, 8);
+        let mut p = params(&xs);
+        p.modulus = Some(m.to_string());
+        let res = lcg_recover(p).unwrap();
+        assert_eq!(res.multiplier, a.to_string());
+        assert_eq!(res.increment, c.to_string());
+
+        // 乘数为偶数时模 2^32 不可逆: 仍给出预测, 只报告无法倒推
+        let (a, c) = (1_664_524, 1_013_904_223);
+        let ys = sequence(m, a, c, 42, 8);
+        let mut p = params(&ys[..6]);
+        p.modulus = Some(m.to_string());
+        p.multiplier = Some(a.to_string());
+        p.increment = Some(c.to_string());
+        p.predict = Some(2);
+        p.previous = Some(2);
+        let res = lcg_recover(p).unwrap();
+        assert_eq!(res.next, [ys[6].to_string(), ys[7].to_string()]);
+        assert!(res.previous.is_empty());
+        assert!(res.previous_error.unwrap().contains("无法倒推"));
+
+        assert!(lcg_recover(params(&xs[..4])).is_err());
+        let mut p = params(&xs);
+        p.dropped_bits = Some(8);
+        assert!(lcg_recover(p).is_err());
+    }
+}
@@ -0,0 +1,625 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_biguint, parse_bytes, render_bytes, BytesView};
+use super::task::CryptoTasks;
+
+pub const LFSR_TASK: &str = "lfsr";
+
+const MAX_EXTEND: usize = 1 << 20; // 额外生成的比特数上限
+const MAX_REGISTER_BITS: usize = 64;
+// 单个寄存器相关攻击时穷举的状态位数上限
+const MAX_CORRELATION_BITS: usize = 28;
+// 与输出不相关的寄存器需要联合穷举, 其总位数上限
+const MAX_JOINT_BITS: usize = 28;
+const PROGRESS_INTERVAL: u64 = 1 << 16;
+// Geffe: f(x0, x1, x2) = x0·x1 ⊕ ¬x0·x2, 真值表下标的第 i 位为第 i 个寄存器的输出
+const GEFFE_TABLE: &str = "00011011";
+
+#[derive(Serialize, Debug)]
+pub struct LfsrResult {
+    pub polynomial: String, // 连接多项式 C(x) = 1 + c1·x + ... + cL·x^L
+    pub taps: Vec<usize>,   // 系数为 1 的 i (1..=L), s[n] = ⊕ s[n-i]
+    pub linear_complexity: usize,
+    pub initial_state: String, // s[0..L)
+    pub keystream: String,     // 已知比特加上额外生成的比特
+    pub unique: bool,          // 2L <= 已知比特数时所得 LFSR 唯一
+    pub plaintext: Option<BytesView>,
+}
+
+/// 用 Berlekamp–Massey 求生成已知比特串的最短 LFSR, 可继续生成 extend 个比特并与密文异或
+#[tauri::command]
+pub fn lfsr_analyze(
+    keystream: String,
+    extend: Option<usize>,
+    ciphertext: Option<String>,
+) -> Result<LfsrResult, String> {
+    let bits = parse_bits(&keystream).map_err(|e| format!("比特串格式错误: {}", e))?;
+    let poly = berlekamp_massey(&bits);
+    let l = poly.len() - 1;
+
+    let ciphertext = parse_ciphertext(&ciphertext)?;
+    let shown = bits.len() + extend.unwrap_or(0).min(MAX_EXTEND);
+    let total = shown.max(ciphertext.as_ref().map_or(0, |c| c.len() * 8));
+    let stream = lfsr_extend(&poly, &bits[..l], total);
+
+    Ok(LfsrResult {
+        polynomial: render_poly(&poly),
+        taps: (1..=l).filter(|&i| poly[i] == 1).collect(),
+        linear_complexity: l,
+        initial_state: render_bits(&bits[..l]),
+        keystream: render_bits(&stream[..shown]),
+        unique: 2 * l <= bits.len(),
+        plaintext: ciphertext.map(|c| xor_keystream(&c, &stream)),
+    })
+}
+
+/// polynomials 按寄存器顺序给出连接多项式 (与 lfsr_analyze 的记法相同, 也可用整数表示系数位);
+/// combiner 为 `geffe` (默认) 或长度 2^k 的 0/1 真值表, 下标第 i 位为第 i 个寄存器的输出
+#[derive(Deserialize, Default)]
+pub struct CombinerParams {
+    pub keystream: String,
+    pub polynomials: Vec<String>,
+    pub combiner: Option<String>,
+    pub extend: Option<usize>,
+    pub ciphertext: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RegisterResult {
+    pub polynomial: String,
+    pub initial_state: String,
+    pub method: String, // correlation: 单独按相关性穷举; exhaustive: 与其他寄存器联合穷举
+    pub agreement: f64, // 寄存器输出与密钥流相同的比例
+}
+
+#[derive(Serialize, Debug)]
+pub struct CombinerResult {
+    pub registers: Vec<RegisterResult>,
+    pub keystream: String,
+    pub plaintext: Option<BytesView>,
+    pub verified: bool, // 恢复的初始状态能否重现全部已知比特
+    pub cancelled: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct LfsrProgress {
+    pub register: usize, // 正在穷举的寄存器, 联合穷举时为其中第一个
+    pub done: u64,
+    pub total: u64,
+}
+
+/// 对 Geffe 等非线性组合生成器做相关攻击, 逐个恢复各 LFSR 的初始状态
+#[tauri::command]
+pub async fn lfsr_combiner_attack(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: CombinerParams,
+) -> Result<CombinerResult, String> {
+    let handle = tasks.begin(LFSR_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: LfsrProgress| {
+            let _ = window.emit("lfsr-progress", progress);
+        };
+        run_combiner_attack(&params, &cancel, &emit)
+    })
+    .await
+    .map_err(|e| format!("LFSR 任务异常退出: {}", e))?
+}
+
+pub fn run_combiner_attack(
+    params: &CombinerParams,
+    cancel: &AtomicBool,
+    progress: &dyn Fn(LfsrProgress),
+) -> Result<CombinerResult, String> {
+    let bits = parse_bits(&params.keystream).map_err(|e| format!("比特串格式错误: {}", e))?;
+    let registers: Vec<Register> = params
+        .polynomials
+        .iter()
+        .enumerate()
+        .map(|(i, p)| Register::new(p).map_err(|e| format!("第 {} 个多项式: {}", i + 1, e)))
+        .collect::<Result<_, String>>()?;
+    let table = parse_combiner(params.combiner.as_deref(), registers.len())?;
+    let ciphertext = parse_ciphertext(&params.ciphertext)?;
+
+    let mut result = CombinerResult {
+        registers: Vec::new(),
+        keystream: String::new(),
+        plaintext: None,
+        verified: false,
+        cancelled: false,
+    };
+
+    // 真值表中 f = x_i 的行数偏离一半时, 第 i 个寄存器与输出相关
+    let rows = table.len();
+    let mut states: Vec<Option<u64>> = vec![None; registers.len()];
+    let mut methods = vec!["exhaustive"; registers.len()];
+    for (r, reg) in registers.iter().enumerate() {
+        let same = (0..rows)
+            .filter(|&x| table[x] == (x >> r & 1) as u8)
+            .count();
+        if 2 * same == rows {
+            continue;
+        }
+        if reg.len > MAX_CORRELATION_BITS {
+            return Err(format!(
+                "第 {} 个寄存器超过 {} 位",
+                r + 1,
+                MAX_CORRELATION_BITS
+            ));
+        }
+        match correlate(reg, r, &bits, 2 * same > rows, cancel, progress) {
+            Some(state) => {
+                states[r] = Some(state);
+                methods[r] = "correlation";
+            }
+            None => {
+                result.cancelled = true;
+                return Ok(result);
+            }
+        }
+    }
+
+    match exhaust(&registers, &table, &bits, &mut states, cancel, progress)? {
+        Some(verified) => result.verified = verified,
+        None => {
+            result.cancelled = true;
+            return Ok(result);
+        }
+    }
+
+    let states: Vec<u64> = states.into_iter().map(Option::unwrap_or_default).collect();
+    let shown = bits.len() + params.extend.unwrap_or(0).min(MAX_EXTEND);
+    let total = shown.max(ciphertext.as_ref().map_or(0, |c| c.len() * 8));
+    let outputs: Vec<Vec<u8>> = registers
+        .iter()
+        .zip(&states)
+        .map(|(reg, &s)| reg.output(s, total))
+        .collect();
+    let stream: Vec<u8> = (0..total)
+        .map(|t| table[combine_index(&outputs, t)])
+        .collect();
+
+    result.registers = registers
+        .iter()
+        .zip(&outputs)
+        .zip(methods)
+        .map(|((reg, out), method)| {
+            let same = bits.iter().zip(out).filter(|(a, b)| a == b).count();
+            RegisterResult {
+                polynomial: render_poly(&reg.poly),
+                initial_state: render_bits(&out[..reg.len]),
+                method: method.into(),
+                agreement: same as f64 / bits.len() as f64,
+            }
+        })
+        .collect();
+    result.keystream = render_bits(&stream[..shown]);
+    result.plaintext = ciphertext.map(|c| xor_keystream(&c, &stream));
+    Ok(result)
+}
+
+// ===================== 输入解析 =====================
+
+/// 比特串输入: 只含 0/1 (可带空白、逗号) 时逐位读取,
+/// 否则按 parse_bytes 解析为字节串后高位在前展开 (`hex:0101` 可强制按十六进制解析)
+pub fn parse_bits(s: &str) -> Result<Vec<u8>, String> {
+    let compact: String = s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect();
+    if compact.is_empty() {
+        return Err("输入为空".into());
+    }
+    if compact.chars().all(|c| c == '0' || c == '1') {
+        return Ok(compact.bytes().map(|b| b - b'0').collect());
+    }
+    let bytes = parse_bytes(s)?;
+    Ok(bytes
+        .iter()
+        .flat_map(|&b| (0..8).rev().map(move |i| b >> i & 1))
+        .collect())
+}
+
+fn parse_ciphertext(s: &Option<String>) -> Result<Option<Vec<u8>>, String> {
+    match s.as_deref().map(str::trim) {
+        Some(t) if !t.is_empty() => parse_bytes(t)
+            .map(Some)
+            .map_err(|e| format!("密文格式错误: {}", e)),
+        _ => Ok(None),
+    }
+}
+
+/// 连接多项式: `x^5 + x^2 + 1` 形式, 或按整数给出 (第 i 位为 x^i 的系数)
+fn parse_poly(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim();
+    let mut degrees = Vec::new();
+    if s.contains(['x', 'X']) {
+        for term in s.split('+').map(str::trim) {
+            let degree = match term.to_ascii_lowercase().as_str() {
+                "1" => 0,
+                "x" => 1,
+                t => t
+                    .strip_prefix("x^")
+                    .and_then(|d| d.trim().parse().ok())
+                    .ok_or_else(|| format!("无法识别的项: {}", term))?,
+            };
+            degrees.push(degree);
+        }
+    } else {
+        let value = parse_biguint(s)?;
+        degrees.extend(
+            (0..value.bits())
+                .filter(|&i| value.bit(i))
+                .map(|i| i as usize),
+        );
+    }
+    let degree = degrees.iter().copied().max().unwrap_or(0);
+    // 先检查次数再分配, 避免超大次数导致溢出或巨量内存
+    if degree > MAX_REGISTER_BITS {
+        return Err(format!("寄存器长度应在 1..={} 之间", MAX_REGISTER_BITS));
+    }
+    let mut poly = vec![0u8; degree + 1];
+    for d in degrees {
+        poly[d] ^= 1;
+    }
+    if poly[0] == 0 {
+        return Err("连接多项式的常数项必须为 1".into());
+    }
+    Ok(poly)
+}
+
+fn parse_combiner(s: Option<&str>, count: usize) -> Result<Vec<u8>, String> {
+    let s = s
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("geffe");
+    let table = if s.eq_ignore_ascii_case("geffe") {
+        GEFFE_TABLE
+    } else {
+        s
+    };
+    if !table.chars().all(|c| c == '0' || c == '1') {
+        return Err("组合函数应为 geffe 或 0/1 真值表".into());
+    }
+    if count == 0 || count > 8 || table.len() != 1 << count {
+        return Err(format!(
+            "{} 个寄存器需要长度为 {} 的真值表",
+            count,
+            1usize << count.min(8)
+        ));
+    }
+    Ok(table.bytes().map(|b| b - b'0').collect())
+}
+
+fn render_bits(bits: &[u8]) -> String {
+    bits.iter().map(|&b| char::from(b'0' + b)).collect()
+}
+
+fn render_poly(poly: &[u8]) -> String {
+    let terms: Vec<String> = (0..poly.len())
+        .rev()
+        .filter(|&i| poly[i] == 1)
+        .map(|i| match i {
+            0 => "1".to_string(),
+            1 => "x".to_string(),
+            _ => format!("x^{}", i),
+        })
+        .collect();
+    terms.join(" + ")
+}
+
+/// 比特按高位在前组成字节后与密文异或, 密钥流需覆盖整个密文
+fn xor_keystream(ciphertext: &[u8], stream: &[u8]) -> BytesView {
+    let plain: Vec<u8> = ciphertext
+        .iter()
+        .zip(stream.chunks(8))
+        .map(|(c, bits)| c ^ bits.iter().fold(0u8, |acc, &b| acc << 1 | b))
+        .collect();
+    render_bytes(&plain)
+}
+
+// ===================== Berlekamp–Massey =====================
+
+/// 返回最短连接多项式的系数 c[0..=L], c[0] = 1
+fn berlekamp_massey(s: &[u8]) -> Vec<u8> {
+    let n = s.len();
+    let mut c = vec![0u8; n + 1];
+    let mut b = vec![0u8; n + 1];
+    c[0] = 1;
+    b[0] = 1;
+    let (mut l, mut m) = (0usize, 1usize);
+    for i in 0..n {
+        let d = (1..=l).fold(s[i], |d, j| d ^ (c[j] & s[i - j]));
+        if d == 0 {
+            m += 1;
+            continue;
+        }
+        let prev = c.clone();
+        for j in 0..=n - m {
+            c[j + m] ^= b[j];
+        }
+        if 2 * l <= i {
+            l = i + 1 - l;
+            b = prev;
+            m = 1;
+        } else {
+            m += 1;
+        }
+    }
+    c.truncate(l + 1);
+    c
+}
+
+/// 由前 L 个比特按 s[n] = ⊕ c[i]·s[n-i] 生成共 total 个比特
+fn lfsr_extend(poly: &[u8], init: &[u8], total: usize) -> Vec<u8> {
+    let l = poly.len() - 1;
+    let mut s = init.to_vec();
+    for n in l..total {
+        let bit = (1..=l).fold(0, |acc, i| acc ^ (poly[i] & s[n - i]));
+        s.push(bit);
+    }
+    s.truncate(total);
+    s
+}
+
+// ===================== 组合生成器 =====================
+
+/// 不超过 64 位的 LFSR, 状态 w 的第 j 位为 s[k+j], 每步输出最低位
+struct Register {
+    poly: Vec<u8>,
+    len: usize,
+    taps: u64,
+}
+
+impl Register {
+    fn new(s: &str) -> Result<Self, String> {
+        let poly = parse_poly(s)?;
+        let len = poly.len() - 1;
+        if len == 0 || len > MAX_REGISTER_BITS {
+            return Err(format!("寄存器长度应在 1..={} 之间", MAX_REGISTER_BITS));
+        }
+        // s[k+L] = ⊕ c[i]·s[k+L-i], 对应状态的第 L-i 位
+        let taps = (1..=len)
+            .filter(|&i| poly[i] == 1)
+            .fold(0u64, |t, i| t | 1 << (len - i));
+        Ok(Register { poly, len, taps })
+    }
+
+    fn step(&self, w: u64) -> u64 {
+        let bit = (w & self.taps).count_ones() as u64 & 1;
+        w >> 1 | bit << (self.len - 1)
+    }
+
+    fn output(&self, state: u64, total: usize) -> Vec<u8> {
+        let mut w = state;
+        (0..total)
+            .map(|_| {
+                let bit = (w & 1) as u8;
+                w = self.step(w);
+                bit
+            })
+            .collect()
+    }
+
+    fn states(&self) -> u64 {
+        if self.len == 64 {
+            u64::MAX
+        } else {
+            (1 << self.len) - 1
+        }
+    }
+}
+
+fn combine_index(outputs: &[Vec<u8>], t: usize) -> usize {
+    outputs
+        .iter()
+        .enumerate()
+        .fold(0, |x, (i, out)| x | (out[t] as usize) << i)
+}
+
+/// 穷举非零初始状态, 取与密钥流相同 (正相关) 或相反 (负相关) 比特最多的一个
+fn correlate(
+    reg: &Register,
+    index: usize,
+    bits: &[u8],
+    positive: bool,
+    cancel: &AtomicBool,
+    progress: &dyn Fn(LfsrProgress),
+) -> Option<u64> {
+    let total = reg.states();
+    let mut best = (0usize, 1u64);
+    for state in 1..=total {
+        if state % PROGRESS_INTERVAL == 0 {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            progress(LfsrProgress {
+                register: index,
+                done: state,
+                total,
+            });
+        }
+        let mut w = state;
+        let mut same = 0;
+        for &b in bits {
+            same += ((w & 1) as u8 == b) as usize;
+            w = reg.step(w);
+        }
+        let score = if positive { same } else { bits.len() - same };
+        if score > best.0 {
+            best = (score, state);
+        }
+    }
+    Some(best.1)
+}
+
+/// 固定已相关恢复的寄存器, 联合穷举其余寄存器直到完全重现密钥流;
+/// 返回 Some(是否找到一致的状态), 被取消时返回 None
+fn exhaust(
+    registers: &[Register],
+    table: &[u8],
+    bits: &[u8],
+    states: &mut [Option<u64>],
+    cancel: &AtomicBool,
+    progress: &dyn Fn(LfsrProgress),
+) -> Result<Option<bool>, String> {
+    let free: Vec<usize> = (0..registers.len())
+        .filter(|&r| states[r].is_none())
+        .collect();
+    let joint: usize = free.iter().map(|&r| registers[r].len).sum();
+    if joint > MAX_JOINT_BITS {
+        return Err(format!(
+            "与输出不相关的寄存器共 {} 位, 超过联合穷举上限 {} 位",
+            joint, MAX_JOINT_BITS
+        ));
+    }
+    let fixed: Vec<Option<Vec<u8>>> = registers
+        .iter()
+        .zip(states.iter())
+        .map(|(reg, s)| s.map(|s| reg.output(s, bits.len())))
+        .collect();
+
+    let radices: Vec<u64> = free.iter().map(|&r| registers[r].states()).collect();
+    let total: u64 = radices.iter().product();
+    let mut choice = vec![1u64; free.len()];
+    for done in 0..total {
+        if done % PROGRESS_INTERVAL == 0 {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+            progress(LfsrProgress {
+                register: free.first().copied().unwrap_or(0),
+                done,
+                total,
+            });
+        }
+        let mut w: Vec<u64> = (0..registers.len())
+            .map(|r| match free.iter().position(|&f| f == r) {
+                Some(k) => choice[k],
+                None => 0,
+            })
+            .collect();
+        let ok = bits.iter().enumerate().all(|(t, &b)| {
+            let mut x = 0;
+            for (r, reg) in registers.iter().enumerate() {
+                let bit = match &fixed[r] {
+                    Some(out) => out[t],
+                    None => {
+                        let bit = (w[r] & 1) as u8;
+                        w[r] = reg.step(w[r]);
+                        bit
+                    }
+                };
+                x |= (bit as usize) << r;
+            }
+            table[x] == b
+        });
+        if ok {
+            for (k, &r) in free.iter().enumerate() {
+                states[r] = Some(choice[k]);
+            }
+            return Ok(Some(true));
+        }
+        // 混合进制计数, 每个寄存器的状态取 1..=radix
+        for (k, c) in choice.iter_mut().enumerate() {
+            if *c < radices[k] {
+                *c += 1;
+                break;
+            }
+            *c = 1;
+        }
+    }
+    Ok(Some(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(poly: &str, state: u64, total: usize) -> Vec<u8> {
+        Register::new(poly).unwrap().output(state, total)
+    }
+
+    #[test]
+    fn test_berlekamp_massey_and_extend() {
+        let stream = generate("x^5 + x^2 + 1", 0b01001, 24);
+        let res = lfsr_analyze(render_bits(&stream[..12]), Some(12), None).unwrap();
+        assert_eq!(res.polynomial, "x^5 + x^2 + 1");
+        assert_eq!(res.taps, vec![2, 5]);
+        assert_eq!(res.linear_complexity, 5);
+        assert_eq!(res.initial_state, "10010");
+        assert!(res.unique);
+        assert_eq!(res.keystream, render_bits(&stream));
+        // 字节输入高位在前展开
+        let bits = parse_bits("hex:a5").unwrap();
+        assert_eq!(render_bits(&bits), "10100101");
+
+        // 次数超限在分配前拒绝
+        assert!(parse_poly("x^18446744073709551615 + 1").is_err());
+        assert!(parse_poly(&format!("0x1{}1", "0".repeat(4000))).is_err());
+    }
+
+    #[test]
+    fn test_keystream_xor_ciphertext() {
+        let plain = b"flag{berlekamp_massey_lfsr}";
+        let stream = generate("x^16 + x^14 + x^13 + x^11 + 1", 0xace1, plain.len() * 8);
+        let cipher: Vec<u8> = plain
+            .iter()
+            .zip(stream.chunks(8))
+            .map(|(p, bits)| p ^ bits.iter().fold(0u8, |acc, &b| acc << 1 | b))
+            .collect();
+        let known: Vec<u8> = cipher[..5].iter().zip(plain).map(|(c, p)| c ^ p).collect();
+        let res = lfsr_analyze(
+            format!("hex:{}", hex::encode(known)),
+            None,
+            Some(hex::encode(&cipher)),
+        )
+        .unwrap();
+        assert_eq!(res.linear_complexity, 16);
+        let plaintext = res.plaintext.unwrap();
+        assert_eq!(
+            plaintext.flag.as_deref(),
+            Some("flag{berlekamp_massey_lfsr}")
+        );
+    }
+
+    #[test]
+    fn test_geffe_correlation_attack() {
+        let polys = ["x^7 + x + 1", "x^9 + x^4 + 1", "x^10 + x^3 + 1"];
+        let states = [0x35u64, 0x1c7, 0x2e9];
+        let n = 200;
+        let outs: Vec<Vec<u8>> = polys
+            .iter()
+            .zip(states)
+            .map(|(p, s)| generate(p, s, n + 16))
+            .collect();
+        let table = parse_combiner(None, 3).unwrap();
+        let stream: Vec<u8> = (0..n + 16)
+            .map(|t| table[combine_index(&outs, t)])
+            .collect();
+
+        let params = CombinerParams {
+            keystream: render_bits(&stream[..n]),
+            polynomials: polys.iter().map(|p| p.to_string()).collect(),
+            extend: Some(16),
+            ..Default::default()
+        };
+        let res = run_combiner_attack(&params, &AtomicBool::new(false), &|_| {}).unwrap();
+        assert!(res.verified);
+        let methods: Vec<&str> = res.registers.iter().map(|r| r.method.as_str()).collect();
+        assert_eq!(methods, ["exhaustive", "correlation", "correlation"]);
+        for (reg, out) in res.registers.iter().zip(&outs) {
+            assert_eq!(
+                reg.initial_state,
+                render_bits(&out[..reg.initial_state.len()])
+            );
+        }
+        assert_eq!(res.keystream, render_bits(&stream));
+    }
+}
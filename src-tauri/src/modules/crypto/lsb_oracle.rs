@@ -0,0 +1,272 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::{parse_biguint, render_number, NumberView};
+use super::oracle::{reply_bit, OracleConn, OracleTarget};
+use super::task::CryptoTasks;
+
+pub const LSB_ORACLE_TASK: &str = "lsb_oracle";
+
+const PROGRESS_INTERVAL: u64 = 8; // 每隔多少次查询推送一次进度
+
+#[derive(Serialize, Clone)]
+pub struct LsbOracleProgress {
+    pub step: u64,
+    pub total: u64,
+    pub upper: NumberView, // 当前区间的上界, 高位字节逐渐确定
+}
+
+/// kind: `lsb` (默认, oracle 回答 m mod 2) 或 `half` (回答 m > n/2)
+///
+/// encoding 为发送密文的格式 (`dec` 默认 / `hex`); one_marker 为表示 1 的响应子串,
+/// 缺省时识别 1/0、odd/even、true/false
+#[derive(Deserialize, Default)]
+pub struct LsbOracleParams {
+    pub target: OracleTarget,
+    pub n: String,
+    pub e: String,
+    pub c: String,
+    pub kind: Option<String>,
+    pub encoding: Option<String>,
+    pub one_marker: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LsbOracleResult {
+    pub plaintext: Option<NumberView>,
+    pub queries: u64,
+    pub cancelled: bool,
+}
+
+/// RSA LSB / half oracle 攻击: 每次把密文乘以 2^e, 由明文奇偶性逐位二分 m 所在的区间
+#[tauri::command]
+pub async fn lsb_oracle_attack(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: LsbOracleParams,
+) -> Result<LsbOracleResult, String> {
+    let handle = tasks.begin(LSB_ORACLE_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: LsbOracleProgress| {
+            let _ = window.emit("lsb-oracle-progress", progress);
+        };
+        run_lsb_oracle(&params, &cancel, &emit)
+    })
+    .await
+    .map_err(|e| format!("LSB oracle 任务异常退出: {}", e))?
+}
+
+pub fn run_lsb_oracle(
+    params: &LsbOracleParams,
+    cancel: &AtomicBool,
+    progress: &dyn Fn(LsbOracleProgress),
+) -> Result<LsbOracleResult, String> {
+    let n = parse_biguint(&params.n).map_err(|err| format!("N 格式错误: {}", err))?;
+    let e = parse_biguint(&params.e).map_err(|err| format!("e 格式错误: {}", err))?;
+    let c = parse_biguint(&params.c).map_err(|err| format!("c 格式错误: {}", err))?;
+    if n.is_even() || n <= BigUint::one() {
+        return Err("N 必须是大于 1 的奇数".into());
+    }
+    let half = match params.kind.as_deref().unwrap_or("lsb") {
+        "lsb" => false,
+        "half" => true,
+        other => return Err(format!("未知的 oracle 类型: {}", other)),
+    };
+    let hex = match params.encoding.as_deref().unwrap_or("dec") {
+        "dec" => false,
+        "hex" => true,
+        other => return Err(format!("未知的密文格式: {}", other)),
+    };
+    let marker = params.one_marker.as_deref().filter(|s| !s.is_empty());
+
+    let mut conn = OracleConn::open(&params.target)?;
+    let mut oracle = |c: &BigUint| -> Result<bool, String> {
+        let line = if hex {
+            format!("{:x}", c)
+        } else {
+            c.to_string()
+        };
+        reply_bit(&conn.query(&line)?, marker)
+    };
+    let m = recover_plaintext(&n, &e, &c, half, cancel, &mut oracle, progress)?;
+    Ok(LsbOracleResult {
+        plaintext: m.as_ref().map(render_number),
+        queries: conn.queries,
+        cancelled: m.is_none(),
+    })
+}
+
+/// 第 k 次查询 2^k·m 的奇偶性, 得到 j_k = floor(2^k·m / n) 的最低位 (n 为奇数);
+/// half oracle 对 2^(k-1)·m 的回答与之等价。bits(n) 次后 m = ceil(j·n / 2^k)
+pub fn recover_plaintext<F>(
+    n: &BigUint,
+    e: &BigUint,
+    c: &BigUint,
+    half: bool,
+    cancel: &AtomicBool,
+    oracle: &mut F,
+    progress: &dyn Fn(LsbOracleProgress),
+) -> Result<Option<BigUint>, String>
+where
+    F: FnMut(&BigUint) -> Result<bool, String>,
+{
+    let total = n.bits();
+    let factor = BigUint::from(2u32).modpow(e, n);
+    let mut cur = c % n;
+    if !half {
+        cur = cur * &factor % n;
+    }
+    let mut j = BigUint::zero();
+    for k in 1..=total {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        j = (j << 1u32) + u32::from(oracle(&cur)?);
+        cur = cur * &factor % n;
+        if k % PROGRESS_INTERVAL == 0 || k == total {
+            progress(LsbOracleProgress {
+                step: k,
+                total,
+                upper: render_number(&(((&j + 1u32) * n) >> k)),
+            });
+        }
+    }
+    let m = (j * n + (BigUint::one() << total) - 1u32) >> total;
+    Ok(Some(m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::crypto::oracle::parity_script_target;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const P: &str = "351078539220351560940160484423438804497";
+    const Q: &str = "264368716054775866813995788202255419117";
+
+    /// 本地替身 oracle: 先发一行欢迎信息, 之后每行读入密文, 按 kind 回答明文的一位
+    fn spawn_oracle(half: bool, hex: bool) -> (u16, BigUint, BigUint) {
+        let p: BigUint = P.parse().unwrap();
+        let q: BigUint = Q.parse().unwrap();
+        let n = &p * &q;
+        let e = BigUint::from(65537u32);
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let d = e.modinv(&phi).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let key = n.clone();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            stream.set_nodelay(true).unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            writeln!(writer, "welcome to the parity oracle").unwrap();
+            for line in BufReader::new(stream).lines() {
+                let line = line.unwrap();
+                let c = if hex {
+                    BigUint::parse_bytes(line.trim().as_bytes(), 16).unwrap()
+                } else {
+                    line.trim().parse().unwrap()
+                };
+                let m = c.modpow(&d, &key);
+                let reply = if half {
+                    format!("> {}", if &m + &m > key { "yes" } else { "no" })
+                } else {
+                    format!("parity: {}", if m.is_odd() { "odd" } else { "even" })
+                };
+                if writeln!(writer, "{}", reply).is_err() {
+                    break;
+                }
+            }
+        });
+        (port, n, e)
+    }
+
+    fn params(port: u16, n: &BigUint, e: &BigUint, c: &BigUint) -> LsbOracleParams {
+        LsbOracleParams {
+            target: OracleTarget {
+                mode: "tcp".into(),
+                address: Some(port.to_string()),
+                banner_lines: Some(1),
+                ..Default::default()
+            },
+            n: n.to_string(),
+            e: e.to_string(),
+            c: c.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lsb_oracle_over_tcp() {
+        let (port, n, e) = spawn_oracle(false, false);
+        let m = BigUint::from_bytes_be(b"flag{parity_leaks_all}");
+        let c = m.modpow(&e, &n);
+        let steps = std::sync::Mutex::new(0);
+        let progress = |p: LsbOracleProgress| *steps.lock().unwrap() = p.step;
+        let res = run_lsb_oracle(
+            &params(port, &n, &e, &c),
+            &AtomicBool::new(false),
+            &progress,
+        )
+        .unwrap();
+        assert_eq!(
+            res.plaintext.unwrap().utf8.as_deref(),
+            Some("flag{parity_leaks_all}")
+        );
+        assert_eq!(res.queries, n.bits());
+        assert_eq!(*steps.lock().unwrap(), n.bits());
+    }
+
+    #[test]
+    fn test_lsb_oracle_over_exec() {
+        // 随附的替身 oracle 脚本通过 stdin/stdout 回答奇偶性
+        let p: BigUint = P.parse().unwrap();
+        let q: BigUint = Q.parse().unwrap();
+        let n = &p * &q;
+        let e = BigUint::from(65537u32);
+        let d = e.modinv(&((&p - 1u32) * (&q - 1u32))).unwrap();
+        let m = BigUint::from_bytes_be(b"flag{exec_oracle}");
+        let c = m.modpow(&e, &n);
+
+        let Some(target) = parity_script_target(&[n.to_string(), d.to_string()]) else {
+            return;
+        };
+        let mut params = params(0, &n, &e, &c);
+        params.target = target;
+        let res = run_lsb_oracle(&params, &AtomicBool::new(false), &|_| {}).unwrap();
+        assert_eq!(
+            res.plaintext.unwrap().flag.as_deref(),
+            Some("flag{exec_oracle}")
+        );
+        assert_eq!(res.queries, n.bits());
+    }
+
+    #[test]
+    fn test_half_oracle_hex_and_cancel() {
+        let (port, n, e) = spawn_oracle(true, true);
+        let m = &n - 12345u32;
+        let c = m.modpow(&e, &n);
+        let mut p = params(port, &n, &e, &c);
+        p.kind = Some("half".into());
+        p.encoding = Some("hex".into());
+        let res = run_lsb_oracle(&p, &AtomicBool::new(false), &|_| {}).unwrap();
+        assert_eq!(res.plaintext.unwrap().decimal, m.to_string());
+
+        // 已取消时不发起查询
+        let (port, ..) = spawn_oracle(false, false);
+        let res =
+            run_lsb_oracle(&params(port, &n, &e, &c), &AtomicBool::new(true), &|_| {}).unwrap();
+        assert!(res.cancelled && res.queries == 0);
+    }
+}
@@ -1,5 +1,40 @@
+pub(crate) mod asn1;
+pub(crate) mod base64_alphabet;
+pub(crate) mod batch_gcd;
 pub(crate) mod big_rsa;
 pub(crate) mod caesar;
+pub(crate) mod cbc_attack;
+pub(crate) mod codec;
 pub mod common_modulus;
+pub(crate) mod coppersmith;
+pub(crate) mod dlog;
+pub(crate) mod ecb_attack;
+pub(crate) mod ecc;
+pub(crate) mod factor;
+pub(crate) mod franklin_reiter;
+pub(crate) mod hastad;
+pub(crate) mod key_import;
+pub(crate) mod knapsack;
+pub(crate) mod language;
+pub(crate) mod lattice;
+pub(crate) mod lcg;
+pub(crate) mod lfsr;
+pub(crate) mod lsb_oracle;
+pub(crate) mod modular_root;
+pub(crate) mod mt19937;
+pub(crate) mod number_theory;
+pub(crate) mod oracle;
+pub(crate) mod padding;
+pub(crate) mod poly;
+pub(crate) mod public_key;
+pub(crate) mod rc4;
 pub(crate) mod replacer;
+pub(crate) mod rsa_solver;
+pub(crate) mod sm2;
+pub(crate) mod sm3;
+pub(crate) mod sm4;
+pub(crate) mod symmetric;
+pub(crate) mod task;
+pub(crate) mod tea;
+pub(crate) mod wiener;
 pub(crate) mod word_freq;
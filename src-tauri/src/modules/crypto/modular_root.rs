@@ -0,0 +1,546 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::codec::{looks_like_plaintext, parse_biguint, render_number, NumberView};
+use super::number_theory::is_probable_prime;
+
+// 单个素数下 e 次方根的个数上限 (即 gcd(e, p - 1) 的上限)
+const MAX_ROOTS_PER_PRIME: u64 = 1 << 20;
+// CRT 组合的候选总数上限
+const MAX_COMBINATIONS: u128 = 1 << 24;
+// 返回给前端的候选明文个数上限
+const MAX_CANDIDATES: usize = 64;
+// AMM 中子群离散对数 (BSGS) 所允许的最大素数阶
+const MAX_DLOG_ORDER: u64 = 1 << 40;
+
+#[derive(Serialize, Debug)]
+pub struct NthRootResult {
+    pub n: String,
+    pub roots_per_factor: Vec<String>, // 每个素数幂模数下的根个数, 形如 "p^k: 5"
+    pub total: String,                 // CRT 组合后的候选总数
+    pub unfiltered: bool,              // 没有候选通过筛选, 返回的是全部根
+    pub truncated: bool,               // 通过筛选的候选超过上限被截断
+    pub candidates: Vec<NumberView>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RabinResult {
+    pub n: String,
+    pub roots: Vec<NumberView>, // 四个平方根, 从小到大
+}
+
+/// e 与 φ 不互质时的 RSA 解密: 在每个素因子下求出 c 的全部 e 次方根, CRT 合并后筛选明文
+///
+/// pattern 为可选的正则 (匹配有损 UTF-8 解码后的明文), 缺省时保留可打印文本或含 flag 的候选
+#[tauri::command]
+pub fn rsa_nth_root_decrypt(
+    factors: Vec<String>,
+    e: String,
+    c: String,
+    pattern: Option<String>,
+) -> Result<NthRootResult, String> {
+    let factors = factors
+        .iter()
+        .enumerate()
+        .map(|(i, p)| parse_biguint(p).map_err(|err| format!("第 {} 个素因子: {}", i + 1, err)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let e = parse_biguint(&e).map_err(|err| format!("e 格式错误: {}", err))?;
+    let c = parse_biguint(&c).map_err(|err| format!("c 格式错误: {}", err))?;
+    let pattern = match pattern.as_deref().map(str::trim) {
+        Some(p) if !p.is_empty() => {
+            Some(Regex::new(p).map_err(|err| format!("筛选正则无效: {}", err))?)
+        }
+        _ => None,
+    };
+
+    let lists = nth_roots_mod_factors(&c, &e, &factors)?;
+    let n: BigUint = lists.iter().map(|(_, m)| m).product();
+    let roots_per_factor = lists
+        .iter()
+        .map(|(roots, m)| format!("{}: {}", m, roots.len()))
+        .collect();
+    let total: u128 = lists.iter().map(|(roots, _)| roots.len() as u128).product();
+
+    let keep = |bytes: &[u8]| match &pattern {
+        Some(re) => re.is_match(&String::from_utf8_lossy(bytes)),
+        None => looks_like_plaintext(bytes),
+    };
+    let mut matched = combine_roots(&lists, |m| keep(&m.to_bytes_be()))?;
+    let mut unfiltered = false;
+    if matched.is_empty() && total <= MAX_CANDIDATES as u128 {
+        matched = combine_roots(&lists, |_| true)?;
+        unfiltered = true;
+    }
+    let truncated = matched.len() > MAX_CANDIDATES;
+    matched.truncate(MAX_CANDIDATES);
+
+    Ok(NthRootResult {
+        n: n.to_string(),
+        roots_per_factor,
+        total: total.to_string(),
+        unfiltered,
+        truncated,
+        candidates: matched.iter().map(render_number).collect(),
+    })
+}
+
+/// Rabin 解密: c = m^2 mod pq 的四个平方根
+#[tauri::command]
+pub fn rabin_decrypt(p: String, q: String, c: String) -> Result<RabinResult, String> {
+    let p = parse_biguint(&p).map_err(|err| format!("p 格式错误: {}", err))?;
+    let q = parse_biguint(&q).map_err(|err| format!("q 格式错误: {}", err))?;
+    let c = parse_biguint(&c).map_err(|err| format!("c 格式错误: {}", err))?;
+    if p == q {
+        return Err("p 与 q 不能相同".into());
+    }
+
+    let lists = nth_roots_mod_factors(&c, &BigUint::from(2u32), &[p.clone(), q.clone()])?;
+    let roots = combine_roots(&lists, |_| true)?;
+    Ok(RabinResult {
+        n: (p * q).to_string(),
+        roots: roots.iter().map(render_number).collect(),
+    })
+}
+
+// ===================== 核心算法 =====================
+
+/// 按素数幂分组后, 求 x^e ≡ a 在每个 p^k 下的全部解, 返回 (根列表, p^k)
+pub fn nth_roots_mod_factors(
+    a: &BigUint,
+    e: &BigUint,
+    factors: &[BigUint],
+) -> Result<Vec<(Vec<BigUint>, BigUint)>, String> {
+    if factors.is_empty() {
+        return Err("至少需要一个素因子".into());
+    }
+    if e.is_zero() {
+        return Err("e 不能为 0".into());
+    }
+    let mut sorted = factors.to_vec();
+    sorted.sort();
+
+    let mut lists = Vec::new();
+    for chunk in sorted.chunk_by(|x, y| x == y) {
+        let p = &chunk[0];
+        let k = chunk.len() as u32;
+        // 合数模数下 Tonelli-Shanks / AMM 的循环不会终止
+        if !is_probable_prime(p) {
+            return Err(format!("因子 {} 不是素数", p));
+        }
+        let mut roots = nth_roots_mod_prime(a, e, p)?;
+        let modulus = p.pow(k);
+        if k > 1 {
+            if (e % p).is_zero() || (a % p).is_zero() {
+                return Err(format!("暂不支持 p | e 或 p | c 时在 {}^{} 下开方", p, k));
+            }
+            roots = roots
+                .iter()
+                .map(|r| hensel_lift(r, a, e, &modulus))
+                .collect();
+        }
+        if roots.is_empty() {
+            return Err(format!(
+                "c 在模 {} 下不是 e 次剩余, 无解 (因子或 e 可能有误)",
+                p
+            ));
+        }
+        lists.push((roots, modulus));
+    }
+    Ok(lists)
+}
+
+/// 枚举各模数下根的全部组合并用 CRT 合并, 只保留满足 keep 的结果 (从小到大)
+pub fn combine_roots<F>(lists: &[(Vec<BigUint>, BigUint)], keep: F) -> Result<Vec<BigUint>, String>
+where
+    F: Fn(&BigUint) -> bool + Sync,
+{
+    let total: u128 = lists.iter().map(|(roots, _)| roots.len() as u128).product();
+    if total > MAX_COMBINATIONS {
+        return Err(format!("候选组合数过多 ({}), 无法枚举", total));
+    }
+
+    // x = Σ r_i·T_i mod N, 其中 T_i ≡ 1 (mod m_i), T_i ≡ 0 (mod m_j, j ≠ i)
+    let n: BigUint = lists.iter().map(|(_, m)| m).product();
+    let mut basis = Vec::with_capacity(lists.len());
+    for (_, m) in lists {
+        let rest = &n / m;
+        let inv = if m.is_one() {
+            BigUint::zero()
+        } else {
+            (&rest % m).modinv(m).ok_or("各模数不互质")?
+        };
+        basis.push(rest * inv % &n);
+    }
+
+    let mut found: Vec<BigUint> = (0..total as u64)
+        .into_par_iter()
+        .filter_map(|mut idx| {
+            let mut x = BigUint::zero();
+            for ((roots, _), t) in lists.iter().zip(&basis) {
+                let len = roots.len() as u64;
+                x += &roots[(idx % len) as usize] * t;
+                idx /= len;
+            }
+            let x = x % &n;
+            keep(&x).then_some(x)
+        })
+        .collect();
+    found.sort();
+    Ok(found)
+}
+
+/// x^e ≡ a (mod p) 的全部解 (p 为素数), 无解时返回空列表
+pub fn nth_roots_mod_prime(a: &BigUint, e: &BigUint, p: &BigUint) -> Result<Vec<BigUint>, String> {
+    let a = a % p;
+    if a.is_zero() {
+        return Ok(vec![BigUint::zero()]);
+    }
+    let p_1 = p - 1u32;
+    if p_1.is_one() {
+        return Ok(vec![a]);
+    }
+
+    let d = e.gcd(&p_1);
+    if d.is_one() {
+        let inv = e.modinv(&p_1).ok_or("模逆不存在")?;
+        return Ok(vec![a.modpow(&inv, p)]);
+    }
+    let d_small = d
+        .to_u64()
+        .filter(|&d| d <= MAX_ROOTS_PER_PRIME)
+        .ok_or_else(|| format!("gcd(e, p - 1) = {} 过大, 根的个数无法枚举", d))?;
+    let cofactor = &p_1 / &d;
+    if !a.modpow(&cofactor, p).is_one() {
+        return Ok(Vec::new());
+    }
+
+    // gcd(e/d, (p-1)/d) = 1, 先把 x^e = a 化为 x^d = b
+    let b = if cofactor.is_one() {
+        BigUint::one()
+    } else {
+        let u = (e / &d).modinv(&cofactor).ok_or("模逆不存在")?;
+        a.modpow(&u, p)
+    };
+
+    // 按素因子从小到大逐次开 r 次方, 每一步都保证结果仍是剩余次数的幂
+    let primes = factor_u64(d_small);
+    let mut x = b;
+    let mut remaining = d_small;
+    for &r in primes.iter() {
+        remaining /= r;
+        let root = if r == 2 {
+            sqrt_mod_prime(&x, p).ok_or("开平方失败")?
+        } else {
+            amm_root(&x, r, p)?
+        };
+        x = if remaining == 1 {
+            root
+        } else {
+            let exp = &p_1 / remaining;
+            let zeta = root_of_unity(r, &primes, p);
+            let mut cand = root;
+            let mut ok = false;
+            for _ in 0..r {
+                if cand.modpow(&exp, p).is_one() {
+                    ok = true;
+                    break;
+                }
+                cand = cand * &zeta % p;
+            }
+            if !ok {
+                return Err("有限域开方失败".into());
+            }
+            cand
+        };
+    }
+    if x.modpow(e, p) != a {
+        return Err("有限域开方结果校验失败".into());
+    }
+
+    // 全部解为 x·ζ^i, ζ 为 d 次本原单位根
+    let zeta = root_of_unity(d_small, &primes, p);
+    let mut roots = Vec::with_capacity(d_small as usize);
+    let mut cur = x;
+    for _ in 0..d_small {
+        let next = &cur * &zeta % p;
+        roots.push(cur);
+        cur = next;
+    }
+    roots.sort();
+    Ok(roots)
+}
+
+/// Tonelli-Shanks 求模素数平方根, a 不是二次剩余时返回 None
+pub fn sqrt_mod_prime(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let a = a % p;
+    if a.is_zero() || *p == BigUint::from(2u32) {
+        return Some(a);
+    }
+    let p_1 = p - 1u32;
+    if !a.modpow(&(&p_1 >> 1), p).is_one() {
+        return None;
+    }
+    if (p % 4u32) == BigUint::from(3u32) {
+        return Some(a.modpow(&((p + 1u32) >> 2), p));
+    }
+
+    let s = p_1.trailing_zeros().unwrap_or(0);
+    let q = &p_1 >> s;
+    let z = non_residue(2, p);
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut r = a.modpow(&((&q + 1u32) >> 1), p);
+    while !t.is_one() {
+        // 找最小的 i 使 t^(2^i) = 1
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while !t2.is_one() {
+            t2 = &t2 * &t2 % p;
+            i += 1;
+        }
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = &b * &b % p;
+        t = t * &c % p;
+        r = r * &b % p;
+    }
+    Some(r)
+}
+
+/// Adleman-Manders-Miller: 素数 r | p - 1 时求 a 的一个 r 次方根 (要求 a 是 r 次剩余)
+fn amm_root(a: &BigUint, r: u64, p: &BigUint) -> Result<BigUint, String> {
+    let r_big = BigUint::from(r);
+    let p_1 = p - 1u32;
+    // p - 1 = r^t·s, gcd(r, s) = 1
+    let mut t = 0u32;
+    let mut s = p_1.clone();
+    while (&s % r).is_zero() {
+        s /= r;
+        t += 1;
+    }
+    // α 满足 r·α ≡ 1 (mod s)
+    let alpha = if s.is_one() {
+        BigUint::zero()
+    } else {
+        r_big.modinv(&s).ok_or("模逆不存在")?
+    };
+
+    let rho = non_residue(r, p);
+    let r_pow = |k: u32| r_big.pow(k);
+    // g 为 r 阶元素, 用于子群离散对数
+    let g = rho.modpow(&(r_pow(t - 1) * &s), p);
+    let mut b = a.modpow(&(&r_big * &alpha + &p_1 - 1u32), p); // a^(rα - 1)
+    let mut c = rho.modpow(&s, p);
+    let mut h = BigUint::one();
+    for i in 1..t {
+        let d = b.modpow(&r_pow(t - 1 - i), p);
+        let j = if d.is_one() {
+            0
+        } else {
+            // 求 j 使 d·g^j = 1
+            let inv = d.modinv(p).ok_or("模逆不存在")?;
+            subgroup_dlog(&g, &inv, r, p).ok_or("AMM 子群离散对数失败")?
+        };
+        let cj = c.modpow(&BigUint::from(j), p);
+        b = b * cj.modpow(&r_big, p) % p;
+        h = h * cj % p;
+        c = c.modpow(&r_big, p);
+    }
+    Ok(a.modpow(&alpha, p) * h % p)
+}
+
+/// BSGS 求 g^x = target 在 order 阶子群内的离散对数
+fn subgroup_dlog(g: &BigUint, target: &BigUint, order: u64, p: &BigUint) -> Option<u64> {
+    if order > MAX_DLOG_ORDER {
+        return None;
+    }
+    let m = (order as f64).sqrt().ceil() as u64;
+    let mut baby = HashMap::with_capacity(m as usize);
+    let mut cur = BigUint::one();
+    for j in 0..m {
+        baby.entry(cur.clone()).or_insert(j);
+        cur = cur * g % p;
+    }
+    // factor = g^(-m)
+    let factor = g.modpow(&BigUint::from(m), p).modinv(p)?;
+    let mut gamma = target.clone();
+    for i in 0..m {
+        if let Some(&j) = baby.get(&gamma) {
+            return Some(i * m + j);
+        }
+        gamma = gamma * &factor % p;
+    }
+    None
+}
+
+/// 找一个非 r 次剩余 (r | p - 1)
+fn non_residue(r: u64, p: &BigUint) -> BigUint {
+    let exp = (p - 1u32) / r;
+    let mut h = BigUint::from(2u32);
+    while h.modpow(&exp, p).is_one() {
+        h += 1u32;
+    }
+    h
+}
+
+/// d 次本原单位根 (d | p - 1), primes 需包含 d 的全部素因子
+fn root_of_unity(d: u64, primes: &[u64], p: &BigUint) -> BigUint {
+    let exp = (p - 1u32) / d;
+    let mut h = BigUint::from(2u32);
+    loop {
+        let zeta = h.modpow(&exp, p);
+        let primitive = primes
+            .iter()
+            .filter(|&&r| d.is_multiple_of(r))
+            .all(|&r| !zeta.modpow(&BigUint::from(d / r), p).is_one());
+        if primitive {
+            return zeta;
+        }
+        h += 1u32;
+    }
+}
+
+/// Hensel 提升: 把模 p 下的根 x 提升到模 p^k (要求 p ∤ e·x)
+fn hensel_lift(x: &BigUint, a: &BigUint, e: &BigUint, modulus: &BigUint) -> BigUint {
+    let a = a % modulus;
+    let e_1 = e - 1u32;
+    let mut x = x.clone();
+    // 牛顿迭代每轮精度翻倍, 迭代到不再变化为止
+    loop {
+        let fx = (x.modpow(e, modulus) + modulus - &a) % modulus;
+        if fx.is_zero() {
+            return x;
+        }
+        let dfx = (e * x.modpow(&e_1, modulus)) % modulus;
+        let Some(inv) = dfx.modinv(modulus) else {
+            return x;
+        };
+        x = (&x + modulus - fx * inv % modulus) % modulus;
+    }
+}
+
+/// 小整数的素因子分解 (带重数, 从小到大)
+fn factor_u64(mut n: u64) -> Vec<u64> {
+    let mut out = Vec::new();
+    let mut f = 2u64;
+    while f * f <= n {
+        while n.is_multiple_of(f) {
+            out.push(f);
+            n /= f;
+        }
+        f += 1;
+    }
+    if n > 1 {
+        out.push(n);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_sqrt_and_amm_mod_prime() {
+        // 998244353 = 119·2^23 + 1, Tonelli-Shanks 需要多轮迭代
+        for p in [1_000_000_007u64, 998_244_353] {
+            let p = BigUint::from(p);
+            let x = BigUint::from(123_456_789u32);
+            let r = sqrt_mod_prime(&(&x * &x % &p), &p).unwrap();
+            assert!(r == x || r == &p - &x);
+        }
+        assert!(sqrt_mod_prime(&BigUint::from(5u32), &BigUint::from(1_000_000_007u64)).is_none());
+
+        // x^(2^4·7·17) = a 有 gcd(e, p - 1) = 16·119 个解
+        let p = BigUint::from(998_244_353u64);
+        let e = BigUint::from(16u32 * 119);
+        let x = BigUint::from(31_415_926u32);
+        let a = x.modpow(&e, &p);
+        let roots = nth_roots_mod_prime(&a, &e, &p).unwrap();
+        assert_eq!(roots.len(), 16 * 119);
+        assert!(roots.contains(&x));
+        assert!(roots.iter().all(|r| r.modpow(&e, &p) == a));
+        // 非剩余无解
+        let non = non_residue(7, &p);
+        assert!(nth_roots_mod_prime(&non, &BigUint::from(7u32), &p)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rsa_e_divides_p_minus_1() {
+        // e = 4919 整除 p - 1, 与 q - 1 互质
+        let p = big("351078539220351560940160484423438804497");
+        let q = big("264368716054775866813995788202255419117");
+        let e = BigUint::from(4919u32);
+        let m = BigUint::from_bytes_be(b"flag{AMM_r00t}");
+        let c = m.modpow(&e, &(&p * &q));
+
+        let res = rsa_nth_root_decrypt(
+            vec![p.to_string(), q.to_string()],
+            e.to_string(),
+            c.to_string(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.total, "4919");
+        assert!(!res.unfiltered);
+        assert_eq!(res.candidates.len(), 1);
+        assert_eq!(res.candidates[0].utf8.as_deref(), Some("flag{AMM_r00t}"));
+
+        let res = rsa_nth_root_decrypt(
+            vec![p.to_string(), q.to_string()],
+            e.to_string(),
+            c.to_string(),
+            Some("^flag".into()),
+        )
+        .unwrap();
+        assert_eq!(res.candidates[0].decimal, m.to_string());
+    }
+
+    #[test]
+    fn test_rabin_and_prime_power() {
+        // p ≡ 3 (mod 4), q ≡ 1 (mod 8)
+        let p = big("40241516113694333970173248559");
+        let q = big("55992294901713873722108962393");
+        let n = &p * &q;
+        let m = BigUint::from_bytes_be(b"rabin!");
+        let c = m.modpow(&BigUint::from(2u32), &n);
+        let res = rabin_decrypt(p.to_string(), q.to_string(), c.to_string()).unwrap();
+        assert_eq!(res.roots.len(), 4);
+        assert!(res
+            .roots
+            .iter()
+            .any(|r| r.utf8.as_deref() == Some("rabin!")));
+
+        // N = p^2·q, 在 p^2 下用 Hensel 提升
+        let n = &p * &p * &q;
+        let e = BigUint::from(6u32);
+        let m = BigUint::from_bytes_be(b"hensel lifting works");
+        let c = m.modpow(&e, &n);
+        let lists = nth_roots_mod_factors(&c, &e, &[p.clone(), q.clone(), p.clone()]).unwrap();
+        let all = combine_roots(&lists, |_| true).unwrap();
+        assert!(all.contains(&m));
+        assert!(all.iter().all(|r| r.modpow(&e, &n) == c));
+    }
+
+    #[test]
+    fn test_rejects_composite_factor() {
+        // 697 = 17·41, 直接开方会在 Tonelli-Shanks 中死循环
+        let err = rabin_decrypt("697".into(), "43".into(), "81".into()).unwrap_err();
+        assert!(err.contains("697"));
+        let res = rsa_nth_root_decrypt(vec!["697".into()], "3".into(), "81".into(), None);
+        assert!(res.is_err());
+        assert!(rabin_decrypt("1".into(), "43".into(), "81".into()).is_err());
+    }
+}
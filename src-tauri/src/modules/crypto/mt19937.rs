@@ -0,0 +1,625 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State, Window};
+
+use super::codec::parse_biguint;
+use super::task::CryptoTasks;
+
+pub const MT19937_TASK: &str = "mt19937";
+
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908_b0df;
+const UPPER: u32 = 0x8000_0000;
+const LOWER: u32 = 0x7fff_ffff;
+const STATE_BITS: usize = N * 32;
+const LIMBS: usize = STATE_BITS / 64;
+/// 有效状态位数: x[0] 只有最高位参与后续 twist
+const EFFECTIVE_BITS: usize = STATE_BITS - 31;
+const DEFAULT_PREDICT: usize = 10;
+const MAX_LIST: usize = 100_000; // 预测 / 倒推个数上限
+const PROGRESS_INTERVAL: usize = 256;
+
+#[derive(Serialize, Clone)]
+pub struct MtProgress {
+    pub stage: String, // build: 符号化生成方程 / done
+    pub done: usize,
+    pub total: usize,
+    pub rank: usize,
+}
+
+/// outputs 为以空白或逗号分隔的连续输出, `?` 表示该次输出未被观察到
+///
+/// bits 为每个输出对应 getrandbits(bits) 的位数 (默认 32, 即 C++ mt19937 / genrand_uint32);
+/// bits < 32 时只知道高位, bits > 32 时每个输出由若干个 32 位字按低位在前拼成
+#[derive(Deserialize, Default)]
+pub struct MtRecoverParams {
+    pub outputs: String,
+    pub bits: Option<u32>,
+    pub predict: Option<usize>,
+    pub previous: Option<usize>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MtRecoverResult {
+    pub method: String, // untemper: 存在 624 个连续完整输出; gf2: 按已知位解线性方程组
+    pub rank: usize,    // 方程组的秩 (状态共 19968 位, 其中有效位 19937)
+    pub next: Vec<String>, // 紧接在最后一个观测之后的输出, 与输入格式相同
+    pub previous: Vec<String>, // 第一个观测之前的输出, 按时间顺序排列
+    pub python_state: String, // 最后一个观测之后的 random.setstate() 参数
+    pub verified: bool, // 恢复的状态能否重现所有已知位
+    pub cancelled: bool,
+}
+
+/// 由 Python random / C++ mt19937 的连续输出恢复内部状态, 预测之后与之前的输出
+#[tauri::command]
+pub async fn mt19937_recover(
+    window: Window,
+    tasks: State<'_, CryptoTasks>,
+    params: MtRecoverParams,
+) -> Result<MtRecoverResult, String> {
+    let handle = tasks.begin(MT19937_TASK)?;
+    let cancel = handle.flag();
+
+    tokio::task::spawn_blocking(move || {
+        let _handle = handle;
+        let emit = |progress: MtProgress| {
+            let _ = window.emit("mt19937-progress", progress);
+        };
+        run_mt_recover(&params, &cancel, &emit)
+    })
+    .await
+    .map_err(|e| format!("MT19937 任务异常退出: {}", e))?
+}
+
+pub fn run_mt_recover(
+    params: &MtRecoverParams,
+    cancel: &AtomicBool,
+    progress: &dyn Fn(MtProgress),
+) -> Result<MtRecoverResult, String> {
+    let bits = params.bits.unwrap_or(32);
+    if bits == 0 {
+        return Err("位数必须大于 0".into());
+    }
+    let per = bits.div_ceil(32) as usize;
+    let observations = parse_outputs(&params.outputs, bits)?;
+    let leaks = expand_leaks(&observations, bits);
+    let predict = params.predict.unwrap_or(DEFAULT_PREDICT).min(MAX_LIST);
+    let previous = params.previous.unwrap_or(0).min(MAX_LIST);
+
+    let mut result = MtRecoverResult {
+        method: String::new(),
+        rank: 0,
+        next: Vec::new(),
+        previous: Vec::new(),
+        python_state: String::new(),
+        verified: false,
+        cancelled: false,
+    };
+    let state = match full_run(&leaks) {
+        Some(start) => {
+            result.method = "untemper".into();
+            result.rank = EFFECTIVE_BITS;
+            let window: Vec<u32> = leaks[start..start + N]
+                .iter()
+                .map(|&(v, _)| untemper(v))
+                .collect();
+            rewind(window, start)
+        }
+        None => {
+            result.method = "gf2".into();
+            match solve_gf2(&leaks, cancel, progress)? {
+                Some((state, rank)) => {
+                    result.rank = rank;
+                    state
+                }
+                None => {
+                    result.cancelled = true;
+                    return Ok(result);
+                }
+            }
+        }
+    };
+
+    // 重新生成所有观测过的字并核对已知位
+    let total = leaks.len();
+    let mut seq = MtSequence::new(state.clone());
+    let words: Vec<u32> = (0..total + predict * per).map(|_| seq.next()).collect();
+    result.verified = leaks
+        .iter()
+        .zip(&words)
+        .all(|(&(v, b), &w)| b == 0 || top_bits(w, b) == v);
+    result.next = words[total..]
+        .chunks(per)
+        .map(|c| compose(c, bits).to_string())
+        .collect();
+
+    let mut back = MtSequence::new(state.clone());
+    let mut before: Vec<u32> = (0..previous * per).map(|_| back.prev()).collect();
+    before.reverse();
+    result.previous = before
+        .chunks(per)
+        .map(|c| compose(c, bits).to_string())
+        .collect();
+
+    result.python_state = python_state(&state, total);
+    Ok(result)
+}
+
+// ===================== 输入解析 =====================
+
+fn parse_outputs(s: &str, bits: u32) -> Result<Vec<Option<BigUint>>, String> {
+    let items: Vec<Option<BigUint>> = s
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '[' || c == ']')
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            if t == "?" {
+                return Ok(None);
+            }
+            let v = parse_biguint(t).map_err(|e| format!("输出格式错误: {}", e))?;
+            if v.bits() > bits as u64 {
+                return Err(format!("{} 超出 {} 位", t, bits));
+            }
+            Ok(Some(v))
+        })
+        .collect::<Result<_, String>>()?;
+    if items.iter().all(Option::is_none) {
+        return Err("没有可用的输出".into());
+    }
+    Ok(items)
+}
+
+/// 把每个观测拆成 32 位字的已知高位: (高位的值, 已知位数), 位数为 0 表示未知
+fn expand_leaks(observations: &[Option<BigUint>], bits: u32) -> Vec<(u32, u32)> {
+    let per = bits.div_ceil(32);
+    let mut leaks = Vec::new();
+    for obs in observations {
+        let digits = obs.as_ref().map(|v| v.to_u32_digits()).unwrap_or_default();
+        for i in 0..per {
+            let known = (bits - 32 * i).min(32);
+            match obs {
+                Some(_) => leaks.push((digits.get(i as usize).copied().unwrap_or(0), known)),
+                None => leaks.push((0, 0)),
+            }
+        }
+    }
+    leaks
+}
+
+/// 与 Python getrandbits 相同的拼接方式: 低位字在前, 最后一个字只取高位
+fn compose(words: &[u32], bits: u32) -> BigUint {
+    let mut digits = Vec::with_capacity(words.len());
+    let mut left = bits;
+    for &w in words {
+        digits.push(if left < 32 { w >> (32 - left) } else { w });
+        left = left.saturating_sub(32);
+    }
+    BigUint::new(digits)
+}
+
+fn top_bits(word: u32, bits: u32) -> u32 {
+    if bits >= 32 {
+        word
+    } else {
+        word >> (32 - bits)
+    }
+}
+
+fn full_run(leaks: &[(u32, u32)]) -> Option<usize> {
+    let mut run = 0;
+    for (i, &(_, b)) in leaks.iter().enumerate() {
+        run = if b == 32 { run + 1 } else { 0 };
+        if run == N {
+            return Some(i + 1 - N);
+        }
+    }
+    None
+}
+
+/// Python 的 mt 数组即最近消耗的 624 个状态字 x[consumed-624..consumed), index = 624
+/// 表示下一次调用先整体 twist
+fn python_state(state: &[u32], consumed: usize) -> String {
+    let mut seq = MtSequence::new(state.to_vec());
+    if consumed >= N {
+        for _ in 0..consumed {
+            seq.state_next();
+        }
+    } else {
+        for _ in consumed..N {
+            seq.prev_state();
+        }
+    }
+    let words: Vec<String> = seq.window.iter().map(u32::to_string).collect();
+    format!("(3, ({}, 624), None)", words.join(", "))
+}
+
+// ===================== 发生器 =====================
+
+fn temper(mut y: u32) -> u32 {
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c_5680;
+    y ^= (y << 15) & 0xefc6_0000;
+    y ^ (y >> 18)
+}
+
+fn untemper(mut y: u32) -> u32 {
+    y ^= y >> 18;
+    y ^= (y << 15) & 0xefc6_0000;
+    let y = (0..4).fold(y, |x, _| y ^ ((x << 7) & 0x9d2c_5680));
+    (0..2).fold(y, |x, _| y ^ (x >> 11))
+}
+
+fn twist(lo: u32, hi: u32) -> u32 {
+    let y = (lo & UPPER) | (hi & LOWER);
+    (y >> 1) ^ if y & 1 == 1 { MATRIX_A } else { 0 }
+}
+
+/// 状态字序列 x[k+624] = x[k+397] ^ twist(x[k], x[k+1]) 上的滑动窗口,
+/// 任意连续 624 个字都是合法状态, 因而可以向前生成, 也可以逐字向后倒推
+struct MtSequence {
+    window: VecDeque<u32>,
+    pos: usize, // 下一个输出在窗口中的下标
+}
+
+impl MtSequence {
+    fn new(state: Vec<u32>) -> Self {
+        MtSequence {
+            window: state.into(),
+            pos: 0,
+        }
+    }
+
+    fn state_next(&mut self) -> u32 {
+        if self.pos == N {
+            let w = &self.window;
+            let next = w[M] ^ twist(w[0], w[1]);
+            self.window.pop_front();
+            self.window.push_back(next);
+            self.pos -= 1;
+        }
+        self.pos += 1;
+        self.window[self.pos - 1]
+    }
+
+    fn next(&mut self) -> u32 {
+        temper(self.state_next())
+    }
+
+    /// 窗口起点之前的状态字: 由 x[k+623] 得到 x[k-1] 的最高位, 由 x[k+622] 得到其低 31 位
+    fn prev_state(&mut self) -> u32 {
+        let w = &self.window;
+        let mut t = w[N - 1] ^ w[M - 1];
+        if t & UPPER != 0 {
+            t ^= MATRIX_A;
+        }
+        let mut prev = (t << 1) & UPPER;
+        let mut t = w[N - 2] ^ w[M - 2];
+        if t & UPPER != 0 {
+            t ^= MATRIX_A;
+            prev |= 1;
+        }
+        prev |= (t << 1) & LOWER;
+        self.window.pop_back();
+        self.window.push_front(prev);
+        prev
+    }
+
+    fn prev(&mut self) -> u32 {
+        temper(self.prev_state())
+    }
+}
+
+/// 由位置 start 处的窗口倒推出位置 0 处的窗口
+fn rewind(window: Vec<u32>, start: usize) -> Vec<u32> {
+    let mut seq = MtSequence::new(window);
+    for _ in 0..start {
+        seq.prev_state();
+    }
+    seq.window.into()
+}
+
+// ===================== GF(2) 求解 =====================
+
+type Row = Vec<u64>;
+
+/// 状态位 (字 i, 位 j) 对应的列: x[0] 的低 31 位只影响第 0 个输出, 排在最后,
+/// 这样主元落在前 EFFECTIVE_BITS 列的行数就是后续输出被确定的程度
+fn column(i: usize, j: usize) -> usize {
+    if i > 0 {
+        (i - 1) * 32 + j + 1
+    } else if j == 31 {
+        0
+    } else {
+        EFFECTIVE_BITS + j
+    }
+}
+
+fn unit(bit: usize) -> Row {
+    let mut row = vec![0u64; LIMBS];
+    row[bit / 64] |= 1 << (bit % 64);
+    row
+}
+
+fn xor(a: &Row, b: &Row) -> Row {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+/// 符号化的 32 位字: 第 j 个元素为该字第 j 位 (最低位为 0) 关于初始状态位的线性组合
+type SymWord = Vec<Row>;
+
+fn sym_twist(lo: &SymWord, hi: &SymWord, mid: &SymWord) -> SymWord {
+    // y 的第 31 位来自 lo, 其余来自 hi; 结果 = mid ^ (y >> 1) ^ (y0 ? A : 0)
+    (0..32)
+        .map(|j| {
+            let mut row = mid[j].clone();
+            let shifted = if j == 30 {
+                Some(&lo[31])
+            } else if j < 30 {
+                Some(&hi[j + 1])
+            } else {
+                None
+            };
+            if let Some(s) = shifted {
+                row = xor(&row, s);
+            }
+            if MATRIX_A >> j & 1 == 1 {
+                row = xor(&row, &hi[0]);
+            }
+            row
+        })
+        .collect()
+}
+
+/// 输出第 j 位关于状态字各位的线性组合: temper 是 GF(2) 上的线性映射, 第 i 列即 temper(1 << i)
+fn sym_temper_bit(word: &SymWord, j: usize) -> Row {
+    let mut row = vec![0u64; LIMBS];
+    for (i, src) in word.iter().enumerate() {
+        if temper(1 << i) >> j & 1 == 1 {
+            for (r, s) in row.iter_mut().zip(src) {
+                *r ^= s;
+            }
+        }
+    }
+    row
+}
+
+/// 以最低位为主元的增量消元
+struct Gf2Basis {
+    rows: Vec<Option<(Row, bool)>>,
+    rank: usize,
+    effective_rank: usize,
+}
+
+impl Gf2Basis {
+    fn insert(&mut self, mut row: Row, mut rhs: bool) -> Result<(), String> {
+        let mut limb = 0;
+        while limb < LIMBS {
+            if row[limb] == 0 {
+                limb += 1;
+                continue;
+            }
+            let pivot = limb * 64 + row[limb].trailing_zeros() as usize;
+            match &self.rows[pivot] {
+                Some((basis, b)) => {
+                    for (r, b) in row[limb..].iter_mut().zip(&basis[limb..]) {
+                        *r ^= b;
+                    }
+                    rhs ^= b;
+                }
+                None => {
+                    self.rows[pivot] = Some((row, rhs));
+                    self.rank += 1;
+                    if pivot < EFFECTIVE_BITS {
+                        self.effective_rank += 1;
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        if rhs {
+            return Err("方程组矛盾: 输出并非来自同一个 MT19937 或位数设置有误".into());
+        }
+        Ok(())
+    }
+
+    /// 从高位主元往低位回代, 自由变量取 0
+    fn solve(&self) -> Vec<u32> {
+        let mut x = vec![0u64; LIMBS];
+        for pivot in (0..STATE_BITS).rev() {
+            if let Some((row, rhs)) = &self.rows[pivot] {
+                let mut parity = *rhs;
+                for (r, v) in row[pivot / 64..].iter().zip(&x[pivot / 64..]) {
+                    parity ^= (r & v).count_ones() & 1 == 1;
+                }
+                // row 的主元位对应的 x 尚为 0, 不影响上面的奇偶性
+                if parity {
+                    x[pivot / 64] |= 1 << (pivot % 64);
+                }
+            }
+        }
+        let bit = |c: usize| (x[c / 64] >> (c % 64) & 1) as u32;
+        (0..N)
+            .map(|i| (0..32).fold(0u32, |w, j| w | bit(column(i, j)) << j))
+            .collect()
+    }
+}
+
+/// 初始窗口 x[0..624) 的 19968 位作为未知数, 每个已知输出位都是它们的线性组合
+fn solve_gf2(
+    leaks: &[(u32, u32)],
+    cancel: &AtomicBool,
+    progress: &dyn Fn(MtProgress),
+) -> Result<Option<(Vec<u32>, usize)>, String> {
+    let mut window: VecDeque<SymWord> = (0..N)
+        .map(|i| (0..32).map(|j| unit(column(i, j))).collect())
+        .collect();
+    let mut basis = Gf2Basis {
+        rows: vec![None; STATE_BITS],
+        rank: 0,
+        effective_rank: 0,
+    };
+    let total = leaks.len();
+    for (k, &(value, bits)) in leaks.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        if k >= N {
+            let next = sym_twist(&window[0], &window[1], &window[M]);
+            window.pop_front();
+            window.push_back(next);
+        }
+        if bits > 0 {
+            let word = &window[k.min(N - 1)];
+            for b in 0..bits {
+                let rhs = value >> (bits - 1 - b) & 1 == 1;
+                basis.insert(sym_temper_bit(word, 31 - b as usize), rhs)?;
+            }
+        }
+        // 有效位已全部确定, 剩余输出留给结果校验即可
+        if basis.effective_rank == EFFECTIVE_BITS {
+            break;
+        }
+        if k % PROGRESS_INTERVAL == 0 || k + 1 == total {
+            progress(MtProgress {
+                stage: "build".into(),
+                done: k + 1,
+                total,
+                rank: basis.rank,
+            });
+        }
+    }
+    progress(MtProgress {
+        stage: "done".into(),
+        done: total,
+        total,
+        rank: basis.rank,
+    });
+    Ok(Some((basis.solve(), basis.rank)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 参考实现: 与 CPython _randommodule.c 的 init_genrand 一致
+    fn seeded(seed: u32) -> MtSequence {
+        let mut mt = vec![0u32; N];
+        mt[0] = seed;
+        for i in 1..N {
+            mt[i] = 1_812_433_253u32
+                .wrapping_mul(mt[i - 1] ^ (mt[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+        let mut seq = MtSequence::new(mt);
+        seq.pos = N; // 第一次输出前先 twist
+        seq
+    }
+
+    fn join(words: &[BigUint]) -> String {
+        words
+            .iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_untemper_and_prediction() {
+        // C++ std::mt19937 默认种子 5489 的第 10000 个输出为 4123659995
+        let mut gen = seeded(5489);
+        let outputs: Vec<u32> = (0..10000).map(|_| gen.next()).collect();
+        assert_eq!(outputs[9999], 4_123_659_995);
+        assert_eq!(untemper(temper(0xdead_beef)), 0xdead_beef);
+
+        let observed: Vec<BigUint> = outputs[2000..2700].iter().map(|&w| w.into()).collect();
+        let res = run_mt_recover(
+            &MtRecoverParams {
+                outputs: join(&observed),
+                predict: Some(5),
+                previous: Some(1500),
+                ..Default::default()
+            },
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(res.method, "untemper");
+        assert!(res.verified);
+        let expect = |r: std::ops::Range<usize>| -> Vec<String> {
+            outputs[r].iter().map(u32::to_string).collect()
+        };
+        assert_eq!(res.next, expect(2700..2705));
+        assert_eq!(res.previous, expect(500..2000));
+    }
+
+    #[test]
+    fn test_getrandbits_64_and_python_state() {
+        let mut gen = seeded(12345);
+        let words: Vec<u32> = (0..1400).map(|_| gen.next()).collect();
+        let obs: Vec<BigUint> = words[..1300].chunks(2).map(|c| compose(c, 64)).collect();
+        let res = run_mt_recover(
+            &MtRecoverParams {
+                outputs: join(&obs),
+                bits: Some(64),
+                predict: Some(3),
+                previous: None,
+            },
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        assert!(res.verified);
+        assert_eq!(res.next[0], compose(&words[1300..1302], 64).to_string());
+        // setstate 后的第一个输出即第 1300 个字
+        let state: Vec<u32> = res
+            .python_state
+            .strip_prefix("(3, (")
+            .and_then(|s| s.strip_suffix(", 624), None)"))
+            .unwrap()
+            .split(", ")
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let mut after = MtSequence::new(state);
+        after.pos = N;
+        assert_eq!(after.next(), words[1300]);
+    }
+
+    #[test]
+    fn test_partial_bits_gf2() {
+        let mut gen = seeded(2024);
+        let words: Vec<u32> = (0..5100).map(|_| gen.next()).collect();
+        let obs: Vec<String> = words[..5000]
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| {
+                if i % 10 == 3 {
+                    "?".to_string()
+                } else {
+                    (w >> 24).to_string()
+                }
+            })
+            .collect();
+        let res = run_mt_recover(
+            &MtRecoverParams {
+                outputs: obs.join(","),
+                bits: Some(8),
+                predict: Some(4),
+                previous: Some(2),
+            },
+            &AtomicBool::new(false),
+            &|_| {},
+        )
+        .unwrap();
+        assert_eq!(res.method, "gf2");
+        assert!(res.verified);
+        assert!(res.rank >= EFFECTIVE_BITS);
+        let expect: Vec<String> = words[5000..5004]
+            .iter()
+            .map(|w| (w >> 24).to_string())
+            .collect();
+        assert_eq!(res.next, expect);
+    }
+}
@@ -0,0 +1,257 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::RngCore;
+use serde::Serialize;
+
+use super::codec::parse_biguint;
+
+#[derive(Serialize, Debug)]
+pub struct CrtResult {
+    pub x: String,
+    pub modulus: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct IntegerRootResult {
+    pub root: String,
+    pub exact: bool, // root^k 是否恰好等于输入
+}
+
+#[tauri::command]
+pub fn chinese_remainder(
+    remainders: Vec<String>,
+    moduli: Vec<String>,
+) -> Result<CrtResult, String> {
+    if remainders.len() != moduli.len() {
+        return Err("余数与模数的个数不一致".into());
+    }
+    if remainders.is_empty() {
+        return Err("至少需要一组同余方程".into());
+    }
+
+    let mut pairs = Vec::with_capacity(remainders.len());
+    for (idx, (r, m)) in remainders.iter().zip(moduli.iter()).enumerate() {
+        let r = parse_biguint(r).map_err(|e| format!("第 {} 组余数: {}", idx + 1, e))?;
+        let m = parse_biguint(m).map_err(|e| format!("第 {} 组模数: {}", idx + 1, e))?;
+        if m.is_zero() {
+            return Err(format!("第 {} 组模数不能为零", idx + 1));
+        }
+        pairs.push((r, m));
+    }
+
+    let (x, modulus) = crt(&pairs)?;
+    Ok(CrtResult {
+        x: x.to_string(),
+        modulus: modulus.to_string(),
+    })
+}
+
+#[tauri::command]
+pub fn integer_root(value: String, k: u32) -> Result<IntegerRootResult, String> {
+    if k == 0 {
+        return Err("开方次数必须大于 0".into());
+    }
+    let value = parse_biguint(&value)?;
+    let root = value.nth_root(k);
+    let exact = root.pow(k) == value;
+    Ok(IntegerRootResult {
+        root: root.to_string(),
+        exact,
+    })
+}
+
+// ===================== 核心算法 =====================
+
+/// 广义中国剩余定理, 允许模数不互质 (此时要求方程组相容)
+///
+/// 返回 (x, M), 满足 0 <= x < M = lcm(m_i)
+pub fn crt(pairs: &[(BigUint, BigUint)]) -> Result<(BigUint, BigUint), String> {
+    let mut x = BigInt::zero();
+    let mut modulus = BigInt::one();
+
+    for (idx, (r, m)) in pairs.iter().enumerate() {
+        let r = BigInt::from_biguint(Sign::Plus, r.clone());
+        let m = BigInt::from_biguint(Sign::Plus, m.clone());
+
+        let g = modulus.gcd(&m);
+        let diff = &r - &x;
+        if !(&diff % &g).is_zero() {
+            return Err(format!("第 {} 组同余方程与前面的方程矛盾", idx + 1));
+        }
+
+        // x + modulus·t ≡ r (mod m)  =>  t ≡ diff/g · (modulus/g)^-1 (mod m/g)
+        let m_g = &m / &g;
+        let inv = if m_g.is_one() {
+            BigInt::zero()
+        } else {
+            (&modulus / &g)
+                .mod_floor(&m_g)
+                .modinv(&m_g)
+                .ok_or("模逆不存在")?
+        };
+        let t = ((&diff / &g) * inv).mod_floor(&m_g);
+        x += &modulus * t;
+        modulus *= &m_g;
+        x = x.mod_floor(&modulus);
+    }
+
+    Ok((x.to_biguint().unwrap(), modulus.to_biguint().unwrap()))
+}
+
+/// 精确整数 k 次方根, 不是完全 k 次幂时返回 None
+pub fn exact_nth_root(value: &BigUint, k: u32) -> Option<BigUint> {
+    let root = value.nth_root(k);
+    if root.pow(k) == *value {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+// Miller-Rabin 使用的固定底数 (前 16 个素数, 对 < 3.3·10^24 的数为确定性判定)
+const MR_BASES: [u32; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+/// Miller-Rabin 素性测试
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return false;
+    }
+    for &p in MR_BASES.iter() {
+        let p = BigUint::from(p);
+        if *n == p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
+    }
+
+    let n_1 = n - 1u32;
+    let s = n_1.trailing_zeros().unwrap_or(0);
+    let d = &n_1 >> s;
+
+    'witness: for &a in MR_BASES.iter() {
+        let mut x = BigUint::from(a).modpow(&d, n);
+        if x.is_one() || x == n_1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// 埃氏筛, 返回不超过 limit 的全部素数
+pub fn small_primes(limit: u64) -> Vec<u64> {
+    if limit < 2 {
+        return Vec::new();
+    }
+    let limit = limit as usize;
+    let mut sieve = vec![true; limit + 1];
+    sieve[0] = false;
+    sieve[1] = false;
+    let mut i = 2;
+    while i * i <= limit {
+        if sieve[i] {
+            for j in (i * i..=limit).step_by(i) {
+                sieve[j] = false;
+            }
+        }
+        i += 1;
+    }
+    sieve
+        .iter()
+        .enumerate()
+        .filter(|(_, &is_p)| is_p)
+        .map(|(i, _)| i as u64)
+        .collect()
+}
+
+/// [0, upper) 内的均匀随机数
+pub fn random_below(upper: &BigUint) -> BigUint {
+    let bits = upper.bits();
+    let len = bits.div_ceil(8) as usize;
+    loop {
+        let mut bytes = vec![0u8; len];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let v = BigUint::from_bytes_be(&bytes) >> (len as u64 * 8 - bits);
+        if &v < upper {
+            return v;
+        }
+    }
+}
+
+/// 恰为 bits 位的随机素数, 且满足 accept (在小素数试除之后、Miller-Rabin 之前调用, 应当廉价)
+pub fn random_prime(bits: u64, accept: impl Fn(&BigUint) -> bool) -> BigUint {
+    let small = small_primes(2000);
+    let top = BigUint::one() << (bits - 1);
+    loop {
+        let v = random_below(&top) | &top | BigUint::one();
+        let divisible = small
+            .iter()
+            .any(|&p| (&v % p).is_zero() && v != BigUint::from(p));
+        if !divisible && accept(&v) && is_probable_prime(&v) {
+            return v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crt_coprime() {
+        // x ≡ 2 (mod 3), x ≡ 3 (mod 5), x ≡ 2 (mod 7) => x = 23
+        let res = chinese_remainder(
+            vec!["2".into(), "3".into(), "2".into()],
+            vec!["3".into(), "5".into(), "7".into()],
+        )
+        .unwrap();
+        assert_eq!(res.x, "23");
+        assert_eq!(res.modulus, "105");
+    }
+
+    #[test]
+    fn test_crt_non_coprime() {
+        // x ≡ 3 (mod 4), x ≡ 5 (mod 6) => x = 11 (mod 12)
+        let res =
+            chinese_remainder(vec!["3".into(), "5".into()], vec!["4".into(), "6".into()]).unwrap();
+        assert_eq!(res.x, "11");
+        assert_eq!(res.modulus, "12");
+
+        // x ≡ 1 (mod 4), x ≡ 2 (mod 6) 无解
+        let err = chinese_remainder(vec!["1".into(), "2".into()], vec!["4".into(), "6".into()]);
+        assert!(err.unwrap_err().contains("矛盾"));
+    }
+
+    #[test]
+    fn test_integer_root() {
+        let big = BigUint::from(123456789u64).pow(5);
+        let res = integer_root(big.to_string(), 5).unwrap();
+        assert_eq!(res.root, "123456789");
+        assert!(res.exact);
+
+        let res = integer_root((big + 1u32).to_string(), 5).unwrap();
+        assert_eq!(res.root, "123456789");
+        assert!(!res.exact);
+    }
+
+    #[test]
+    fn test_primality() {
+        assert!(is_probable_prime(&BigUint::from(2u32)));
+        assert!(is_probable_prime(&BigUint::from(1_000_000_007u64)));
+        assert!(!is_probable_prime(&BigUint::from(561u32))); // Carmichael 数
+        let m127 = (BigUint::one() << 127u32) - 1u32;
+        assert!(is_probable_prime(&m127));
+        assert!(!is_probable_prime(&(&m127 * &m127)));
+        assert_eq!(small_primes(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+}
@@ -0,0 +1,255 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// 交互式 oracle 的连接方式, 两种方式都按行交互: 每次查询写入一行, 读回一行非空响应
+///
+/// - `exec`: 启动本地程序或脚本 (program + args), 通过 stdin/stdout 通信, 整个攻击期间保持运行
+/// - `tcp`: 连接 address (host:port, 只写端口时为 127.0.0.1)
+#[derive(Deserialize, Default, Clone)]
+pub struct OracleTarget {
+    pub mode: String,
+    pub program: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub address: Option<String>,
+    pub banner_lines: Option<usize>, // 建立连接后先丢弃的行数 (欢迎信息、公钥等)
+    pub timeout_ms: Option<u64>,     // 读取响应的超时 (TCP 同时用于写入)
+}
+
+/// 响应来源: TCP 直接按行读取 (由 socket 超时兜底); 子进程的 stdout 没有超时,
+/// 由后台线程逐行转发, 读取时用 recv_timeout 等待
+enum Replies {
+    Stream(Box<dyn BufRead + Send>),
+    Lines(Receiver<std::io::Result<String>>),
+}
+
+/// 已建立的 oracle 会话, 析构时结束子进程
+pub struct OracleConn {
+    replies: Replies,
+    writer: Box<dyn Write + Send>,
+    child: Option<Child>,
+    timeout: Duration,
+    pub queries: u64,
+}
+
+impl OracleConn {
+    pub fn open(target: &OracleTarget) -> Result<Self, String> {
+        let timeout = Duration::from_millis(target.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+        let mut conn = match target.mode.as_str() {
+            "exec" => {
+                let program = target
+                    .program
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .ok_or("缺少 oracle 程序路径")?;
+                let mut child = Command::new(program)
+                    .args(target.args.iter().flatten())
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .map_err(|e| format!("无法启动 oracle 程序 {}: {}", program, e))?;
+                let stdin = child.stdin.take().ok_or("无法打开 oracle 的 stdin")?;
+                let stdout = child.stdout.take().ok_or("无法打开 oracle 的 stdout")?;
+                // 子进程结束 (包括析构时被 kill) 后 stdout 关闭, 转发线程随之退出
+                let (tx, rx) = mpsc::channel();
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stdout);
+                    loop {
+                        let mut line = String::new();
+                        match reader.read_line(&mut line) {
+                            Ok(0) => break,
+                            Ok(_) => {
+                                if tx.send(Ok(line)).is_err() {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                let _ = tx.send(Err(e));
+                                break;
+                            }
+                        }
+                    }
+                });
+                OracleConn {
+                    replies: Replies::Lines(rx),
+                    writer: Box::new(stdin),
+                    child: Some(child),
+                    timeout,
+                    queries: 0,
+                }
+            }
+            "tcp" => {
+                let address = target
+                    .address
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .ok_or("缺少 oracle 地址")?;
+                let address = match address.parse::<u16>() {
+                    Ok(port) => format!("127.0.0.1:{}", port),
+                    Err(_) => address.to_string(),
+                };
+                let stream = TcpStream::connect(&address)
+                    .map_err(|e| format!("无法连接 oracle {}: {}", address, e))?;
+                stream
+                    .set_read_timeout(Some(timeout))
+                    .and_then(|_| stream.set_write_timeout(Some(timeout)))
+                    .and_then(|_| stream.set_nodelay(true))
+                    .map_err(|e| format!("设置连接参数失败: {}", e))?;
+                let reader = stream
+                    .try_clone()
+                    .map_err(|e| format!("设置连接参数失败: {}", e))?;
+                OracleConn {
+                    replies: Replies::Stream(Box::new(BufReader::new(reader))),
+                    writer: Box::new(stream),
+                    child: None,
+                    timeout,
+                    queries: 0,
+                }
+            }
+            other => return Err(format!("未知的 oracle 连接方式: {}", other)),
+        };
+        for _ in 0..target.banner_lines.unwrap_or(0) {
+            conn.read_line()?;
+        }
+        Ok(conn)
+    }
+
+    /// 发送一行并读回一行非空响应 (已去掉首尾空白)
+    pub fn query(&mut self, line: &str) -> Result<String, String> {
+        self.writer
+            .write_all(format!("{}\n", line).as_bytes())
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| format!("向 oracle 写入失败: {}", e))?;
+        self.queries += 1;
+        loop {
+            let resp = self.read_line()?;
+            if !resp.is_empty() {
+                return Ok(resp);
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String, String> {
+        let line = match &mut self.replies {
+            Replies::Stream(reader) => {
+                let mut line = String::new();
+                let n = reader
+                    .read_line(&mut line)
+                    .map_err(|e| format!("读取 oracle 响应失败: {}", e))?;
+                if n == 0 {
+                    return Err("oracle 已关闭连接".into());
+                }
+                line
+            }
+            Replies::Lines(rx) => match rx.recv_timeout(self.timeout) {
+                Ok(line) => line.map_err(|e| format!("读取 oracle 响应失败: {}", e))?,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!(
+                        "oracle 在 {} ms 内没有响应",
+                        self.timeout.as_millis()
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err("oracle 已关闭连接".into()),
+            },
+        };
+        Ok(line.trim().to_string())
+    }
+}
+
+impl Drop for OracleConn {
+    fn drop(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// 把 oracle 响应解析为一位: 给出 marker 时以是否包含它为准,
+/// 否则取最后一个词识别 1/0、true/false、yes/no、odd/even、valid/invalid
+pub fn reply_bit(resp: &str, marker: Option<&str>) -> Result<bool, String> {
+    if let Some(marker) = marker {
+        return Ok(resp.contains(marker));
+    }
+    let word = resp
+        .rsplit(|c: char| c.is_whitespace() || matches!(c, ':' | '=' | '>'))
+        .find(|s| !s.is_empty())
+        .unwrap_or("")
+        .trim_matches(|c: char| c.is_ascii_punctuation())
+        .to_ascii_lowercase();
+    match word.as_str() {
+        "1" | "true" | "yes" | "odd" | "valid" | "ok" => Ok(true),
+        "0" | "false" | "no" | "even" | "invalid" | "error" => Ok(false),
+        _ => Err(format!("无法识别的 oracle 响应: {}", resp)),
+    }
+}
+
+/// 测试用: 以 exec 模式运行随附的替身 oracle 脚本, 本机没有 python3 时返回 None 以跳过测试
+#[cfg(test)]
+pub(crate) fn parity_script_target(args: &[String]) -> Option<OracleTarget> {
+    let found = Command::new("python3")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !found {
+        eprintln!("未找到 python3, 跳过替身 oracle 测试");
+        return None;
+    }
+    let script = std::path::Path::new(file!()).with_file_name("data/parity_oracle.py");
+    let mut full = vec![script.to_string_lossy().into_owned()];
+    full.extend_from_slice(args);
+    Some(OracleTarget {
+        mode: "exec".into(),
+        program: Some("python3".into()),
+        args: Some(full),
+        banner_lines: Some(1),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reply_bit() {
+        assert_eq!(reply_bit("parity: odd", None), Ok(true));
+        assert_eq!(reply_bit("> no", None), Ok(false));
+        assert_eq!(reply_bit("padding is invalid!", None), Ok(false));
+        assert_eq!(reply_bit("Decrypted OK", Some("OK")), Ok(true));
+        assert!(reply_bit("what?", None).is_err());
+        assert!(OracleConn::open(&OracleTarget {
+            mode: "exec".into(),
+            program: Some("/nonexistent/oracle".into()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_exec_read_timeout() {
+        // 替身 oracle 收到查询后不回答, 读取应在超时后返回错误而不是一直阻塞
+        let args = ["15", "7", "hang"].map(String::from);
+        let Some(mut target) = parity_script_target(&args) else {
+            return;
+        };
+        target.timeout_ms = Some(200);
+        let mut conn = OracleConn::open(&target).unwrap();
+        let start = std::time::Instant::now();
+        let err = conn.query("4").unwrap_err();
+        assert!(err.contains("没有响应"), "{}", err);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
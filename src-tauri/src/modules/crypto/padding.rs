@@ -0,0 +1,60 @@
+// ===================== PKCS#7 =====================
+
+pub fn pkcs7_pad(data: &[u8], bs: usize) -> Vec<u8> {
+    let pad = bs - data.len() % bs;
+    let mut out = data.to_vec();
+    out.resize(data.len() + pad, pad as u8);
+    out
+}
+
+/// 校验并去掉 PKCS#7 填充, 填充不合法时给出原因
+pub fn unpad_pkcs7(data: &[u8], bs: usize) -> Result<&[u8], String> {
+    let pad = *data.last().ok_or("数据为空")? as usize;
+    if pad == 0 || pad > bs || pad > data.len() {
+        return Err(format!(
+            "PKCS#7 填充无效: 末字节 0x{:02x} 超出 1..={}",
+            pad,
+            bs.min(data.len())
+        ));
+    }
+    let (body, tail) = data.split_at(data.len() - pad);
+    match tail.iter().rposition(|&b| b as usize != pad) {
+        Some(i) => Err(format!(
+            "PKCS#7 填充无效: 倒数第 {} 字节为 0x{:02x}, 应为 0x{:02x}",
+            pad - i,
+            tail[i],
+            pad
+        )),
+        None => Ok(body),
+    }
+}
+
+/// 去掉合法的 PKCS#7 填充, 不合法时原样返回
+pub fn strip_pkcs7(data: &[u8], bs: usize) -> &[u8] {
+    unpad_pkcs7(data, bs).unwrap_or(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkcs7() {
+        assert_eq!(
+            pkcs7_pad(b"YELLOW SUBMARINE", 20),
+            b"YELLOW SUBMARINE\x04\x04\x04\x04"
+        );
+        assert_eq!(pkcs7_pad(b"", 8), [8; 8]);
+        assert_eq!(
+            unpad_pkcs7(b"ICE ICE BABY\x04\x04\x04\x04", 16),
+            Ok(&b"ICE ICE BABY"[..])
+        );
+
+        let err = unpad_pkcs7(b"ICE ICE BABY\x01\x02\x03\x04", 16).unwrap_err();
+        assert!(err.contains("倒数第 2 字节为 0x03"));
+        assert!(unpad_pkcs7(b"ICE ICE BABY\x05\x05\x05\x05", 16).is_err());
+        assert!(unpad_pkcs7(b"\x00", 16).is_err());
+        // 填充长度超过数据长度时不能越界
+        assert_eq!(strip_pkcs7(b"\x03\x03", 16), b"\x03\x03");
+    }
+}
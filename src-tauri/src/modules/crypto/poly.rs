@@ -0,0 +1,198 @@
+// Z_n 上的一元多项式运算, 系数按升幂排列并规约到 [0, n)
+//
+// n 通常是未分解的 RSA 模数, 求逆可能失败; 此时返回 Err(g), g 为不可逆元素与 n 的非平凡公因子。
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// Err 中为遇到的不可逆元素与 n 的公因子 (除数为零多项式时为 n 本身)
+pub type PolyResult<T> = Result<T, BigInt>;
+
+/// 去掉高次的零系数, 零多项式表示为空向量
+pub fn poly_trim(mut p: Vec<BigInt>) -> Vec<BigInt> {
+    while p.last().is_some_and(|c| c.is_zero()) {
+        p.pop();
+    }
+    p
+}
+
+/// 次数, 零多项式返回 None
+pub fn poly_degree(p: &[BigInt]) -> Option<usize> {
+    p.iter().rposition(|c| !c.is_zero())
+}
+
+pub fn poly_mul_mod(a: &[BigInt], b: &[BigInt], n: &BigInt) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![BigInt::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        if x.is_zero() {
+            continue;
+        }
+        for (j, y) in b.iter().enumerate() {
+            out[i + j] += x * y;
+        }
+    }
+    poly_trim(out.into_iter().map(|c| c.mod_floor(n)).collect())
+}
+
+/// (a·x + b)^e 的展开式
+pub fn affine_pow(a: &BigInt, b: &BigInt, e: u32, n: &BigInt) -> Vec<BigInt> {
+    let mut binom = BigInt::one();
+    let out = (0..=e)
+        .map(|i| {
+            let c = &binom * a.modpow(&BigInt::from(i), n) * b.modpow(&BigInt::from(e - i), n);
+            binom = &binom * (e - i) / (i + 1);
+            c.mod_floor(n)
+        })
+        .collect();
+    poly_trim(out)
+}
+
+/// 模 n 求逆, 不可逆时返回 gcd(a, n)
+fn inverse(a: &BigInt, n: &BigInt) -> PolyResult<BigInt> {
+    a.modinv(n).ok_or_else(|| a.gcd(n))
+}
+
+/// 首一化
+pub fn poly_monic(p: &[BigInt], n: &BigInt) -> PolyResult<Vec<BigInt>> {
+    let p = poly_trim(p.to_vec());
+    let Some(lc) = p.last() else {
+        return Ok(p);
+    };
+    let inv = inverse(lc, n)?;
+    Ok(p.iter().map(|c| (c * &inv).mod_floor(n)).collect())
+}
+
+/// a mod b
+pub fn poly_rem_mod(a: &[BigInt], b: &[BigInt], n: &BigInt) -> PolyResult<Vec<BigInt>> {
+    let b = poly_trim(b.to_vec());
+    let db = poly_degree(&b).ok_or_else(|| n.clone())?;
+    let inv = inverse(&b[db], n)?;
+    let mut r = poly_trim(a.iter().map(|c| c.mod_floor(n)).collect());
+    while let Some(dr) = poly_degree(&r).filter(|&d| d >= db) {
+        let q = (&r[dr] * &inv).mod_floor(n);
+        let shift = dr - db;
+        for (i, c) in b.iter().enumerate() {
+            r[shift + i] = (&r[shift + i] - &q * c).mod_floor(n);
+        }
+        r = poly_trim(r);
+    }
+    Ok(r)
+}
+
+/// 首一的最大公因式 (欧几里得算法)
+pub fn poly_gcd_mod(a: &[BigInt], b: &[BigInt], n: &BigInt) -> PolyResult<Vec<BigInt>> {
+    let mut a = poly_trim(a.iter().map(|c| c.mod_floor(n)).collect());
+    let mut b = poly_trim(b.iter().map(|c| c.mod_floor(n)).collect());
+    while !b.is_empty() {
+        let r = poly_rem_mod(&a, &b, n)?;
+        a = b;
+        b = r;
+    }
+    poly_monic(&a, n)
+}
+
+/// 结式 Res(f, g), 利用 Res(f, g) = (-1)^(deg f·deg g)·lc(g)^(deg f - deg r)·Res(g, r), r = f mod g
+pub fn resultant_mod(f: &[BigInt], g: &[BigInt], n: &BigInt) -> PolyResult<BigInt> {
+    let mut f = poly_trim(f.iter().map(|c| c.mod_floor(n)).collect());
+    let mut g = poly_trim(g.iter().map(|c| c.mod_floor(n)).collect());
+    let mut acc = BigInt::one();
+    loop {
+        let (Some(df), Some(dg)) = (poly_degree(&f), poly_degree(&g)) else {
+            return Ok(BigInt::zero());
+        };
+        if dg == 0 {
+            return Ok((acc * g[0].modpow(&BigInt::from(df), n)).mod_floor(n));
+        }
+        let r = poly_rem_mod(&f, &g, n)?;
+        let Some(dr) = poly_degree(&r) else {
+            return Ok(BigInt::zero());
+        };
+        if (df * dg) % 2 == 1 {
+            acc = -acc;
+        }
+        acc = (acc * g[dg].modpow(&BigInt::from(df - dr), n)).mod_floor(n);
+        f = g;
+        g = r;
+    }
+}
+
+/// 拉格朗日插值: 求次数 < xs.len() 的多项式 p 使 p(xs[i]) = ys[i]
+pub fn interpolate_mod(xs: &[BigInt], ys: &[BigInt], n: &BigInt) -> PolyResult<Vec<BigInt>> {
+    let mut out = vec![BigInt::zero(); xs.len()];
+    for (i, (xi, yi)) in xs.iter().zip(ys).enumerate() {
+        // l_i(x) = Π_(j≠i) (x - x_j) / (x_i - x_j)
+        let mut basis = vec![BigInt::one()];
+        let mut denom = BigInt::one();
+        for (j, xj) in xs.iter().enumerate() {
+            if i != j {
+                basis = poly_mul_mod(&basis, &[(-xj).mod_floor(n), BigInt::one()], n);
+                denom = (denom * (xi - xj)).mod_floor(n);
+            }
+        }
+        let scale = (yi * inverse(&denom, n)?).mod_floor(n);
+        for (k, c) in basis.iter().enumerate() {
+            out[k] = (&out[k] + c * &scale).mod_floor(n);
+        }
+    }
+    Ok(poly_trim(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(v: &[i64]) -> Vec<BigInt> {
+        v.iter().map(|&x| BigInt::from(x)).collect()
+    }
+
+    #[test]
+    fn test_gcd_and_rem() {
+        let n = BigInt::from(1_000_000_007u64);
+        // (x - 3)(x + 5) 与 (x - 3)(x - 7) 的公因式为 x - 3
+        let a = poly_mul_mod(&p(&[-3, 1]), &p(&[5, 1]), &n);
+        let b = poly_mul_mod(&p(&[-3, 1]), &p(&[-7, 1]), &n);
+        assert_eq!(poly_gcd_mod(&a, &b, &n).unwrap(), p(&[1_000_000_004, 1]));
+        assert_eq!(
+            poly_rem_mod(&a, &p(&[-3, 1]), &n).unwrap(),
+            Vec::<BigInt>::new()
+        );
+
+        // 首项系数与 n 不互质时暴露 n 的因子
+        let n = BigInt::from(91);
+        assert_eq!(
+            poly_rem_mod(&p(&[1, 0, 1]), &p(&[1, 7]), &n),
+            Err(BigInt::from(7))
+        );
+    }
+
+    #[test]
+    fn test_resultant_and_interpolation() {
+        let n = BigInt::from(1_000_000_007u64);
+        // Res(x^2 - 2, x - 3) = 3^2 - 2 = 7
+        assert_eq!(
+            resultant_mod(&p(&[-2, 0, 1]), &p(&[-3, 1]), &n).unwrap(),
+            BigInt::from(7)
+        );
+        // Res(x^2 - 1, x^2 - 4) = g(1)·g(-1) = 9
+        assert_eq!(
+            resultant_mod(&p(&[-1, 0, 1]), &p(&[-4, 0, 1]), &n).unwrap(),
+            BigInt::from(9)
+        );
+        assert_eq!(
+            affine_pow(&BigInt::from(2), &BigInt::from(1), 2, &n),
+            p(&[1, 4, 4])
+        );
+
+        let xs = p(&[0, 1, 2, 3]);
+        let f = p(&[5, 0, 2, 1]);
+        let ys: Vec<BigInt> = xs
+            .iter()
+            .map(|x| f.iter().rev().fold(BigInt::zero(), |acc, c| acc * x + c))
+            .collect();
+        assert_eq!(interpolate_mod(&xs, &ys, &n).unwrap(), f);
+    }
+}
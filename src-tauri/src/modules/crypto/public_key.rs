@@ -0,0 +1,439 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use serde::Serialize;
+
+use super::codec::{parse_bigint, parse_biguint, render_number, NumberView};
+use super::dlog;
+use super::number_theory::{is_probable_prime, random_below, random_prime, small_primes};
+
+const DEFAULT_MODULUS_BITS: u64 = 1024; // Paillier / Rabin 的 n
+const DEFAULT_ELGAMAL_BITS: u64 = 512; // ElGamal 的安全素数 p
+const MIN_KEY_BITS: u64 = 32;
+const MAX_KEY_BITS: u64 = 4096;
+
+#[derive(Serialize, Debug)]
+pub struct PaillierKey {
+    pub n: String,
+    pub g: String, // 取 n + 1
+    pub p: String,
+    pub q: String,
+    pub lambda: String, // lcm(p - 1, q - 1)
+    pub mu: String,     // L(g^λ mod n²)^-1 mod n
+}
+
+#[derive(Serialize, Debug)]
+pub struct ElGamalKey {
+    pub p: String, // 安全素数 p = 2q + 1
+    pub g: String, // Z_p* 的生成元
+    pub x: String, // 私钥
+    pub y: String, // 公钥 g^x mod p
+}
+
+#[derive(Serialize, Debug)]
+pub struct ElGamalCiphertext {
+    pub c1: String, // g^k
+    pub c2: String, // m·y^k
+}
+
+#[derive(Serialize, Debug)]
+pub struct RabinKey {
+    pub n: String,
+    pub p: String, // p ≡ q ≡ 3 (mod 4)
+    pub q: String,
+}
+
+// ===================== Paillier =====================
+
+#[tauri::command]
+pub fn paillier_generate_key(bits: Option<u64>) -> Result<PaillierKey, String> {
+    let bits = key_bits(bits, DEFAULT_MODULUS_BITS)?;
+    let (p, q) = prime_pair(bits, |_| true);
+    let n = &p * &q;
+    let g = &n + 1u32;
+    let lambda = (&p - 1u32).lcm(&(&q - 1u32));
+    let mu = paillier_mu(&n, &g, &lambda)?;
+    Ok(PaillierKey {
+        n: n.to_string(),
+        g: g.to_string(),
+        p: p.to_string(),
+        q: q.to_string(),
+        lambda: lambda.to_string(),
+        mu: mu.to_string(),
+    })
+}
+
+/// c = g^m · r^n mod n², g 缺省为 n + 1, r 缺省随机
+#[tauri::command]
+pub fn paillier_encrypt(
+    n: String,
+    g: Option<String>,
+    m: String,
+    r: Option<String>,
+) -> Result<String, String> {
+    let n = parse_modulus(&n, "n")?;
+    let n2 = &n * &n;
+    let g = parse_optional(&g, "g")?.unwrap_or_else(|| &n + 1u32);
+    let m = parse_number(&m, "m")?;
+    if m >= n {
+        return Err("明文必须小于 n".into());
+    }
+    let r = match parse_optional(&r, "r")? {
+        Some(r) => r,
+        None => random_unit(&n),
+    };
+    if !r.gcd(&n).is_one() {
+        return Err("r 必须与 n 互素".into());
+    }
+    let c = g.modpow(&m, &n2) * r.modpow(&n, &n2) % &n2;
+    Ok(c.to_string())
+}
+
+/// m = L(c^λ mod n²)·μ mod n, 其中 L(x) = (x - 1) / n; 给出 p、q 或 λ 之一即可
+#[tauri::command]
+pub fn paillier_decrypt(
+    n: Option<String>,
+    g: Option<String>,
+    p: Option<String>,
+    q: Option<String>,
+    lambda: Option<String>,
+    c: String,
+) -> Result<NumberView, String> {
+    let p = parse_optional(&p, "p")?;
+    let q = parse_optional(&q, "q")?;
+    let n = match (parse_optional(&n, "n")?, &p, &q) {
+        (Some(n), _, _) => n,
+        (None, Some(p), Some(q)) => p * q,
+        _ => return Err("缺少 n (或 p、q)".into()),
+    };
+    if n <= BigUint::from(2u32) {
+        return Err("n 必须大于 2".into());
+    }
+    for (f, name) in [(&p, "p"), (&q, "q")] {
+        if f.as_ref().is_some_and(|f| *f <= BigUint::one()) {
+            return Err(format!("{} 必须大于 1", name));
+        }
+    }
+    if let (Some(p), Some(q)) = (&p, &q) {
+        if p * q != n {
+            return Err("p·q 与 n 不一致".into());
+        }
+    }
+    let lambda = match (parse_optional(&lambda, "λ")?, p, q) {
+        (Some(lambda), _, _) => lambda,
+        (None, Some(p), Some(q)) => (p - 1u32).lcm(&(q - 1u32)),
+        _ => return Err("需要 p、q 或 λ".into()),
+    };
+    let g = parse_optional(&g, "g")?.unwrap_or_else(|| &n + 1u32);
+    let c = parse_number(&c, "c")?;
+    let n2 = &n * &n;
+    if c.is_zero() || c >= n2 {
+        return Err("密文必须在 (0, n²) 内".into());
+    }
+    if !c.gcd(&n).is_one() {
+        return Err("密文必须与 n 互素".into());
+    }
+    let mu = paillier_mu(&n, &g, &lambda)?;
+    let m = paillier_l(&c.modpow(&lambda, &n2), &n) * mu % &n;
+    Ok(render_number(&m))
+}
+
+/// 同态加法: 密文相乘 (mod n²) 对应明文相加 (mod n)
+#[tauri::command]
+pub fn paillier_add(n: String, ciphertexts: Vec<String>) -> Result<String, String> {
+    let n = parse_modulus(&n, "n")?;
+    let n2 = &n * &n;
+    if ciphertexts.is_empty() {
+        return Err("至少需要一个密文".into());
+    }
+    let mut acc = BigUint::one();
+    for (i, c) in ciphertexts.iter().enumerate() {
+        let c = parse_biguint(c).map_err(|e| format!("第 {} 个密文: {}", i + 1, e))?;
+        acc = acc * c % &n2;
+    }
+    Ok(acc.to_string())
+}
+
+/// 同态数乘: c^k (mod n²) 对应明文乘以 k, k 可为负数
+#[tauri::command]
+pub fn paillier_scalar_mul(n: String, c: String, k: String) -> Result<String, String> {
+    let n = parse_modulus(&n, "n")?;
+    let n2 = &n * &n;
+    let c = parse_number(&c, "c")?;
+    let k = parse_bigint(&k).map_err(|e| format!("k 格式错误: {}", e))?;
+    let base = if k.is_negative() {
+        c.modinv(&n2).ok_or("密文与 n² 不互素, 无法取逆")?
+    } else {
+        c
+    };
+    Ok(base.modpow(k.magnitude(), &n2).to_string())
+}
+
+fn paillier_l(x: &BigUint, n: &BigUint) -> BigUint {
+    (x - 1u32) / n
+}
+
+fn paillier_mu(n: &BigUint, g: &BigUint, lambda: &BigUint) -> Result<BigUint, String> {
+    let x = g.modpow(lambda, &(n * n));
+    if x.is_zero() {
+        return Err("g 必须与 n 互素".into());
+    }
+    paillier_l(&x, n)
+        .modinv(n)
+        .ok_or_else(|| "L(g^λ mod n²) 在模 n 下不可逆, 请检查 g 与 λ".into())
+}
+
+// ===================== ElGamal =====================
+
+/// 生成安全素数 p = 2q + 1 上的 ElGamal 密钥, g 取 Z_p* 的最小生成元
+#[tauri::command]
+pub fn elgamal_generate_key(bits: Option<u64>) -> Result<ElGamalKey, String> {
+    let bits = key_bits(bits, DEFAULT_ELGAMAL_BITS)?;
+    let p = safe_prime(bits);
+    let q: BigUint = (&p - 1u32) >> 1;
+    let mut g = BigUint::from(2u32);
+    while g.modpow(&BigUint::from(2u32), &p).is_one() || g.modpow(&q, &p).is_one() {
+        g += 1u32;
+    }
+    let x = random_below(&(&p - 3u32)) + 2u32;
+    let y = g.modpow(&x, &p);
+    Ok(ElGamalKey {
+        p: p.to_string(),
+        g: g.to_string(),
+        x: x.to_string(),
+        y: y.to_string(),
+    })
+}
+
+/// c1 = g^k, c2 = m·y^k (mod p), k 缺省随机
+#[tauri::command]
+pub fn elgamal_encrypt(
+    p: String,
+    g: String,
+    y: String,
+    m: String,
+    k: Option<String>,
+) -> Result<ElGamalCiphertext, String> {
+    let p = parse_modulus(&p, "p")?;
+    let g = parse_number(&g, "g")?;
+    let y = parse_number(&y, "y")?;
+    let m = parse_number(&m, "m")?;
+    if m.is_zero() || m >= p {
+        return Err("明文必须在 [1, p) 内".into());
+    }
+    let k = match parse_optional(&k, "k")? {
+        Some(k) => k,
+        None => random_below(&(&p - 2u32)) + 1u32,
+    };
+    Ok(ElGamalCiphertext {
+        c1: g.modpow(&k, &p).to_string(),
+        c2: (m * y.modpow(&k, &p) % &p).to_string(),
+    })
+}
+
+/// m = c2·(c1^x)^-1 (mod p)
+#[tauri::command]
+pub fn elgamal_decrypt(p: String, x: String, c1: String, c2: String) -> Result<NumberView, String> {
+    let p = parse_modulus(&p, "p")?;
+    let x = parse_number(&x, "x")?;
+    let c1 = parse_number(&c1, "c1")?;
+    let c2 = parse_number(&c2, "c2")?;
+    dlog::elgamal_decrypt(&p, &x, &c1, &c2).map(|m| render_number(&m))
+}
+
+// ===================== Rabin =====================
+
+/// 生成 p ≡ q ≡ 3 (mod 4) 的 Rabin 密钥, 解密见 rabin_decrypt
+#[tauri::command]
+pub fn rabin_generate_key(bits: Option<u64>) -> Result<RabinKey, String> {
+    let bits = key_bits(bits, DEFAULT_MODULUS_BITS)?;
+    let (p, q) = prime_pair(bits, |p| p.bit(1));
+    Ok(RabinKey {
+        n: (&p * &q).to_string(),
+        p: p.to_string(),
+        q: q.to_string(),
+    })
+}
+
+/// c = m² mod n
+#[tauri::command]
+pub fn rabin_encrypt(n: String, m: String) -> Result<String, String> {
+    let n = parse_modulus(&n, "n")?;
+    let m = parse_number(&m, "m")?;
+    if m >= n {
+        return Err("明文必须小于 n".into());
+    }
+    Ok(m.modpow(&BigUint::from(2u32), &n).to_string())
+}
+
+// ===================== 辅助函数 =====================
+
+fn parse_number(s: &str, name: &str) -> Result<BigUint, String> {
+    parse_biguint(s).map_err(|e| format!("{} 格式错误: {}", name, e))
+}
+
+fn parse_modulus(s: &str, name: &str) -> Result<BigUint, String> {
+    let v = parse_number(s, name)?;
+    if v <= BigUint::from(2u32) {
+        return Err(format!("{} 必须大于 2", name));
+    }
+    Ok(v)
+}
+
+fn parse_optional(s: &Option<String>, name: &str) -> Result<Option<BigUint>, String> {
+    match s.as_deref().map(str::trim) {
+        Some(t) if !t.is_empty() => parse_number(t, name).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn key_bits(bits: Option<u64>, default: u64) -> Result<u64, String> {
+    let bits = bits.unwrap_or(default);
+    if !(MIN_KEY_BITS..=MAX_KEY_BITS).contains(&bits) {
+        return Err(format!(
+            "密钥位数应在 {}..={} 之间",
+            MIN_KEY_BITS, MAX_KEY_BITS
+        ));
+    }
+    Ok(bits)
+}
+
+/// 两个不同的 bits/2 位素数, 乘积恰为 bits 位
+fn prime_pair(bits: u64, accept: impl Fn(&BigUint) -> bool + Copy) -> (BigUint, BigUint) {
+    loop {
+        let p = random_prime(bits / 2, accept);
+        let q = random_prime(bits - bits / 2, accept);
+        if p != q && (&p * &q).bits() == bits {
+            return (p, q);
+        }
+    }
+}
+
+/// p = 2q + 1, q 为素数; 试除时同时排除 2q + 1 含小素因子的候选
+fn safe_prime(bits: u64) -> BigUint {
+    let small = small_primes(2000);
+    loop {
+        let q = random_prime(bits - 1, |q| {
+            small.iter().skip(1).all(|&s| {
+                let r = (q % s).to_u64().unwrap_or(0);
+                r != (s - 1) / 2
+            })
+        });
+        let p: BigUint = (q << 1) + 1u32;
+        if is_probable_prime(&p) {
+            return p;
+        }
+    }
+}
+
+/// [1, n) 内与 n 互素的随机数
+fn random_unit(n: &BigUint) -> BigUint {
+    loop {
+        let r = random_below(n);
+        if !r.is_zero() && r.gcd(n).is_one() {
+            return r;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::crypto::modular_root::rabin_decrypt;
+
+    #[test]
+    fn test_paillier_homomorphic() {
+        let key = paillier_generate_key(Some(256)).unwrap();
+        let c1 = paillier_encrypt(key.n.clone(), None, "1234".into(), None).unwrap();
+        let c2 = paillier_encrypt(
+            key.n.clone(),
+            Some(key.g.clone()),
+            "766".into(),
+            Some("17".into()),
+        )
+        .unwrap();
+        let sum = paillier_add(key.n.clone(), vec![c1.clone(), c2]).unwrap();
+        let scaled = paillier_scalar_mul(key.n.clone(), sum, "3".into()).unwrap();
+        let m = paillier_decrypt(
+            None,
+            None,
+            Some(key.p.clone()),
+            Some(key.q.clone()),
+            None,
+            scaled,
+        )
+        .unwrap();
+        assert_eq!(m.decimal, "6000");
+
+        // 负数乘子: 明文变为 n - 1234, 只给 λ 也能解密
+        let neg = paillier_scalar_mul(key.n.clone(), c1, "-1".into()).unwrap();
+        let m =
+            paillier_decrypt(Some(key.n.clone()), None, None, None, Some(key.lambda), neg).unwrap();
+        let n: BigUint = key.n.parse().unwrap();
+        assert_eq!(m.decimal, (n - 1234u32).to_string());
+    }
+
+    #[test]
+    fn test_paillier_rejects_non_unit_ciphertext() {
+        // c 与 n 不互素时 c^λ 可能为 0 (mod n²), 不能进入 L 函数
+        for c in ["3233", "61", "53"] {
+            let res = paillier_decrypt(
+                Some("3233".into()),
+                None,
+                Some("61".into()),
+                Some("53".into()),
+                None,
+                c.into(),
+            );
+            assert!(res.unwrap_err().contains("互素"));
+        }
+    }
+
+    #[test]
+    fn test_paillier_rejects_bad_factors() {
+        for (p, q) in [("0", "53"), ("61", "1"), ("61", "59")] {
+            let res = paillier_decrypt(
+                Some("3233".into()),
+                None,
+                Some(p.into()),
+                Some(q.into()),
+                None,
+                "2".into(),
+            );
+            assert!(res.is_err(), "p = {}, q = {}", p, q);
+        }
+    }
+
+    #[test]
+    fn test_elgamal_roundtrip() {
+        let key = elgamal_generate_key(Some(128)).unwrap();
+        let p: BigUint = key.p.parse().unwrap();
+        assert!(is_probable_prime(&((&p - 1u32) >> 1)));
+        let ct = elgamal_encrypt(
+            key.p.clone(),
+            key.g.clone(),
+            key.y.clone(),
+            "b'flag{eg}'".into(),
+            None,
+        )
+        .unwrap();
+        let m = elgamal_decrypt(key.p, key.x, ct.c1, ct.c2).unwrap();
+        assert_eq!(m.flag.as_deref(), Some("flag{eg}"));
+    }
+
+    #[test]
+    fn test_rabin_roundtrip() {
+        let key = rabin_generate_key(Some(256)).unwrap();
+        for p in [&key.p, &key.q] {
+            let p: BigUint = p.parse().unwrap();
+            assert_eq!(&p % 4u32, BigUint::from(3u32));
+        }
+        let c = rabin_encrypt(key.n.clone(), "b'rabin!'".into()).unwrap();
+        let res = rabin_decrypt(key.p, key.q, c).unwrap();
+        assert!(res
+            .roots
+            .iter()
+            .any(|r| r.utf8.as_deref() == Some("rabin!")));
+        assert!(rabin_encrypt(res.n.clone(), res.n).is_err());
+    }
+}
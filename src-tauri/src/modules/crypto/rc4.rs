@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+use super::codec::{parse_bytes, parse_text_or_bytes, render_bytes, BytesView};
+
+// 跳过的密钥流字节上限, 防止误填超大数值时长时间空转
+const MAX_DROP: usize = 1 << 24;
+const MAX_KSA_ROUNDS: u32 = 1024;
+
+/// 逆向题中常见的魔改 RC4
+///
+/// - key 按 parse_text_or_bytes 解析, data 加密时同样按明文解析, 解密时按字节串解析
+/// - sbox: 自定义的 S 盒初值 (256 字节的排列), 缺省为 0..=255
+/// - ksa_rounds: 重复执行 KSA 的次数 (默认 1); drop: 丢弃密钥流开头的字节数 (RC4-drop[n])
+/// - combine: 密钥流与数据的结合方式 xor (默认) / add / sub, 解密时自动取逆运算
+#[derive(Deserialize, Default)]
+pub struct Rc4Params {
+    pub key: String,
+    pub data: String,
+    pub sbox: Option<String>,
+    pub ksa_rounds: Option<u32>,
+    pub drop: Option<usize>,
+    pub combine: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Rc4Result {
+    pub output: BytesView,
+    pub keystream: String, // 实际使用的密钥流 (hex), 便于与动态调试时的内存比对
+}
+
+#[tauri::command]
+pub fn rc4_encrypt(params: Rc4Params) -> Result<Rc4Result, String> {
+    run_rc4(&params, true)
+}
+
+#[tauri::command]
+pub fn rc4_decrypt(params: Rc4Params) -> Result<Rc4Result, String> {
+    run_rc4(&params, false)
+}
+
+pub fn run_rc4(params: &Rc4Params, encrypt: bool) -> Result<Rc4Result, String> {
+    let key = parse_text_or_bytes(&params.key).map_err(|e| format!("密钥格式错误: {}", e))?;
+    if key.is_empty() {
+        return Err("密钥不能为空".into());
+    }
+    let data = if encrypt {
+        parse_text_or_bytes(&params.data)
+    } else {
+        parse_bytes(&params.data)
+    }
+    .map_err(|e| format!("数据格式错误: {}", e))?;
+
+    let sbox = match params.sbox.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(s) => {
+            let bytes = parse_bytes(s).map_err(|e| format!("S 盒格式错误: {}", e))?;
+            let mut sorted = bytes.clone();
+            sorted.sort_unstable();
+            if !sorted.iter().copied().eq(0..=255) {
+                return Err("S 盒须为 0..=255 的一个排列 (256 个互不相同的字节)".into());
+            }
+            Some(bytes)
+        }
+        None => None,
+    };
+    let ksa_rounds = params.ksa_rounds.unwrap_or(1);
+    if !(1..=MAX_KSA_ROUNDS).contains(&ksa_rounds) {
+        return Err(format!("KSA 次数须在 1 到 {} 之间", MAX_KSA_ROUNDS));
+    }
+    let drop = params.drop.unwrap_or(0);
+    if drop > MAX_DROP {
+        return Err(format!("丢弃的字节数不能超过 {}", MAX_DROP));
+    }
+    let combine = params.combine.as_deref().unwrap_or("xor");
+    let apply: fn(u8, u8) -> u8 = match (combine, encrypt) {
+        ("xor", _) => |b, k| b ^ k,
+        ("add", true) | ("sub", false) => u8::wrapping_add,
+        ("sub", true) | ("add", false) => u8::wrapping_sub,
+        (other, _) => return Err(format!("未知的结合方式: {}", other)),
+    };
+
+    let stream = keystream(&key, sbox.as_deref(), ksa_rounds, drop, data.len());
+    let output: Vec<u8> = data
+        .iter()
+        .zip(&stream)
+        .map(|(&b, &k)| apply(b, k))
+        .collect();
+    Ok(Rc4Result {
+        output: render_bytes(&output),
+        keystream: hex::encode(&stream),
+    })
+}
+
+/// 生成 len 字节密钥流; sbox 为 None、ksa_rounds = 1、drop = 0 时即标准 RC4
+pub fn keystream(
+    key: &[u8],
+    sbox: Option<&[u8]>,
+    ksa_rounds: u32,
+    drop: usize,
+    len: usize,
+) -> Vec<u8> {
+    let mut s: Vec<u8> = match sbox {
+        Some(sbox) => sbox.to_vec(),
+        None => (0..=255).collect(),
+    };
+    let mut j = 0u8;
+    for _ in 0..ksa_rounds {
+        for i in 0..256 {
+            j = j.wrapping_add(s[i]).wrapping_add(key[i % key.len()]);
+            s.swap(i, j as usize);
+        }
+    }
+    let (mut i, mut j) = (0u8, 0u8);
+    let mut next = || {
+        i = i.wrapping_add(1);
+        j = j.wrapping_add(s[i as usize]);
+        s.swap(i as usize, j as usize);
+        s[s[i as usize].wrapping_add(s[j as usize]) as usize]
+    };
+    for _ in 0..drop {
+        next();
+    }
+    (0..len).map(|_| next()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(key: &str, data: &str) -> Rc4Params {
+        Rc4Params {
+            key: key.into(),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_published_vectors() {
+        for (key, plain, cipher) in [
+            ("Key", "Plaintext", "bbf316e8d940af0ad3"),
+            ("Wiki", "pedia", "1021bf0420"),
+            ("Secret", "Attack at dawn", "45a01f645fc35b383552544b9bf5"),
+        ] {
+            let enc = run_rc4(&params(key, plain), true).unwrap();
+            assert_eq!(enc.output.hex, cipher);
+            let dec = run_rc4(&params(key, cipher), false).unwrap();
+            assert_eq!(dec.output.utf8.as_deref(), Some(plain));
+        }
+
+        // RFC 6229: 40 位密钥 0x0102030405 在偏移 0 与 16 处的密钥流
+        let zeros = format!("hex:{}", "00".repeat(16));
+        let mut p = params("hex:0102030405", &zeros);
+        let res = run_rc4(&p, true).unwrap();
+        assert_eq!(res.keystream, "b2396305f03dc027ccc3524a0a1118a8");
+        p.drop = Some(16);
+        let res = run_rc4(&p, true).unwrap();
+        assert_eq!(res.output.hex, "6982944f18fc82d589c403a47a0d0919");
+    }
+
+    #[test]
+    fn test_modified_variants() {
+        // S 盒倒序初始化、两轮 KSA、按字节加法结合
+        let mut p = params("b'rc4_magic'", "flag{modified_rc4}");
+        p.sbox = Some(hex::encode((0..=255u8).rev().collect::<Vec<_>>()));
+        p.ksa_rounds = Some(2);
+        p.combine = Some("add".into());
+        let enc = run_rc4(&p, true).unwrap();
+        let stream = hex::decode(&enc.keystream).unwrap();
+        let expected: Vec<u8> = b"flag{modified_rc4}"
+            .iter()
+            .zip(&stream)
+            .map(|(b, k)| b.wrapping_add(*k))
+            .collect();
+        assert_eq!(enc.output.hex, hex::encode(expected));
+        assert_ne!(stream, keystream(b"rc4_magic", None, 1, 0, stream.len()));
+
+        p.data = enc.output.hex;
+        let dec = run_rc4(&p, false).unwrap();
+        assert_eq!(dec.output.utf8.as_deref(), Some("flag{modified_rc4}"));
+
+        p.sbox = Some(hex::encode([0u8; 256]));
+        assert!(run_rc4(&p, false).is_err());
+        p.sbox = None;
+        p.combine = Some("rol".into());
+        assert!(run_rc4(&p, false).is_err());
+    }
+}
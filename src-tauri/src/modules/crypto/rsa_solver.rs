@@ -0,0 +1,527 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{CheckedSub, One, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::codec::{parse_biguint, render_number, NumberView};
+use super::factor::euler_phi;
+use super::number_theory::{is_probable_prime, small_primes};
+use super::wiener::factor_from_sum;
+
+// 由 (e, d) 分解 N 时尝试的底数个数
+const ED_FACTOR_BASES: u64 = 200;
+// 由 dp 或 dq 求 gcd(a^(e·dp) - a, N) 时使用的底数
+const DP_BASES: [u32; 4] = [2, 3, 5, 7];
+
+/// 已知量, 未知的字段留空
+#[derive(Deserialize, Default)]
+pub struct RsaKnowns {
+    pub n: Option<String>,
+    pub e: Option<String>,
+    pub c: Option<String>,
+    pub p: Option<String>,
+    pub q: Option<String>,
+    pub phi: Option<String>,
+    pub d: Option<String>,
+    pub sum: Option<String>,  // p + q
+    pub diff: Option<String>, // |p - q|
+    pub dp: Option<String>,
+    pub dq: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RsaFullKey {
+    pub n: String,
+    pub p: String,
+    pub q: String,
+    pub phi: String,
+    pub e: Option<String>,
+    pub d: Option<String>,
+    pub dp: Option<String>,
+    pub dq: Option<String>,
+    pub qinv: Option<String>,
+    pub steps: Vec<String>, // 推导过程
+    pub plaintext: Option<NumberView>,
+}
+
+/// 内部状态: 反复套用各个关系式, 直到求出 p、q
+struct State {
+    n: Option<BigUint>,
+    e: Option<BigUint>,
+    p: Option<BigUint>,
+    q: Option<BigUint>,
+    phi: Option<BigUint>,
+    d: Option<BigUint>,
+    sum: Option<BigUint>,
+    diff: Option<BigUint>,
+    dp: Option<BigUint>,
+    dq: Option<BigUint>,
+    steps: Vec<String>,
+}
+
+#[tauri::command]
+pub fn rsa_solve(known: RsaKnowns) -> Result<RsaFullKey, String> {
+    let parse = |value: &Option<String>, name: &str| -> Result<Option<BigUint>, String> {
+        match value {
+            Some(s) if !s.trim().is_empty() => parse_biguint(s)
+                .map(Some)
+                .map_err(|err| format!("{}: {}", name, err)),
+            _ => Ok(None),
+        }
+    };
+    // 指数为 0 或因子不大于 1 时各关系式无意义 (且会导致下溢或除零)
+    let above =
+        |value: &Option<String>, name: &str, bound: u32| -> Result<Option<BigUint>, String> {
+            let value = parse(value, name)?;
+            if value.as_ref().is_some_and(|v| *v <= BigUint::from(bound)) {
+                return Err(format!("{} 必须大于 {}", name, bound));
+            }
+            Ok(value)
+        };
+    let mut state = State {
+        n: parse(&known.n, "n")?,
+        e: above(&known.e, "e", 0)?,
+        p: above(&known.p, "p", 1)?,
+        q: above(&known.q, "q", 1)?,
+        phi: parse(&known.phi, "phi")?,
+        d: above(&known.d, "d", 0)?,
+        sum: parse(&known.sum, "p + q")?,
+        diff: parse(&known.diff, "p - q")?,
+        dp: above(&known.dp, "dp", 0)?,
+        dq: above(&known.dq, "dq", 0)?,
+        steps: Vec::new(),
+    };
+    if let Some(n) = &state.n {
+        if *n <= BigUint::from(DP_BASES[DP_BASES.len() - 1]) {
+            return Err("N 过小".into());
+        }
+    }
+    let c = parse(&known.c, "c")?;
+
+    state.solve(c.as_ref())?;
+    state.into_key(c.as_ref())
+}
+
+impl State {
+    fn solve(&mut self, c: Option<&BigUint>) -> Result<(), String> {
+        // 每轮至少推出一个新量, 否则停止
+        loop {
+            if self.p.is_some() && self.q.is_some() {
+                return Ok(());
+            }
+            if !self.step(c)? {
+                return Err("已知条件不足以分解 N".into());
+            }
+        }
+    }
+
+    fn step(&mut self, c: Option<&BigUint>) -> Result<bool, String> {
+        // p 或 q 已知
+        if let (Some(n), Some(p), None) = (&self.n, &self.p, &self.q) {
+            let (q, r) = n.div_rem(p);
+            if !r.is_zero() {
+                return Err("p 不整除 N".into());
+            }
+            self.q = Some(q);
+            self.steps.push("q = N / p".into());
+            return Ok(true);
+        }
+        if let (Some(_), None, Some(_)) = (&self.n, &self.p, &self.q) {
+            std::mem::swap(&mut self.p, &mut self.q);
+            return Ok(true);
+        }
+
+        // p + q 与 p - q 同时已知, 不需要 N
+        if let (Some(s), Some(t), None) = (&self.sum, &self.diff, &self.p) {
+            // q = (s - t) / 2 必须大于 1
+            if (s + t).is_odd() || t + 4u32 > *s {
+                return Err("p + q 与 p - q 不相容".into());
+            }
+            self.p = Some((s + t) >> 1);
+            self.q = Some((s - t) >> 1);
+            self.steps.push("p = ((p + q) + (p - q)) / 2".into());
+            return Ok(true);
+        }
+
+        if let Some(n) = self.n.clone() {
+            if let Some(phi) = &self.phi {
+                if self.sum.is_none() {
+                    // (p - 1)(q - 1) = N - (p + q) + 1
+                    let s = (&n + 1u32).checked_sub(phi).ok_or("phi 大于 N, 数据有误")?;
+                    self.sum = Some(s);
+                    self.steps.push("p + q = N - φ + 1".into());
+                    return Ok(true);
+                }
+            }
+            if let Some(s) = &self.sum {
+                let (p, q) =
+                    factor_from_sum(&n, s).ok_or("由 p + q 解方程失败 (判别式不是平方数)")?;
+                self.p = Some(p);
+                self.q = Some(q);
+                self.steps.push("解 x² - (p + q)x + N = 0".into());
+                return Ok(true);
+            }
+            if let Some(t) = &self.diff {
+                // (p + q)² = (p - q)² + 4N
+                let s2: BigUint = t * t + (&n << 2);
+                let s = s2.sqrt();
+                if &s * &s != s2 {
+                    return Err("由 p - q 求 p + q 失败 (不是平方数)".into());
+                }
+                self.sum = Some(s);
+                self.steps.push("p + q = √((p - q)² + 4N)".into());
+                return Ok(true);
+            }
+            if let (Some(e), Some(d)) = (&self.e, &self.d) {
+                let p = factor_with_exponents(&n, &(e * d - 1u32))
+                    .ok_or("由 (e, d) 分解 N 失败, 请检查 e·d ≡ 1 (mod φ)")?;
+                self.p = Some(p);
+                self.steps.push("由 e·d - 1 为 λ(N) 的倍数分解 N".into());
+                return Ok(true);
+            }
+            if let Some(e) = &self.e {
+                for (leak, name) in [(&self.dp, "dp"), (&self.dq, "dq")] {
+                    let Some(leak) = leak else { continue };
+                    // a^(e·dp) ≡ a (mod p)  =>  p | a^(e·dp) - a
+                    let exp = e * leak;
+                    for a in DP_BASES {
+                        let a = BigUint::from(a);
+                        let x = a.modpow(&exp, &n);
+                        let g = (x + &n - &a).gcd(&n);
+                        if !g.is_one() && g != n {
+                            self.p = Some(g);
+                            self.steps.push(format!("p = gcd(a^(e·{}) - a, N)", name));
+                            return Ok(true);
+                        }
+                    }
+                }
+            }
+        } else {
+            // 没有 N: 由 dp、dq 枚举 k (e·dp - 1 = k·(p - 1), k < e) 恢复 p、q
+            if let (Some(e), Some(dp), Some(dq)) = (&self.e, &self.dp, &self.dq) {
+                let ps = primes_from_crt_exponent(e, dp)?;
+                let qs = primes_from_crt_exponent(e, dq)?;
+                let (p, q) = pick_pair(&ps, &qs, e, c).ok_or("枚举 k 未找到满足条件的素数")?;
+                self.steps.push(format!(
+                    "枚举 e·dp - 1 = k(p - 1) 与 e·dq - 1 = k(q - 1) ({} × {} 个候选)",
+                    ps.len(),
+                    qs.len()
+                ));
+                self.n = Some(&p * &q);
+                self.p = Some(p);
+                self.q = Some(q);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn into_key(self, c: Option<&BigUint>) -> Result<RsaFullKey, String> {
+        let p = self.p.ok_or("未能求出 p")?;
+        let q = self.q.ok_or("未能求出 q")?;
+        if p.is_one() || q.is_one() {
+            return Err("求出的因子为 1, 已知条件有误".into());
+        }
+        let n = &p * &q;
+        if let Some(given) = &self.n {
+            if *given != n {
+                return Err("p·q 与给定的 N 不一致".into());
+            }
+        }
+        let (p, q) = if p >= q { (p, q) } else { (q, p) };
+        let phi = euler_phi(&[p.clone(), q.clone()]);
+
+        let d = match (&self.d, &self.e) {
+            (Some(d), Some(e)) => {
+                // d 可能按 λ(N) 而非 φ(N) 求出, 用 λ 检查
+                let lambda = (&p - 1u32).lcm(&(&q - 1u32));
+                if !(e * d % &lambda).is_one() {
+                    return Err("e·d ≢ 1 (mod λ(N)), 已知的 e 与 d 不一致".into());
+                }
+                Some(d.clone())
+            }
+            (Some(d), None) => Some(d.clone()),
+            (None, Some(e)) => Some(e.modinv(&phi).ok_or("e 与 φ 不互质, 无法求 d")?),
+            _ => None,
+        };
+        let plaintext = match (c, &d) {
+            (Some(c), Some(d)) => Some(render_number(&c.modpow(d, &n))),
+            _ => None,
+        };
+
+        Ok(RsaFullKey {
+            n: n.to_string(),
+            phi: phi.to_string(),
+            e: self.e.as_ref().map(|e| e.to_string()),
+            dp: d.as_ref().map(|d| (d % (&p - 1u32)).to_string()),
+            dq: d.as_ref().map(|d| (d % (&q - 1u32)).to_string()),
+            qinv: q.modinv(&p).map(|x| x.to_string()),
+            d: d.map(|d| d.to_string()),
+            p: p.to_string(),
+            q: q.to_string(),
+            steps: self.steps,
+            plaintext,
+        })
+    }
+}
+
+// ===================== 各关系式 =====================
+
+/// 已知 λ(N) 的倍数 k (如 e·d - 1) 时分解 N
+///
+/// 写 k = 2^s·t, 对随机底数 a 计算 a^t, a^2t, ... 直到出现 1 的非平凡平方根 x, 则 gcd(x - 1, N) 为因子。
+pub fn factor_with_exponents(n: &BigUint, k: &BigUint) -> Option<BigUint> {
+    let s = k.trailing_zeros()?;
+    let t = k >> s;
+    let n_1 = n - 1u32;
+    for a in small_primes(ED_FACTOR_BASES * 10)
+        .into_iter()
+        .take(ED_FACTOR_BASES as usize)
+    {
+        let a = BigUint::from(a);
+        let g = a.gcd(n);
+        if !g.is_one() {
+            return (g != *n).then_some(g);
+        }
+        let mut x = a.modpow(&t, n);
+        for _ in 0..s {
+            let y = &x * &x % n;
+            if y.is_one() && !x.is_one() && x != n_1 {
+                return Some((&x - 1u32).gcd(n));
+            }
+            x = y;
+        }
+    }
+    None
+}
+
+/// 由 e·dp - 1 = k·(p - 1) 枚举 1 <= k < e, 返回所有素数候选
+fn primes_from_crt_exponent(e: &BigUint, dp: &BigUint) -> Result<Vec<BigUint>, String> {
+    let e_small = e
+        .to_u64()
+        .filter(|&e| e <= 1 << 24)
+        .ok_or("e 过大, 无法枚举 k")?;
+    let target = e * dp - 1u32;
+    let mut primes = Vec::new();
+    for k in 1..e_small {
+        let (p_1, r) = target.div_rem(&BigUint::from(k));
+        if r.is_zero() {
+            let p = p_1 + 1u32;
+            if is_probable_prime(&p) {
+                primes.push(p);
+            }
+        }
+    }
+    Ok(primes)
+}
+
+/// 有多个候选时, 给出密文则优先选择解出可读文本的组合
+fn pick_pair(
+    ps: &[BigUint],
+    qs: &[BigUint],
+    e: &BigUint,
+    c: Option<&BigUint>,
+) -> Option<(BigUint, BigUint)> {
+    let mut pairs = ps
+        .iter()
+        .flat_map(|p| qs.iter().map(move |q| (p, q)))
+        .filter(|(p, q)| p != q);
+    let first = pairs.next()?;
+    let Some(c) = c else {
+        return Some((first.0.clone(), first.1.clone()));
+    };
+
+    let readable = |(p, q): (&BigUint, &BigUint)| {
+        let n = p * q;
+        let phi = euler_phi(&[p.clone(), q.clone()]);
+        let d = e.modinv(&phi)?;
+        let view = render_number(&c.modpow(&d, &n));
+        (view.utf8.is_some() || view.flag.is_some()).then(|| (p.clone(), q.clone()))
+    };
+    std::iter::once(first)
+        .chain(pairs)
+        .find_map(readable)
+        .or(Some((first.0.clone(), first.1.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: &str = "9850123957575902665104089481444125792007308699457361074267579941176959828396552554177961557617483753169006969298140389205214634823837608680507987941471723";
+    const P: &str =
+        "107674494662767043094277875419688853276678008574094782333963044639862849200449";
+    const Q: &str = "91480568248090368984997984906827538872289899110514214646578030038154671120427";
+    const D: &str = "8583256465343922507877448496365581819887290922816286620218700204170679492196147976206758249345106411494943670057453928123950512152221697923825288780552705";
+    const DP: &str =
+        "97890683751117475039188006366722633869890735109353728748984331381239731465601";
+    const DQ: &str =
+        "52643516653317670566248287269421758716901006378899447505699739458000293981045";
+
+    fn big(s: &str) -> BigUint {
+        parse_biguint(s).unwrap()
+    }
+
+    fn cipher(msg: &[u8]) -> String {
+        BigUint::from_bytes_be(msg)
+            .modpow(&BigUint::from(65537u32), &big(N))
+            .to_string()
+    }
+
+    fn check(key: RsaFullKey) {
+        assert_eq!(key.n, N);
+        assert_eq!((key.p.as_str(), key.q.as_str()), (P, Q));
+        assert_eq!(key.d.as_deref(), Some(D));
+        assert_eq!(key.dp.as_deref(), Some(DP));
+        assert_eq!(key.dq.as_deref(), Some(DQ));
+    }
+
+    #[test]
+    fn test_relations_with_modulus() {
+        let (p, q) = (big(P), big(Q));
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let cases = [
+            RsaKnowns {
+                q: Some(Q.into()),
+                ..Default::default()
+            },
+            RsaKnowns {
+                phi: Some(phi.to_string()),
+                ..Default::default()
+            },
+            RsaKnowns {
+                sum: Some((&p + &q).to_string()),
+                ..Default::default()
+            },
+            RsaKnowns {
+                diff: Some((&p - &q).to_string()),
+                ..Default::default()
+            },
+            RsaKnowns {
+                d: Some(D.into()),
+                ..Default::default()
+            },
+            RsaKnowns {
+                dp: Some(DP.into()),
+                ..Default::default()
+            },
+            RsaKnowns {
+                dq: Some(DQ.into()),
+                ..Default::default()
+            },
+        ];
+        for known in cases {
+            let key = rsa_solve(RsaKnowns {
+                n: Some(N.into()),
+                e: Some("65537".into()),
+                c: Some(cipher(b"flag{partial}")),
+                ..known
+            })
+            .unwrap();
+            assert_eq!(
+                key.plaintext.unwrap().flag.as_deref(),
+                Some("flag{partial}")
+            );
+            assert_eq!(key.n, N);
+            assert_eq!(key.d.as_deref(), Some(D));
+        }
+    }
+
+    #[test]
+    fn test_dp_dq_without_modulus() {
+        let key = rsa_solve(RsaKnowns {
+            e: Some("65537".into()),
+            dp: Some(DP.into()),
+            dq: Some(DQ.into()),
+            c: Some(cipher(b"no modulus")),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            key.plaintext.as_ref().unwrap().utf8.as_deref(),
+            Some("no modulus")
+        );
+        check(key);
+    }
+
+    #[test]
+    fn test_sum_and_diff_without_modulus() {
+        let (p, q) = (big(P), big(Q));
+        let key = rsa_solve(RsaKnowns {
+            e: Some("65537".into()),
+            sum: Some((&p + &q).to_string()),
+            diff: Some((&p - &q).to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        check(key);
+    }
+
+    #[test]
+    fn test_insufficient_knowledge() {
+        let err = rsa_solve(RsaKnowns {
+            n: Some(N.into()),
+            e: Some("65537".into()),
+            ..Default::default()
+        });
+        assert!(err.is_err());
+        let err = rsa_solve(RsaKnowns {
+            n: Some(N.into()),
+            p: Some("12345".into()),
+            ..Default::default()
+        });
+        assert!(err.unwrap_err().contains("不整除"));
+    }
+
+    #[test]
+    fn test_rejects_degenerate_input() {
+        for (field, value) in [("d", "0"), ("p", "0"), ("p", "1"), ("e", "0"), ("n", "6")] {
+            let mut known = RsaKnowns {
+                n: Some(N.into()),
+                e: Some("65537".into()),
+                dp: Some(DP.into()),
+                ..Default::default()
+            };
+            let slot = match field {
+                "d" => &mut known.d,
+                "p" => &mut known.p,
+                "e" => &mut known.e,
+                _ => &mut known.n,
+            };
+            *slot = Some(value.into());
+            assert!(rsa_solve(known).is_err(), "{} = {}", field, value);
+        }
+        let err = rsa_solve(RsaKnowns {
+            n: Some(N.into()),
+            e: Some("3".into()),
+            p: Some(P.into()),
+            d: Some(D.into()),
+            ..Default::default()
+        });
+        assert!(err.unwrap_err().contains("不一致"));
+
+        // 不带 N 时由 p + q 与 p - q 直接求因子, q 不能为 0 或 1
+        for (sum, diff) in [("12", "12"), ("12", "10"), ("12", "14")] {
+            let known = RsaKnowns {
+                sum: Some(sum.into()),
+                diff: Some(diff.into()),
+                ..Default::default()
+            };
+            assert!(
+                rsa_solve(known).is_err(),
+                "p + q = {}, p - q = {}",
+                sum,
+                diff
+            );
+        }
+        // p + q = N + 1 解出的因子为 1 与 N
+        let n = big(N);
+        let known = RsaKnowns {
+            n: Some(N.into()),
+            e: Some("65537".into()),
+            sum: Some((&n + 1u32).to_string()),
+            ..Default::default()
+        };
+        assert!(rsa_solve(known).is_err());
+    }
+}
@@ -0,0 +1,539 @@
+use std::sync::LazyLock;
+
+use base64::{engine::general_purpose, Engine as _};
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::Zero;
+use serde::Serialize;
+
+use super::asn1::{parse_der, TAG_SEQUENCE};
+use super::codec::{parse_bytes, parse_text_or_bytes, render_bytes, BytesView};
+use super::ecc::{Curve, Field, Point, Zmod};
+use super::modular_root::sqrt_mod_prime;
+use super::sm3::sm3;
+
+const DEFAULT_ID: &str = "1234567812345678";
+
+/// GB/T 32918.5-2017 推荐曲线 sm2p256v1
+static SM2: LazyLock<Sm2Curve> = LazyLock::new(|| {
+    let hex = |s: &str| BigUint::parse_bytes(s.as_bytes(), 16).unwrap();
+    let p = hex("FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF00000000FFFFFFFFFFFFFFFF");
+    Sm2Curve {
+        curve: Curve {
+            a: &p - 3u32,
+            b: hex("28E9FA9E9D9F5E344D5A9E4BCF6509A7F39789F515AB8F92DDBCBD414D940E93"),
+            field: Zmod(p),
+        },
+        g: (
+            hex("32C4AE2C1F1981195F9904466A39C9948FE30BBFF2660BE1715A4589334C74C7"),
+            hex("BC3736A2F4F6779C59BDCEE36B692153D0A9877CC62A474002DF32E52139F0A0"),
+        ),
+        n: hex("FFFFFFFEFFFFFFFFFFFFFFFFFFFFFFFF7203DF6B21C6052B53BBF40939D54123"),
+    }
+});
+
+struct Sm2Curve {
+    curve: Curve<Zmod>,
+    g: (BigUint, BigUint),
+    n: BigUint,
+}
+
+impl Sm2Curve {
+    fn mul_g(&self, k: &BigUint) -> Point<BigUint> {
+        self.curve.mul(&Some(self.g.clone()), k)
+    }
+}
+
+/// 公钥以未压缩形式 04||x||y 给出; 仅导入公钥时 private_key 为空
+#[derive(Serialize, Debug)]
+pub struct Sm2KeyPair {
+    pub private_key: Option<String>,
+    pub public_key: String,
+    pub x: String,
+    pub y: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Sm2Signature {
+    pub r: String,
+    pub s: String,
+    pub raw: String, // r||s
+    pub der: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Sm2Ciphertext {
+    pub hex: String,
+    pub base64: String,
+}
+
+#[tauri::command]
+pub fn sm2_generate_key() -> Result<Sm2KeyPair, String> {
+    let d = random_scalar(&(&SM2.n - 1u32));
+    Ok(key_pair(Some(&d), &public_from_private(&d)?))
+}
+
+/// 导入 hex 密钥: 32 字节为私钥 (同时导出公钥), 64/65 字节为未压缩公钥, 33 字节为压缩公钥
+#[tauri::command]
+pub fn sm2_import_key(key: String) -> Result<Sm2KeyPair, String> {
+    let (d, pk) = parse_key(&key)?;
+    Ok(key_pair(d.as_ref(), &pk))
+}
+
+/// k 仅用于复现标准示例或题目给定的随机数, 缺省时随机选取
+#[tauri::command]
+pub fn sm2_sign(
+    private_key: String,
+    message: String,
+    id: Option<String>,
+    k: Option<String>,
+) -> Result<Sm2Signature, String> {
+    let d = parse_private(&private_key)?;
+    let msg = parse_text_or_bytes(&message).map_err(|e| format!("消息格式错误: {}", e))?;
+    let za = user_hash(&id_bytes(id.as_deref())?, &public_from_private(&d)?);
+    let k = parse_nonce(k.as_deref())?;
+    let (r, s) = sign(&d, &za, &msg, k.as_ref())?;
+    Ok(signature_view(&r, &s))
+}
+
+/// 签名可以是 r||s (64 字节) 或 DER 编码的 SEQUENCE { r, s }
+#[tauri::command]
+pub fn sm2_verify(
+    public_key: String,
+    message: String,
+    signature: String,
+    id: Option<String>,
+) -> Result<bool, String> {
+    let (_, pk) = parse_key(&public_key)?;
+    let msg = parse_text_or_bytes(&message).map_err(|e| format!("消息格式错误: {}", e))?;
+    let (r, s) = parse_signature(&signature)?;
+    let za = user_hash(&id_bytes(id.as_deref())?, &pk);
+    Ok(verify(&pk, &za, &msg, &r, &s))
+}
+
+/// order: `c1c3c2` (默认, GB/T 32918-2016) 或 `c1c2c3` (旧版)
+#[tauri::command]
+pub fn sm2_encrypt(
+    public_key: String,
+    message: String,
+    order: Option<String>,
+    k: Option<String>,
+) -> Result<Sm2Ciphertext, String> {
+    let (_, pk) = parse_key(&public_key)?;
+    let msg = parse_text_or_bytes(&message).map_err(|e| format!("消息格式错误: {}", e))?;
+    let c1c3c2 = parse_order(order.as_deref())?;
+    let k = parse_nonce(k.as_deref())?;
+    let out = encrypt(&pk, &msg, c1c3c2, k.as_ref())?;
+    Ok(Sm2Ciphertext {
+        hex: hex::encode(&out),
+        base64: general_purpose::STANDARD.encode(&out),
+    })
+}
+
+/// C1 可带或不带 04 前缀
+#[tauri::command]
+pub fn sm2_decrypt(
+    private_key: String,
+    ciphertext: String,
+    order: Option<String>,
+) -> Result<BytesView, String> {
+    let d = parse_private(&private_key)?;
+    let data = parse_bytes(&ciphertext).map_err(|e| format!("密文格式错误: {}", e))?;
+    let c1c3c2 = parse_order(order.as_deref())?;
+    Ok(render_bytes(&decrypt(&d, &data, c1c3c2)?))
+}
+
+// ===================== 算法 =====================
+
+/// Z_A = SM3(ENTL_A || ID_A || a || b || x_G || y_G || x_A || y_A)
+fn user_hash(id: &[u8], pk: &(BigUint, BigUint)) -> [u8; 32] {
+    let mut buf = ((id.len() * 8) as u16).to_be_bytes().to_vec();
+    buf.extend(id);
+    for v in [&SM2.curve.a, &SM2.curve.b, &SM2.g.0, &SM2.g.1, &pk.0, &pk.1] {
+        buf.extend(be32(v));
+    }
+    sm3(&buf)
+}
+
+fn message_digest(za: &[u8; 32], msg: &[u8]) -> BigUint {
+    let mut buf = za.to_vec();
+    buf.extend(msg);
+    BigUint::from_bytes_be(&sm3(&buf))
+}
+
+fn sign(
+    d: &BigUint,
+    za: &[u8; 32],
+    msg: &[u8],
+    fixed_k: Option<&BigUint>,
+) -> Result<(BigUint, BigUint), String> {
+    let n = &SM2.n;
+    let e = message_digest(za, msg);
+    let inv = (d + 1u32).modinv(n).ok_or("私钥无效: d + 1 不可逆")?;
+    loop {
+        let k = fixed_k
+            .cloned()
+            .unwrap_or_else(|| random_scalar(&(n - 1u32)));
+        let (x1, _) = SM2.mul_g(&k).ok_or("k 无效")?;
+        let r = (&e + x1) % n;
+        let s = if r.is_zero() || &r + &k == *n {
+            BigUint::zero()
+        } else {
+            // s = (1 + d)^-1 · (k - r·d) mod n
+            &inv * ((&k + n - &r * d % n) % n) % n
+        };
+        if !s.is_zero() {
+            return Ok((r, s));
+        }
+        if fixed_k.is_some() {
+            return Err("给定的 k 不能产生有效签名".into());
+        }
+    }
+}
+
+fn verify(pk: &(BigUint, BigUint), za: &[u8; 32], msg: &[u8], r: &BigUint, s: &BigUint) -> bool {
+    let n = &SM2.n;
+    let in_range = |v: &BigUint| !v.is_zero() && v < n;
+    if !in_range(r) || !in_range(s) {
+        return false;
+    }
+    let t = (r + s) % n;
+    if t.is_zero() {
+        return false;
+    }
+    let e = message_digest(za, msg);
+    let point = SM2
+        .curve
+        .add(&SM2.mul_g(s), &SM2.curve.mul(&Some(pk.clone()), &t));
+    match point {
+        Some((x1, _)) => (e + x1) % n == *r,
+        None => false,
+    }
+}
+
+fn encrypt(
+    pk: &(BigUint, BigUint),
+    msg: &[u8],
+    c1c3c2: bool,
+    fixed_k: Option<&BigUint>,
+) -> Result<Vec<u8>, String> {
+    if msg.is_empty() {
+        return Err("明文为空".into());
+    }
+    loop {
+        let k = fixed_k
+            .cloned()
+            .unwrap_or_else(|| random_scalar(&(&SM2.n - 1u32)));
+        let c1 = SM2.mul_g(&k).ok_or("k 无效")?;
+        let (x2, y2) = SM2.curve.mul(&Some(pk.clone()), &k).ok_or("公钥无效")?;
+        let t = kdf(&[be32(&x2), be32(&y2)].concat(), msg.len());
+        if t.iter().all(|&b| b == 0) {
+            if fixed_k.is_some() {
+                return Err("给定的 k 导出的密钥流全为零".into());
+            }
+            continue;
+        }
+        let c2: Vec<u8> = msg.iter().zip(&t).map(|(m, t)| m ^ t).collect();
+        let c3 = sm3(&[be32(&x2), msg.to_vec(), be32(&y2)].concat());
+        let c1 = encode_point(&c1);
+        return Ok(if c1c3c2 {
+            [c1, c3.to_vec(), c2].concat()
+        } else {
+            [c1, c2, c3.to_vec()].concat()
+        });
+    }
+}
+
+/// 不带前缀的 C1 其 x 坐标也可能以 0x04 开头, 因此首字节为 04 时两种长度都尝试, 以 C3 校验为准
+fn decrypt(d: &BigUint, data: &[u8], c1c3c2: bool) -> Result<Vec<u8>, String> {
+    let prefixed = data.first() == Some(&4);
+    let result = decrypt_with(d, data, if prefixed { 65 } else { 64 }, c1c3c2);
+    if prefixed && result.is_err() {
+        if let Ok(msg) = decrypt_with(d, data, 64, c1c3c2) {
+            return Ok(msg);
+        }
+    }
+    result
+}
+
+fn decrypt_with(d: &BigUint, data: &[u8], c1_len: usize, c1c3c2: bool) -> Result<Vec<u8>, String> {
+    if data.len() <= c1_len + 32 {
+        return Err("密文过短".into());
+    }
+    let c1 = decode_point(&data[..c1_len])?;
+    let rest = &data[c1_len..];
+    let (c3, c2) = if c1c3c2 {
+        (&rest[..32], &rest[32..])
+    } else {
+        (&rest[rest.len() - 32..], &rest[..rest.len() - 32])
+    };
+    let (x2, y2) = SM2.curve.mul(&Some(c1), d).ok_or("C1 无效")?;
+    let t = kdf(&[be32(&x2), be32(&y2)].concat(), c2.len());
+    let msg: Vec<u8> = c2.iter().zip(&t).map(|(c, t)| c ^ t).collect();
+    if sm3(&[be32(&x2), msg.clone(), be32(&y2)].concat()) != c3 {
+        return Err("C3 校验失败: 私钥或密文顺序不正确".into());
+    }
+    Ok(msg)
+}
+
+/// 密钥派生: SM3(Z || ct) 依次拼接, ct 为从 1 开始的 32 位大端计数器
+fn kdf(z: &[u8], len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len + 32);
+    let mut ct = 1u32;
+    while out.len() < len {
+        out.extend(sm3(&[z, &ct.to_be_bytes()].concat()));
+        ct += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+// ===================== 编码 =====================
+
+fn be32(v: &BigUint) -> Vec<u8> {
+    let bytes = v.to_bytes_be();
+    let mut out = vec![0u8; 32usize.saturating_sub(bytes.len())];
+    out.extend(bytes);
+    out
+}
+
+fn encode_point(pt: &(BigUint, BigUint)) -> Vec<u8> {
+    [vec![4], be32(&pt.0), be32(&pt.1)].concat()
+}
+
+/// 支持 04||x||y、x||y 与 02/03||x 三种形式
+fn decode_point(bytes: &[u8]) -> Result<(BigUint, BigUint), String> {
+    let curve = &SM2.curve;
+    let p = &curve.field.0;
+    let pt = match (bytes.len(), bytes.first()) {
+        (65, Some(4)) => (
+            BigUint::from_bytes_be(&bytes[1..33]),
+            BigUint::from_bytes_be(&bytes[33..]),
+        ),
+        (64, _) => (
+            BigUint::from_bytes_be(&bytes[..32]),
+            BigUint::from_bytes_be(&bytes[32..]),
+        ),
+        (33, Some(&tag @ (2 | 3))) => {
+            let x = BigUint::from_bytes_be(&bytes[1..]);
+            let f = &curve.field;
+            let rhs = f.add(
+                &f.add(&f.pow(&x, &3u32.into()), &f.mul(&curve.a, &x)),
+                &curve.b,
+            );
+            let y = sqrt_mod_prime(&rhs, p).ok_or("压缩点的 x 不在曲线上")?;
+            let y = if y.is_odd() == (tag == 3) { y } else { p - y };
+            (x, y)
+        }
+        _ => return Err(format!("无法识别的公钥长度: {} 字节", bytes.len())),
+    };
+    if &pt.0 >= p || &pt.1 >= p || !curve.contains(&Some(pt.clone())) {
+        return Err("点不在 SM2 曲线上".into());
+    }
+    Ok(pt)
+}
+
+fn parse_key(key: &str) -> Result<(Option<BigUint>, (BigUint, BigUint)), String> {
+    let bytes = parse_bytes(key).map_err(|e| format!("密钥格式错误: {}", e))?;
+    if bytes.len() == 32 {
+        let d = check_private(BigUint::from_bytes_be(&bytes))?;
+        let pk = public_from_private(&d)?;
+        return Ok((Some(d), pk));
+    }
+    Ok((None, decode_point(&bytes)?))
+}
+
+fn parse_private(key: &str) -> Result<BigUint, String> {
+    match parse_key(key)? {
+        (Some(d), _) => Ok(d),
+        (None, _) => Err("需要 32 字节的私钥".into()),
+    }
+}
+
+fn check_private(d: BigUint) -> Result<BigUint, String> {
+    if d.is_zero() || d >= &SM2.n - 1u32 {
+        return Err("私钥须在 [1, n-2] 范围内".into());
+    }
+    Ok(d)
+}
+
+fn public_from_private(d: &BigUint) -> Result<(BigUint, BigUint), String> {
+    SM2.mul_g(d).ok_or_else(|| "私钥无效".to_string())
+}
+
+fn parse_nonce(k: Option<&str>) -> Result<Option<BigUint>, String> {
+    let Some(k) = k.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    // 与密钥一致按十六进制读取
+    let k = BigUint::parse_bytes(k.trim_start_matches("0x").as_bytes(), 16)
+        .ok_or_else(|| format!("k 格式错误: {}", k))?;
+    if k.is_zero() || k >= SM2.n {
+        return Err("k 须在 [1, n-1] 范围内".into());
+    }
+    Ok(Some(k))
+}
+
+fn parse_order(order: Option<&str>) -> Result<bool, String> {
+    match order.unwrap_or("c1c3c2").to_ascii_lowercase().as_str() {
+        "c1c3c2" => Ok(true),
+        "c1c2c3" => Ok(false),
+        other => Err(format!("未知的密文顺序: {}", other)),
+    }
+}
+
+fn id_bytes(id: Option<&str>) -> Result<Vec<u8>, String> {
+    let id = match id.filter(|s| !s.is_empty()) {
+        Some(id) => parse_text_or_bytes(id).map_err(|e| format!("ID 格式错误: {}", e))?,
+        None => DEFAULT_ID.as_bytes().to_vec(),
+    };
+    if id.len() * 8 > u16::MAX as usize {
+        return Err("ID 过长".into());
+    }
+    Ok(id)
+}
+
+fn parse_signature(sig: &str) -> Result<(BigUint, BigUint), String> {
+    let bytes = parse_bytes(sig).map_err(|e| format!("签名格式错误: {}", e))?;
+    if bytes.len() == 64 {
+        return Ok((
+            BigUint::from_bytes_be(&bytes[..32]),
+            BigUint::from_bytes_be(&bytes[32..]),
+        ));
+    }
+    let nodes = parse_der(&bytes).map_err(|e| format!("签名格式错误: {}", e))?;
+    match nodes.as_slice() {
+        [seq] if seq.is(TAG_SEQUENCE) => match seq.children.as_slice() {
+            [r, s] => r.as_biguint().zip(s.as_biguint()),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| "签名须为 r||s (64 字节) 或 DER SEQUENCE { r, s }".to_string())
+}
+
+fn signature_view(r: &BigUint, s: &BigUint) -> Sm2Signature {
+    let der_int = |v: &BigUint| {
+        let mut bytes = v.to_bytes_be();
+        if bytes[0] & 0x80 != 0 {
+            bytes.insert(0, 0);
+        }
+        [vec![2, bytes.len() as u8], bytes].concat()
+    };
+    let body = [der_int(r), der_int(s)].concat();
+    Sm2Signature {
+        r: hex::encode(be32(r)),
+        s: hex::encode(be32(s)),
+        raw: hex::encode([be32(r), be32(s)].concat()),
+        der: hex::encode([vec![0x30, body.len() as u8], body].concat()),
+    }
+}
+
+fn key_pair(d: Option<&BigUint>, pk: &(BigUint, BigUint)) -> Sm2KeyPair {
+    Sm2KeyPair {
+        private_key: d.map(|d| hex::encode(be32(d))),
+        public_key: hex::encode(encode_point(pk)),
+        x: hex::encode(be32(&pk.0)),
+        y: hex::encode(be32(&pk.1)),
+    }
+}
+
+/// [1, upper) 内的随机数
+fn random_scalar(upper: &BigUint) -> BigUint {
+    loop {
+        let bytes: [u8; 32] = rand::random();
+        let v = BigUint::from_bytes_be(&bytes);
+        if !v.is_zero() && &v < upper {
+            return v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // GB/T 32918.5-2017 附录中推荐曲线上的示例
+    const D: &str = "3945208F7B2144B13F36E38AC6D39F95889393692860B51A42FB81EF4DF7C5B8";
+    const K: &str = "59276E27D506861A16680F3AD9C02DCCEF3CC1FA3CDBE4CE6D54B80DEAC1BC21";
+
+    #[test]
+    fn test_standard_signature() {
+        let pair = sm2_import_key(D.into()).unwrap();
+        assert_eq!(
+            pair.x,
+            "09f9df311e5421a150dd7d161e4bc5c672179fad1833fc076bb08ff356f35020"
+        );
+        assert_eq!(
+            pair.y,
+            "ccea490ce26775a52dc6ea718cc1aa600aed05fbf35e084a6632f6072da9ad13"
+        );
+
+        let sig = sm2_sign(D.into(), "message digest".into(), None, Some(K.into())).unwrap();
+        assert_eq!(
+            sig.r,
+            "f5a03b0648d2c4630eeac513e1bb81a15944da3827d5b74143ac7eaceee720b3"
+        );
+        assert_eq!(
+            sig.s,
+            "b1b6aa29df212fd8763182bc0d421ca1bb9038fd1f7f42d4840b69c485bbc1aa"
+        );
+        let pk = pair.public_key.clone();
+        assert!(sm2_verify(pk.clone(), "message digest".into(), sig.raw, None).unwrap());
+        assert!(sm2_verify(pk.clone(), "message digest".into(), sig.der.clone(), None).unwrap());
+        assert!(!sm2_verify(pk.clone(), "message digesT".into(), sig.der.clone(), None).unwrap());
+        assert!(!sm2_verify(pk, "message digest".into(), sig.der, Some("ALICE".into())).unwrap());
+    }
+
+    #[test]
+    fn test_standard_encryption() {
+        let pk = sm2_import_key(D.into()).unwrap().public_key;
+        let ct = sm2_encrypt(
+            pk.clone(),
+            "encryption standard".into(),
+            None,
+            Some(K.into()),
+        )
+        .unwrap();
+        assert_eq!(
+            ct.hex,
+            "0404ebfc718e8d1798620432268e77feb6415e2ede0e073c0f4f640ecd2e149a73\
+             e858f9d81e5430a57b36daab8f950a3c64e6ee6a63094d99283aff767e124df0\
+             59983c18f809e262923c53aec295d30383b54e39d609d160afcb1908d0bd8766\
+             21886ca989ca9c7d58087307ca93092d651efa"
+        );
+        let plain = sm2_decrypt(D.into(), ct.base64, None).unwrap();
+        assert_eq!(plain.utf8.as_deref(), Some("encryption standard"));
+        // 去掉 04 前缀后 C1 的 x 坐标本身以 04 开头, 不能按前缀判断长度
+        let plain = sm2_decrypt(D.into(), ct.hex[2..].into(), None).unwrap();
+        assert_eq!(plain.utf8.as_deref(), Some("encryption standard"));
+        // 旧版顺序, 带或不带 04 前缀的 C1
+        let old = sm2_encrypt(
+            pk,
+            "flag{sm2}".into(),
+            Some("c1c2c3".into()),
+            Some(K.into()),
+        )
+        .unwrap();
+        for hex in [&old.hex[..], &old.hex[2..]] {
+            let plain = sm2_decrypt(D.into(), hex.into(), Some("c1c2c3".into())).unwrap();
+            assert_eq!(plain.utf8.as_deref(), Some("flag{sm2}"));
+        }
+        assert!(sm2_decrypt(D.into(), old.hex, None).is_err());
+    }
+
+    #[test]
+    fn test_generated_and_compressed_keys() {
+        let pair = sm2_generate_key().unwrap();
+        let d = pair.private_key.unwrap();
+        let y_odd = u8::from_str_radix(&pair.y[62..], 16).unwrap() & 1;
+        let compressed = format!("0{}{}", 2 + y_odd, pair.x);
+        assert_eq!(
+            sm2_import_key(compressed.clone()).unwrap().public_key,
+            pair.public_key
+        );
+        let sig = sm2_sign(d, "hello".into(), Some("hex:414c494345".into()), None).unwrap();
+        assert!(sm2_verify(compressed, "hello".into(), sig.raw, Some("ALICE".into())).unwrap());
+        assert!(sm2_import_key("04".repeat(65)).is_err());
+    }
+}
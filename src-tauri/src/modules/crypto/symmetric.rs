@@ -0,0 +1,704 @@
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::aead::consts::{U12, U16};
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::AesGcm;
+use base64::{engine::general_purpose, Engine as _};
+use blowfish::Blowfish;
+use chacha20::{ChaCha20, ChaCha20Legacy, XChaCha20};
+use cipher::{
+    Block, BlockCipher, BlockDecrypt, BlockEncrypt, BlockSizeUser, KeyInit, KeyIvInit, StreamCipher,
+};
+use des::{Des, TdesEde2, TdesEde3};
+use salsa20::{Salsa20, XSalsa20};
+use serde::{Deserialize, Serialize};
+
+use super::codec::{parse_bytes, parse_text_or_bytes, render_bytes, BytesView};
+use super::padding::{pkcs7_pad, unpad_pkcs7};
+use super::rc4::keystream;
+use super::sm4::Sm4;
+
+const ALGORITHMS: [&str; 8] = [
+    "aes", "des", "3des", "blowfish", "sm4", "rc4", "salsa20", "chacha20",
+];
+const GCM_TAG_LEN: usize = 16;
+
+/// algorithm: aes / des / 3des / blowfish / sm4 / rc4 / salsa20 / chacha20
+///
+/// - mode 仅对分组密码有效: ecb / cbc / cfb / ofb / ctr / gcm (gcm 仅限 AES)
+/// - padding 仅对 ecb / cbc 有效: pkcs7 (默认) / zero / none
+/// - *_format 为 auto (默认) / hex / base64 / utf8; key 与 iv 的 auto 按算法要求的长度
+///   依次尝试 hex、UTF-8、Base64, data 的 auto 在加密时按明文、解密时按字节串解析
+/// - GCM 加密输出 密文||tag, 解密时 tag 可附在密文末尾或单独给出
+#[derive(Deserialize, Default)]
+pub struct SymmetricParams {
+    pub algorithm: String,
+    pub mode: Option<String>,
+    pub padding: Option<String>,
+    pub key: String,
+    pub key_format: Option<String>,
+    pub iv: Option<String>,
+    pub iv_format: Option<String>,
+    pub data: String,
+    pub data_format: Option<String>,
+    pub aad: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SymmetricResult {
+    pub output: BytesView,
+    pub base64: String,
+    pub tag: Option<String>,           // GCM 加密得到的认证标签 (hex)
+    pub error: Option<SymmetricError>, // 填充或认证校验失败, 此时 output 为未去填充 / 未认证的数据
+}
+
+/// kind: `padding` 或 `auth`
+#[derive(Serialize, Debug, PartialEq)]
+pub struct SymmetricError {
+    pub kind: String,
+    pub message: String,
+}
+
+#[tauri::command]
+pub fn symmetric_encrypt(params: SymmetricParams) -> Result<SymmetricResult, String> {
+    run_symmetric(&params, true)
+}
+
+#[tauri::command]
+pub fn symmetric_decrypt(params: SymmetricParams) -> Result<SymmetricResult, String> {
+    run_symmetric(&params, false)
+}
+
+pub fn run_symmetric(params: &SymmetricParams, encrypt: bool) -> Result<SymmetricResult, String> {
+    let algorithm = params.algorithm.trim().to_ascii_lowercase();
+    if !ALGORITHMS.contains(&algorithm.as_str()) {
+        return Err(format!("未知的算法: {}", params.algorithm));
+    }
+    let data = decode_data(params, encrypt)?;
+    let key = decode_sized(&params.key, params.key_format.as_deref(), "密钥", |len| {
+        key_len_ok(&algorithm, len)
+    })?;
+    if !key_len_ok(&algorithm, key.len()) {
+        return Err(format!("{} 不支持 {} 字节的密钥", algorithm, key.len()));
+    }
+
+    let mut result = SymmetricResult {
+        output: render_bytes(&[]),
+        base64: String::new(),
+        tag: None,
+        error: None,
+    };
+    let output = match algorithm.as_str() {
+        "rc4" => keystream(&key, None, 1, 0, data.len())
+            .iter()
+            .zip(&data)
+            .map(|(k, b)| k ^ b)
+            .collect(),
+        "salsa20" | "chacha20" => {
+            let nonce = decode_iv(params, "nonce", |len| nonce_len_ok(&algorithm, len))?;
+            stream_cipher(&algorithm, &key, &nonce, data)?
+        }
+        _ => {
+            let mode = params
+                .mode
+                .as_deref()
+                .map(|m| m.trim().to_ascii_lowercase())
+                .ok_or("分组密码需要指定工作模式")?;
+            if mode == "gcm" {
+                gcm(params, &algorithm, &key, data, encrypt, &mut result)?
+            } else {
+                let cipher = block_cipher(&algorithm, &key)?;
+                block_mode(params, cipher.as_ref(), &mode, data, encrypt, &mut result)?
+            }
+        }
+    };
+    result.base64 = general_purpose::STANDARD.encode(&output);
+    result.output = render_bytes(&output);
+    Ok(result)
+}
+
+// ===================== 输入解析 =====================
+
+fn decode_data(params: &SymmetricParams, encrypt: bool) -> Result<Vec<u8>, String> {
+    let data = &params.data;
+    match params.data_format.as_deref().unwrap_or("auto") {
+        "auto" if encrypt => parse_text_or_bytes(data),
+        "auto" => parse_bytes(data),
+        format => decode_format(data, format),
+    }
+    .map_err(|e| format!("数据格式错误: {}", e))
+}
+
+fn decode_iv(
+    params: &SymmetricParams,
+    what: &str,
+    accept: impl Fn(usize) -> bool,
+) -> Result<Vec<u8>, String> {
+    let iv = params
+        .iv
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| format!("缺少 {}", what))?;
+    let bytes = decode_sized(iv, params.iv_format.as_deref(), what, &accept)?;
+    if !accept(bytes.len()) {
+        return Err(format!("{} 长度 {} 字节不符合要求", what, bytes.len()));
+    }
+    Ok(bytes)
+}
+
+/// auto 时依次尝试 hex、UTF-8、Base64, 取第一个长度可用的结果; 带引号或前缀时按 parse_bytes
+fn decode_sized(
+    s: &str,
+    format: Option<&str>,
+    what: &str,
+    accept: impl Fn(usize) -> bool,
+) -> Result<Vec<u8>, String> {
+    let format = format.unwrap_or("auto");
+    if format != "auto" {
+        return decode_format(s, format).map_err(|e| format!("{}格式错误: {}", what, e));
+    }
+    let t = s.trim();
+    let explicit = parse_text_or_bytes(t).map_err(|e| format!("{}格式错误: {}", what, e))?;
+    if explicit != t.as_bytes() {
+        return Ok(explicit);
+    }
+    let compact: String = t.chars().filter(|c| !c.is_whitespace()).collect();
+    let candidates = [
+        hex::decode(compact.trim_start_matches("0x")).ok(),
+        Some(t.as_bytes().to_vec()),
+        general_purpose::STANDARD.decode(&compact).ok(),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|b| accept(b.len()))
+        .ok_or_else(|| format!("{}长度不符合算法要求", what))
+}
+
+fn decode_format(s: &str, format: &str) -> Result<Vec<u8>, String> {
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    match format {
+        "hex" => hex::decode(compact.trim_start_matches("0x"))
+            .map_err(|_| format!("无效十六进制: {}", s.trim())),
+        "base64" => general_purpose::STANDARD
+            .decode(&compact)
+            .map_err(|_| format!("无效 Base64: {}", s.trim())),
+        "utf8" => Ok(s.as_bytes().to_vec()),
+        other => Err(format!("未知的输入格式: {}", other)),
+    }
+}
+
+fn key_len_ok(algorithm: &str, len: usize) -> bool {
+    match algorithm {
+        "aes" => matches!(len, 16 | 24 | 32),
+        "des" => len == 8,
+        "3des" => matches!(len, 16 | 24),
+        "blowfish" => (4..=56).contains(&len),
+        "sm4" => len == 16,
+        "rc4" => (1..=256).contains(&len),
+        "salsa20" | "chacha20" => len == 32,
+        _ => false,
+    }
+}
+
+fn nonce_len_ok(algorithm: &str, len: usize) -> bool {
+    match algorithm {
+        "salsa20" => matches!(len, 8 | 24),
+        _ => matches!(len, 8 | 12 | 24),
+    }
+}
+
+// ===================== 分组密码 =====================
+
+/// 单块加解密, 供自实现的各工作模式使用
+trait RawBlock {
+    fn size(&self) -> usize;
+    fn encrypt(&self, block: &mut [u8]);
+    fn decrypt(&self, block: &mut [u8]);
+}
+
+impl<C: BlockEncrypt + BlockDecrypt> RawBlock for C {
+    fn size(&self) -> usize {
+        C::block_size()
+    }
+
+    fn encrypt(&self, block: &mut [u8]) {
+        self.encrypt_block(Block::<C>::from_mut_slice(block));
+    }
+
+    fn decrypt(&self, block: &mut [u8]) {
+        self.decrypt_block(Block::<C>::from_mut_slice(block));
+    }
+}
+
+impl RawBlock for Sm4 {
+    fn size(&self) -> usize {
+        16
+    }
+
+    fn encrypt(&self, block: &mut [u8]) {
+        self.encrypt_block(block);
+    }
+
+    fn decrypt(&self, block: &mut [u8]) {
+        self.decrypt_block(block);
+    }
+}
+
+fn block_cipher(algorithm: &str, key: &[u8]) -> Result<Box<dyn RawBlock>, String> {
+    fn boxed<C: RawBlock + KeyInit + 'static>(key: &[u8]) -> Result<Box<dyn RawBlock>, String> {
+        let cipher = C::new_from_slice(key).map_err(|_| "密钥长度无效".to_string())?;
+        Ok(Box::new(cipher))
+    }
+    match (algorithm, key.len()) {
+        ("aes", 16) => boxed::<Aes128>(key),
+        ("aes", 24) => boxed::<Aes192>(key),
+        ("aes", 32) => boxed::<Aes256>(key),
+        ("des", _) => boxed::<Des>(key),
+        ("3des", 16) => boxed::<TdesEde2>(key),
+        ("3des", _) => boxed::<TdesEde3>(key),
+        ("blowfish", _) => boxed::<Blowfish>(key),
+        ("sm4", 16) => Ok(Box::new(Sm4::new(key.try_into().unwrap()))),
+        (other, _) => Err(format!("未知的算法: {}", other)),
+    }
+}
+
+fn block_mode(
+    params: &SymmetricParams,
+    cipher: &dyn RawBlock,
+    mode: &str,
+    mut data: Vec<u8>,
+    encrypt: bool,
+    result: &mut SymmetricResult,
+) -> Result<Vec<u8>, String> {
+    let bs = cipher.size();
+    let padding = params.padding.as_deref().unwrap_or("pkcs7");
+    if !matches!(padding, "pkcs7" | "zero" | "none") {
+        return Err(format!("未知的填充方式: {}", padding));
+    }
+    let iv = match mode {
+        "ecb" => Vec::new(),
+        "ctr" => decode_iv(params, "IV", |len| (1..=bs).contains(&len))?,
+        "cbc" | "cfb" | "ofb" => decode_iv(params, "IV", |len| len == bs)?,
+        other => return Err(format!("未知的工作模式: {}", other)),
+    };
+    match mode {
+        "ecb" | "cbc" => {
+            if encrypt {
+                data = match padding {
+                    "pkcs7" => pkcs7_pad(&data, bs),
+                    "zero" => {
+                        let len = data.len().next_multiple_of(bs);
+                        data.resize(len, 0);
+                        data
+                    }
+                    _ => data,
+                };
+            }
+            if data.is_empty() || !data.len().is_multiple_of(bs) {
+                return Err(format!("数据长度须为 {} 字节的整数倍", bs));
+            }
+            if mode == "ecb" {
+                ecb(cipher, &mut data, encrypt);
+            } else {
+                cbc(cipher, &iv, &mut data, encrypt);
+            }
+            if !encrypt {
+                match padding {
+                    "pkcs7" => match unpad_pkcs7(&data, bs) {
+                        Ok(body) => data.truncate(body.len()),
+                        Err(message) => {
+                            result.error = Some(SymmetricError {
+                                kind: "padding".into(),
+                                message,
+                            })
+                        }
+                    },
+                    "zero" => {
+                        let len = data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+                        data.truncate(len);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        "cfb" => cfb(cipher, &iv, &mut data, encrypt),
+        "ofb" => ofb(cipher, &iv, &mut data),
+        _ => ctr(cipher, &iv, &mut data),
+    }
+    Ok(data)
+}
+
+fn ecb(cipher: &dyn RawBlock, data: &mut [u8], encrypt: bool) {
+    for block in data.chunks_mut(cipher.size()) {
+        if encrypt {
+            cipher.encrypt(block);
+        } else {
+            cipher.decrypt(block);
+        }
+    }
+}
+
+fn cbc(cipher: &dyn RawBlock, iv: &[u8], data: &mut [u8], encrypt: bool) {
+    let mut prev = iv.to_vec();
+    for block in data.chunks_mut(cipher.size()) {
+        if encrypt {
+            xor_into(block, &prev);
+            cipher.encrypt(block);
+            prev.copy_from_slice(block);
+        } else {
+            let saved = block.to_vec();
+            cipher.decrypt(block);
+            xor_into(block, &prev);
+            prev = saved;
+        }
+    }
+}
+
+/// 整块反馈的 CFB (AES 即 CFB-128), 末块可以不完整
+fn cfb(cipher: &dyn RawBlock, iv: &[u8], data: &mut [u8], encrypt: bool) {
+    let mut reg = iv.to_vec();
+    for block in data.chunks_mut(cipher.size()) {
+        cipher.encrypt(&mut reg);
+        let saved = block.to_vec();
+        xor_into(block, &reg);
+        let feedback = if encrypt { &*block } else { &saved };
+        reg[..feedback.len()].copy_from_slice(feedback);
+    }
+}
+
+fn ofb(cipher: &dyn RawBlock, iv: &[u8], data: &mut [u8]) {
+    let mut reg = iv.to_vec();
+    for block in data.chunks_mut(cipher.size()) {
+        cipher.encrypt(&mut reg);
+        xor_into(block, &reg);
+    }
+}
+
+/// IV 不足一块时视为 nonce, 后接全零计数器 (与 PyCryptodome 的 nonce 参数一致); 整块按大端自增
+fn ctr(cipher: &dyn RawBlock, iv: &[u8], data: &mut [u8]) {
+    let mut counter = iv.to_vec();
+    counter.resize(cipher.size(), 0);
+    for block in data.chunks_mut(cipher.size()) {
+        let mut stream = counter.clone();
+        cipher.encrypt(&mut stream);
+        xor_into(block, &stream);
+        for b in counter.iter_mut().rev() {
+            *b = b.wrapping_add(1);
+            if *b != 0 {
+                break;
+            }
+        }
+    }
+}
+
+fn xor_into(block: &mut [u8], stream: &[u8]) {
+    for (b, s) in block.iter_mut().zip(stream) {
+        *b ^= s;
+    }
+}
+
+// ===================== GCM =====================
+
+fn gcm(
+    params: &SymmetricParams,
+    algorithm: &str,
+    key: &[u8],
+    mut data: Vec<u8>,
+    encrypt: bool,
+    result: &mut SymmetricResult,
+) -> Result<Vec<u8>, String> {
+    if algorithm != "aes" {
+        return Err("GCM 模式仅支持 AES".into());
+    }
+    let nonce = decode_iv(params, "nonce", |len| len == 12)?;
+    let aad = match params.aad.as_deref().filter(|s| !s.is_empty()) {
+        Some(aad) => parse_text_or_bytes(aad).map_err(|e| format!("AAD 格式错误: {}", e))?,
+        None => Vec::new(),
+    };
+    if !encrypt {
+        if let Some(tag) = params.tag.as_deref().filter(|s| !s.trim().is_empty()) {
+            data.extend(parse_bytes(tag).map_err(|e| format!("tag 格式错误: {}", e))?);
+        }
+        if data.len() < GCM_TAG_LEN {
+            return Err(format!("GCM 密文至少需要 {} 字节的 tag", GCM_TAG_LEN));
+        }
+    }
+    let payload = Payload {
+        msg: &data,
+        aad: &aad,
+    };
+    let sealed = match key.len() {
+        16 => gcm_apply::<Aes128>(key, &nonce, payload, encrypt),
+        24 => gcm_apply::<Aes192>(key, &nonce, payload, encrypt),
+        _ => gcm_apply::<Aes256>(key, &nonce, payload, encrypt),
+    };
+    if encrypt {
+        let out = sealed.ok_or("GCM 加密失败")?;
+        result.tag = Some(hex::encode(&out[out.len() - GCM_TAG_LEN..]));
+        return Ok(out);
+    }
+    if let Some(plain) = sealed {
+        return Ok(plain);
+    }
+    // 认证失败时仍给出 CTR 解密结果 (计数器从 nonce||00000002 开始), 便于分析 nonce 重用等问题
+    result.error = Some(SymmetricError {
+        kind: "auth".into(),
+        message: "GCM 认证失败: tag 不匹配".into(),
+    });
+    let cipher = block_cipher(algorithm, key)?;
+    let mut counter = nonce;
+    counter.extend([0, 0, 0, 2]);
+    data.truncate(data.len() - GCM_TAG_LEN);
+    ctr(cipher.as_ref(), &counter, &mut data);
+    Ok(data)
+}
+
+fn gcm_apply<C>(key: &[u8], nonce: &[u8], payload: Payload, encrypt: bool) -> Option<Vec<u8>>
+where
+    C: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + KeyInit,
+{
+    let aead = AesGcm::<C, U12>::new_from_slice(key).ok()?;
+    let nonce = aes_gcm::Nonce::<U12>::from_slice(nonce);
+    if encrypt {
+        aead.encrypt(nonce, payload).ok()
+    } else {
+        aead.decrypt(nonce, payload).ok()
+    }
+}
+
+// ===================== 流密码 =====================
+
+/// nonce 长度决定变体: Salsa20 8 / XSalsa20 24; ChaCha20 8 (原始版) / 12 (RFC 8439) / 24 (XChaCha20)
+fn stream_cipher(
+    algorithm: &str,
+    key: &[u8],
+    nonce: &[u8],
+    mut data: Vec<u8>,
+) -> Result<Vec<u8>, String> {
+    fn apply<C: KeyIvInit + StreamCipher>(key: &[u8], nonce: &[u8], data: &mut [u8]) {
+        C::new_from_slices(key, nonce)
+            .expect("密钥与 nonce 长度已校验")
+            .apply_keystream(data);
+    }
+    match (algorithm, nonce.len()) {
+        ("salsa20", 8) => apply::<Salsa20>(key, nonce, &mut data),
+        ("salsa20", _) => apply::<XSalsa20>(key, nonce, &mut data),
+        (_, 8) => apply::<ChaCha20Legacy>(key, nonce, &mut data),
+        (_, 12) => apply::<ChaCha20>(key, nonce, &mut data),
+        _ => apply::<XChaCha20>(key, nonce, &mut data),
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAG: &str = "flag{symmetric_workbench}";
+
+    fn params(algorithm: &str, mode: &str, key: &str, iv: &str, data: &str) -> SymmetricParams {
+        SymmetricParams {
+            algorithm: algorithm.into(),
+            mode: Some(mode.into()).filter(|m: &String| !m.is_empty()),
+            key: key.into(),
+            iv: Some(iv.into()).filter(|v: &String| !v.is_empty()),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 已知答案测试: 无填充加密 plain 得到 cipher, 再解密回 plain (均为 hex)
+    fn known_answer(mut p: SymmetricParams, plain: &str, cipher: &str) {
+        p.padding = Some("none".into());
+        p.data_format = Some("hex".into());
+        p.data = plain.into();
+        let enc = run_symmetric(&p, true).unwrap();
+        assert_eq!(enc.output.hex, cipher, "{} 加密", p.algorithm);
+        p.data = cipher.into();
+        let dec = run_symmetric(&p, false).unwrap();
+        assert_eq!(dec.output.hex, plain, "{} 解密", p.algorithm);
+    }
+
+    #[test]
+    fn test_block_ciphers_and_modes() {
+        // NIST SP 800-38A F.2.1 / F.3.13 / F.4.1 / F.5.1 (AES-128)
+        let key = "2b7e151628aed2a6abf7158809cf4f3c";
+        let iv = "000102030405060708090a0b0c0d0e0f";
+        let plain = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51";
+        known_answer(
+            params("aes", "cbc", key, iv, ""),
+            plain,
+            "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2",
+        );
+        known_answer(
+            params("aes", "cfb", key, iv, ""),
+            plain,
+            "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b",
+        );
+        known_answer(
+            params("aes", "ofb", key, iv, ""),
+            plain,
+            "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed825",
+        );
+        known_answer(
+            params("aes", "ctr", key, "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff", ""),
+            plain,
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff",
+        );
+
+        // FIPS 81 附录 B: "Now is the time for all "
+        let plain = "4e6f77206973207468652074696d6520666f7220616c6c20";
+        known_answer(
+            params("des", "ecb", "0123456789abcdef", "", ""),
+            plain,
+            "3fa40e8a984d48156a271787ab8883f9893d51ec4b563b53",
+        );
+        known_answer(
+            params("des", "cbc", "0123456789abcdef", "1234567890abcdef", ""),
+            plain,
+            "e5c7cdde872bf27c43e934008c389c0f683788499a7c05f6",
+        );
+
+        // NIST SP 800-67 Rev.1 示例: "The qufck brown fox jump"
+        known_answer(
+            params(
+                "3des",
+                "ecb",
+                "0123456789abcdef23456789abcdef01456789abcdef0123",
+                "",
+                "",
+            ),
+            "54686520717566636b2062726f776e20666f78206a756d70",
+            "a826fd8ce53b855fcce21c8112256fe668d5c05dd9b6b900",
+        );
+
+        // Eric Young 的 Blowfish 测试向量: "7654321 Now is the time for " + 0x00
+        let bf_key = "0123456789abcdeff0e1d2c3b4a59687";
+        let bf_iv = "fedcba9876543210";
+        known_answer(
+            params("blowfish", "ecb", "0000000000000000", "", ""),
+            "0000000000000000",
+            "4ef997456198dd78",
+        );
+        known_answer(
+            params("blowfish", "cbc", bf_key, bf_iv, ""),
+            "37363534333231204e6f77206973207468652074696d6520666f722000000000",
+            "6b77b4d63006dee605b156e27403979358deb9e7154616d959f1652bd5ff92cc",
+        );
+        known_answer(
+            params("blowfish", "cfb", bf_key, bf_iv, ""),
+            "37363534333231204e6f77206973207468652074696d6520666f722000",
+            "e73214a2822139caf26ecf6d2eb9e76e3da3de04d1517200519d57a6c3",
+        );
+        known_answer(
+            params("blowfish", "ofb", bf_key, bf_iv, ""),
+            "37363534333231204e6f77206973207468652074696d6520666f722000",
+            "e73214a2822139ca62b343cc5b65587310dd908d0c241b2263c2cf80da",
+        );
+
+        // GB/T 32907-2016 附录 A 示例 1
+        known_answer(
+            params("sm4", "ecb", "0123456789abcdeffedcba9876543210", "", ""),
+            "0123456789abcdeffedcba9876543210",
+            "681edf34d206965e86b3e94f536e4246",
+        );
+    }
+
+    #[test]
+    fn test_gcm_and_stream_ciphers() {
+        // McGrew–Viega GCM 规范 (NIST SP 800-38D 引用) Test Case 4: AES-128, 96 位 IV, 带 AAD
+        let plain = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+                     1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+        let cipher = "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+                      21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091";
+        let tag = "5bc94fbc3221a5db94fae95ae7121a47";
+        let mut p = params(
+            "aes",
+            "gcm",
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            plain,
+        );
+        p.data_format = Some("hex".into());
+        p.aad = Some("hex:feedfacedeadbeeffeedfacedeadbeefabaddad2".into());
+        let enc = run_symmetric(&p, true).unwrap();
+        assert_eq!(enc.output.hex, format!("{}{}", cipher, tag));
+        assert_eq!(enc.tag.as_deref(), Some(tag));
+        // tag 附在密文末尾或单独给出都可以
+        p.data = enc.output.hex;
+        let dec = run_symmetric(&p, false).unwrap();
+        assert_eq!(dec.output.hex, plain);
+        assert!(dec.error.is_none());
+        p.data = cipher.into();
+        p.tag = Some(tag.into());
+        let dec = run_symmetric(&p, false).unwrap();
+        assert_eq!(dec.output.hex, plain);
+        // AAD 不符时认证失败, 但仍给出未认证的明文
+        p.aad = Some("forged".into());
+        let dec = run_symmetric(&p, false).unwrap();
+        assert_eq!(dec.error.unwrap().kind, "auth");
+        assert_eq!(dec.output.hex, plain);
+
+        let res = run_symmetric(&params("rc4", "", "Key", "", "Plaintext"), true).unwrap();
+        assert_eq!(res.output.hex, "bbf316e8d940af0ad3");
+
+        // RFC 8439 A.1 #1: 全零密钥与 nonce 的首个密钥流块 (8 字节 nonce 的原始版首块相同)
+        let zeros = "00".repeat(64);
+        let chacha_block = "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7\
+                            da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586";
+        let key = "00".repeat(32);
+        known_answer(
+            params("chacha20", "", &key, &"00".repeat(12), ""),
+            &zeros,
+            chacha_block,
+        );
+        known_answer(
+            params("chacha20", "", &key, &"00".repeat(8), ""),
+            &zeros,
+            chacha_block,
+        );
+
+        // eSTREAM Salsa20/20 Set 1 向量 0: 密钥 80 00 .. 00, IV 全零
+        let key = format!("80{}", "00".repeat(31));
+        known_answer(
+            params("salsa20", "", &key, &"00".repeat(8), ""),
+            &zeros,
+            "e3be8fdd8beca2e3ea8ef9475b29a6e7003951e1097a5c38d23b7a5fad9f6844\
+             b22c97559e2723c7cbbd3fe4fc8d9a0744652a83e72a9c461876af4d7ef1a117",
+        );
+    }
+
+    #[test]
+    fn test_padding_errors_are_structured() {
+        // 用 PKCS#7 解密 zero 填充的数据: 返回结构化错误和未去填充的明文
+        let mut p = params("aes", "ecb", "YELLOW SUBMARINE", "", FLAG);
+        p.padding = Some("zero".into());
+        p.data = run_symmetric(&p, true).unwrap().base64;
+        p.padding = None;
+        let res = run_symmetric(&p, false).unwrap();
+        let err = res.error.unwrap();
+        assert_eq!(err.kind, "padding");
+        assert!(err.message.contains("0x00"));
+        assert_eq!(
+            res.output.hex,
+            format!("{}{}", hex::encode(FLAG), "00".repeat(7))
+        );
+
+        p.padding = Some("zero".into());
+        let res = run_symmetric(&p, false).unwrap();
+        assert_eq!(res.output.utf8.as_deref(), Some(FLAG));
+        assert!(res.error.is_none());
+
+        p.padding = Some("none".into());
+        p.data = "00".into();
+        assert!(run_symmetric(&p, false).is_err());
+        assert!(run_symmetric(&params("aes", "cbc", "short", "", FLAG), true).is_err());
+
+        // 未知的填充方式在加解密时都报错
+        p.padding = Some("iso10126".into());
+        p.data = "00".repeat(16);
+        assert!(run_symmetric(&p, true).is_err());
+        assert!(run_symmetric(&p, false).is_err());
+    }
+}
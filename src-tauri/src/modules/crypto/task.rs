@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tauri::State;
+
+// ===================== 状态 =====================
+
+/// 可取消的长时间密码学任务, 按任务类型登记 (同类任务同时只允许运行一个)
+#[derive(Default)]
+pub struct CryptoTasks(pub Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>);
+
+/// 运行中任务的句柄, 析构时自动注销
+pub struct TaskHandle {
+    kind: String,
+    flag: Arc<AtomicBool>,
+    registry: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+impl CryptoTasks {
+    pub fn begin(&self, kind: &str) -> Result<TaskHandle, String> {
+        let mut lock = self.0.lock().map_err(|_| "任务状态损坏")?;
+        if lock.contains_key(kind) {
+            return Err("已有任务运行中".into());
+        }
+        let flag = Arc::new(AtomicBool::new(false));
+        lock.insert(kind.to_string(), flag.clone());
+        Ok(TaskHandle {
+            kind: kind.to_string(),
+            flag,
+            registry: self.0.clone(),
+        })
+    }
+
+    pub fn cancel(&self, kind: &str) -> Result<(), String> {
+        let lock = self.0.lock().map_err(|_| "任务状态损坏")?;
+        match lock.get(kind) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                Ok(())
+            }
+            None => Err("没有运行中的任务".into()),
+        }
+    }
+}
+
+impl TaskHandle {
+    /// 取消标志, 计算代码应定期检查
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        self.flag.clone()
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        if let Ok(mut lock) = self.registry.lock() {
+            lock.remove(&self.kind);
+        }
+    }
+}
+
+// ===================== stop =====================
+
+#[tauri::command]
+pub fn cancel_crypto_task(state: State<'_, CryptoTasks>, kind: String) -> Result<(), String> {
+    state.cancel(&kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_lifecycle() {
+        let tasks = CryptoTasks::default();
+        let handle = tasks.begin("factor").unwrap();
+        assert!(tasks.begin("factor").is_err());
+        assert!(tasks.begin("dlog").is_ok());
+
+        tasks.cancel("factor").unwrap();
+        assert!(handle.flag().load(Ordering::Relaxed));
+
+        drop(handle);
+        assert!(tasks.begin("factor").is_ok());
+        assert!(tasks.cancel("factor").is_err());
+    }
+}
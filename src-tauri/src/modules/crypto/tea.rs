@@ -0,0 +1,383 @@
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+use super::codec::{parse_bigint, parse_bytes, render_bytes, BytesView};
+
+const DEFAULT_DELTA: u64 = 0x9E37_79B9;
+
+/// variant: tea / xtea / xxtea
+///
+/// - key 与 data 可以是字节串 (hex、b'..'、Base64) 或整数数组 (如 `{0x1234u, 5678}`),
+///   字节串按 endian (little 默认 / big) 与 word_size (32 默认 / 64) 转换为字
+/// - delta 默认 0x9E3779B9, 可写负数 (如 -0x61C88647)
+/// - rounds 对 TEA / XTEA 为循环次数 (默认 32), 对 XXTEA 为轮数 (默认 6 + 52/n)
+#[derive(Deserialize, Default)]
+pub struct TeaParams {
+    pub variant: String,
+    pub key: String,
+    pub data: String,
+    pub delta: Option<String>,
+    pub rounds: Option<u32>,
+    pub endian: Option<String>,
+    pub word_size: Option<u32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TeaResult {
+    pub output: BytesView,
+    pub words: Vec<String>, // 按字宽补零的 0x.. 形式, 便于直接粘贴回 C / Python 代码
+}
+
+#[tauri::command]
+pub fn tea_encrypt(params: TeaParams) -> Result<TeaResult, String> {
+    run_tea(&params, true)
+}
+
+#[tauri::command]
+pub fn tea_decrypt(params: TeaParams) -> Result<TeaResult, String> {
+    run_tea(&params, false)
+}
+
+/// 按字宽截断的算术, 32 位与 64 位变体共用同一套轮函数
+#[derive(Clone, Copy)]
+struct Word {
+    mask: u64,
+}
+
+impl Word {
+    fn add(self, a: u64, b: u64) -> u64 {
+        a.wrapping_add(b) & self.mask
+    }
+
+    fn sub(self, a: u64, b: u64) -> u64 {
+        a.wrapping_sub(b) & self.mask
+    }
+
+    fn mul(self, a: u64, b: u64) -> u64 {
+        a.wrapping_mul(b) & self.mask
+    }
+
+    fn shl(self, a: u64, n: u32) -> u64 {
+        (a << n) & self.mask
+    }
+}
+
+pub fn run_tea(params: &TeaParams, encrypt: bool) -> Result<TeaResult, String> {
+    let bits = params.word_size.unwrap_or(32);
+    if bits != 32 && bits != 64 {
+        return Err("字宽只支持 32 或 64".into());
+    }
+    let w = Word {
+        mask: u64::MAX >> (64 - bits),
+    };
+    let big_endian = match params.endian.as_deref().unwrap_or("little") {
+        "little" => false,
+        "big" => true,
+        other => return Err(format!("未知的字节序: {}", other)),
+    };
+    let delta = match params.delta.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(s) => parse_word(s, bits, true).map_err(|e| format!("delta 格式错误: {}", e))?,
+        None => DEFAULT_DELTA,
+    };
+    let key = parse_words(&params.key, bits, big_endian).map_err(|e| format!("密钥{}", e))?;
+    if key.len() != 4 {
+        return Err(format!("密钥须为 4 个字, 实际为 {} 个", key.len()));
+    }
+    let key: [u64; 4] = key.try_into().unwrap();
+    let mut data = parse_words(&params.data, bits, big_endian).map_err(|e| format!("数据{}", e))?;
+
+    match params.variant.as_str() {
+        "tea" | "xtea" => {
+            if data.is_empty() || data.len() % 2 != 0 {
+                return Err("TEA / XTEA 的数据须为偶数个字 (每块两个字)".into());
+            }
+            let rounds = params.rounds.unwrap_or(32);
+            let xtea = params.variant == "xtea";
+            for block in data.chunks_mut(2) {
+                let v = [block[0], block[1]];
+                let out = match (xtea, encrypt) {
+                    (false, true) => tea_encrypt_block(w, v, &key, delta, rounds),
+                    (false, false) => tea_decrypt_block(w, v, &key, delta, rounds),
+                    (true, true) => xtea_encrypt_block(w, v, &key, delta, rounds),
+                    (true, false) => xtea_decrypt_block(w, v, &key, delta, rounds),
+                };
+                block.copy_from_slice(&out);
+            }
+        }
+        "xxtea" => {
+            if data.len() < 2 {
+                return Err("XXTEA 的数据至少需要 2 个字".into());
+            }
+            let rounds = params.rounds.unwrap_or(6 + 52 / data.len() as u32);
+            if encrypt {
+                xxtea_encrypt(w, &mut data, &key, delta, rounds);
+            } else {
+                xxtea_decrypt(w, &mut data, &key, delta, rounds);
+            }
+        }
+        other => return Err(format!("未知的算法: {}", other)),
+    }
+
+    let width = bits as usize / 4;
+    Ok(TeaResult {
+        output: render_bytes(&words_to_bytes(&data, bits, big_endian)),
+        words: data
+            .iter()
+            .map(|v| format!("0x{:0width$x}", v, width = width))
+            .collect(),
+    })
+}
+
+fn tea_encrypt_block(w: Word, v: [u64; 2], k: &[u64; 4], delta: u64, rounds: u32) -> [u64; 2] {
+    let [mut v0, mut v1] = v;
+    let mut sum = 0;
+    for _ in 0..rounds {
+        sum = w.add(sum, delta);
+        v0 = w.add(v0, tea_f(w, v1, sum, k[0], k[1]));
+        v1 = w.add(v1, tea_f(w, v0, sum, k[2], k[3]));
+    }
+    [v0, v1]
+}
+
+fn tea_decrypt_block(w: Word, v: [u64; 2], k: &[u64; 4], delta: u64, rounds: u32) -> [u64; 2] {
+    let [mut v0, mut v1] = v;
+    let mut sum = w.mul(delta, rounds as u64);
+    for _ in 0..rounds {
+        v1 = w.sub(v1, tea_f(w, v0, sum, k[2], k[3]));
+        v0 = w.sub(v0, tea_f(w, v1, sum, k[0], k[1]));
+        sum = w.sub(sum, delta);
+    }
+    [v0, v1]
+}
+
+fn tea_f(w: Word, v: u64, sum: u64, ka: u64, kb: u64) -> u64 {
+    w.add(w.shl(v, 4), ka) ^ w.add(v, sum) ^ w.add(v >> 5, kb)
+}
+
+fn xtea_encrypt_block(w: Word, v: [u64; 2], k: &[u64; 4], delta: u64, rounds: u32) -> [u64; 2] {
+    let [mut v0, mut v1] = v;
+    let mut sum = 0;
+    for _ in 0..rounds {
+        v0 = w.add(v0, xtea_f(w, v1, sum, k[(sum & 3) as usize]));
+        sum = w.add(sum, delta);
+        v1 = w.add(v1, xtea_f(w, v0, sum, k[((sum >> 11) & 3) as usize]));
+    }
+    [v0, v1]
+}
+
+fn xtea_decrypt_block(w: Word, v: [u64; 2], k: &[u64; 4], delta: u64, rounds: u32) -> [u64; 2] {
+    let [mut v0, mut v1] = v;
+    let mut sum = w.mul(delta, rounds as u64);
+    for _ in 0..rounds {
+        v1 = w.sub(v1, xtea_f(w, v0, sum, k[((sum >> 11) & 3) as usize]));
+        sum = w.sub(sum, delta);
+        v0 = w.sub(v0, xtea_f(w, v1, sum, k[(sum & 3) as usize]));
+    }
+    [v0, v1]
+}
+
+fn xtea_f(w: Word, v: u64, sum: u64, k: u64) -> u64 {
+    w.add(w.shl(v, 4) ^ (v >> 5), v) ^ w.add(sum, k)
+}
+
+/// Corrected Block TEA (btea) 的 MX
+fn xxtea_mx(w: Word, sum: u64, y: u64, z: u64, p: usize, e: usize, k: &[u64; 4]) -> u64 {
+    w.add((z >> 5) ^ w.shl(y, 2), (y >> 3) ^ w.shl(z, 4)) ^ w.add(sum ^ y, k[(p & 3) ^ e] ^ z)
+}
+
+fn xxtea_encrypt(w: Word, v: &mut [u64], k: &[u64; 4], delta: u64, rounds: u32) {
+    let n = v.len();
+    let mut sum = 0;
+    let mut z = v[n - 1];
+    for _ in 0..rounds {
+        sum = w.add(sum, delta);
+        let e = ((sum >> 2) & 3) as usize;
+        for p in 0..n {
+            let y = v[(p + 1) % n];
+            v[p] = w.add(v[p], xxtea_mx(w, sum, y, z, p, e, k));
+            z = v[p];
+        }
+    }
+}
+
+fn xxtea_decrypt(w: Word, v: &mut [u64], k: &[u64; 4], delta: u64, rounds: u32) {
+    let n = v.len();
+    let mut sum = w.mul(delta, rounds as u64);
+    let mut y = v[0];
+    for _ in 0..rounds {
+        let e = ((sum >> 2) & 3) as usize;
+        for p in (0..n).rev() {
+            let z = v[(p + n - 1) % n];
+            v[p] = w.sub(v[p], xxtea_mx(w, sum, y, z, p, e, k));
+            y = v[p];
+        }
+        sum = w.sub(sum, delta);
+    }
+}
+
+// ===================== 输入输出 =====================
+
+/// 含逗号或括号 (且不是引号字面量) 时按整数数组解析 (允许 C 风格的 u / L 后缀), 否则按字节串解析后分组成字
+fn parse_words(s: &str, bits: u32, big_endian: bool) -> Result<Vec<u64>, String> {
+    let s = s.trim();
+    let quoted = s.trim_start_matches(['b', 'B']).starts_with(['\'', '"']);
+    if !quoted && s.contains([',', '[', '{']) {
+        return s
+            .split([',', '[', ']', '{', '}'])
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| parse_word(t.trim_end_matches(['u', 'U', 'l', 'L']), bits, false))
+            .collect();
+    }
+    let bytes = parse_bytes(s).map_err(|e| format!("格式错误: {}", e))?;
+    let size = bits as usize / 8;
+    if !bytes.len().is_multiple_of(size) {
+        return Err(format!("长度须为 {} 字节的整数倍", size));
+    }
+    Ok(bytes
+        .chunks(size)
+        .map(|chunk| {
+            let fold = |acc: u64, &b: &u8| (acc << 8) | b as u64;
+            if big_endian {
+                chunk.iter().fold(0, fold)
+            } else {
+                chunk.iter().rev().fold(0, fold)
+            }
+        })
+        .collect())
+}
+
+/// 解析单个字; allow_negative 时负数按 2^bits 取模 (用于 delta)
+fn parse_word(s: &str, bits: u32, allow_negative: bool) -> Result<u64, String> {
+    let value = parse_bigint(s)?;
+    if value.is_negative() && !allow_negative {
+        return Err(format!("不能为负数: {}", s));
+    }
+    let modulus = BigInt::from(1u8) << bits;
+    if value.abs() >= modulus {
+        return Err(format!("{} 超出 {} 位", s.trim(), bits));
+    }
+    let value = ((value % &modulus) + &modulus) % &modulus;
+    Ok(value.to_u64().unwrap())
+}
+
+fn words_to_bytes(words: &[u64], bits: u32, big_endian: bool) -> Vec<u8> {
+    let size = bits as usize / 8;
+    words
+        .iter()
+        .flat_map(|v| {
+            let be = v.to_be_bytes();
+            let mut word = be[8 - size..].to_vec();
+            if !big_endian {
+                word.reverse();
+            }
+            word
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(variant: &str, key: &str, data: &str) -> TeaParams {
+        TeaParams {
+            variant: variant.into(),
+            key: key.into(),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 加密得到期望的字, 再以字数组形式解密回原数据
+    fn roundtrip(p: TeaParams, expected: &[&str]) {
+        let enc = run_tea(&p, true).unwrap();
+        assert_eq!(enc.words, expected, "{}", p.variant);
+        let big_endian = p.endian.as_deref() == Some("big");
+        let plain = words_to_bytes(
+            &parse_words(&p.data, 32, big_endian).unwrap(),
+            32,
+            big_endian,
+        );
+        let p = TeaParams {
+            data: format!("[{}]", expected.join(", ")),
+            ..p
+        };
+        assert_eq!(run_tea(&p, false).unwrap().output.hex, hex::encode(plain));
+    }
+
+    #[test]
+    fn test_published_vectors() {
+        // 全零密钥与明文 (TEA 参考实现 / Crypto++ 向量)
+        roundtrip(
+            params("tea", "{0, 0, 0, 0}", "{0, 0}"),
+            &["0x41ea3a0a", "0x94baa940"],
+        );
+        // Crypto++ XTEA 向量, 大端
+        let mut p = params(
+            "xtea",
+            "000102030405060708090a0b0c0d0e0f",
+            "4142434445464748",
+        );
+        p.endian = Some("big".into());
+        roundtrip(p, &["0x497df3d0", "0x72612cb5"]);
+        roundtrip(
+            params("xxtea", "[0x0u, 0x0u, 0x0u, 0x0u]", "[0, 0]"),
+            &["0x053704ab", "0x575d8c80"],
+        );
+    }
+
+    #[test]
+    fn test_custom_delta_rounds_and_word_size() {
+        let mut p = params("xxtea", "b'flag_key_1234567'", "b'flag{tea_family_ok!}'");
+        p.delta = Some("-0x61C88647".into()); // 与 0x9E3779B9 等价
+        let same = run_tea(&p, true).unwrap();
+        p.delta = None;
+        assert_eq!(run_tea(&p, true).unwrap().words, same.words);
+
+        for variant in ["tea", "xtea", "xxtea"] {
+            let mut p = params(
+                variant,
+                "00112233445566778899aabbccddeeff",
+                "b'sixteen bytes!!!'",
+            );
+            p.delta = Some("0x12345678".into());
+            p.rounds = Some(16);
+            p.endian = Some("big".into());
+            let enc = run_tea(&p, true).unwrap();
+            p.data = enc.output.hex;
+            let dec = run_tea(&p, false).unwrap();
+            assert_eq!(
+                dec.output.utf8.as_deref(),
+                Some("sixteen bytes!!!"),
+                "{}",
+                variant
+            );
+
+            p.word_size = Some(64);
+            p.key = hex::encode([7u8; 32]);
+            p.data = "b'64-bit words, two blocks here!!!'".into();
+            p.delta = Some("0x9E3779B97F4A7C15".into());
+            let enc = run_tea(&p, true).unwrap();
+            assert_eq!(enc.words[0].len(), 18);
+            p.data = enc.output.hex;
+            let dec = run_tea(&p, false).unwrap();
+            assert_eq!(
+                dec.output.utf8.as_deref(),
+                Some("64-bit words, two blocks here!!!"),
+                "{}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn test_input_errors() {
+        assert!(run_tea(&params("tea", "[1, 2, 3]", "[0, 0]"), true).is_err());
+        assert!(run_tea(&params("tea", "[1, 2, 3, 4]", "[0, 0, 0]"), true).is_err());
+        assert!(run_tea(&params("xtea", "[1, 2, 3, 0x100000000]", "[0, 0]"), true).is_err());
+        assert!(run_tea(&params("tea", "[1, 2, 3, 4]", "0011223344"), true).is_err());
+        assert!(run_tea(&params("rc5", "[1, 2, 3, 4]", "[0, 0]"), true).is_err());
+    }
+}
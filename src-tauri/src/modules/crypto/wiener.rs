@@ -0,0 +1,428 @@
+use std::collections::BTreeMap;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use serde::Serialize;
+
+use super::codec::{parse_biguint, render_number, NumberView};
+use super::lattice::{determinant, integer_roots, lll_reduce, primitive_part};
+
+#[derive(Serialize, Debug)]
+pub struct SmallDResult {
+    pub method: String, // "Wiener" | "Boneh-Durfee"
+    pub p: String,
+    pub q: String,
+    pub d: String,
+    pub plaintext: NumberView,
+}
+
+// Boneh–Durfee 参数表 (δ, m), 依次尝试; m 越大格维数越高, 能力越强但越慢
+const BONEH_DURFEE_PARAMS: [(f64, u32); 3] = [(0.26, 4), (0.28, 5), (0.29, 6)];
+
+#[tauri::command]
+pub fn small_private_exponent_attack(
+    n: String,
+    e: String,
+    c: String,
+) -> Result<SmallDResult, String> {
+    let n = parse_biguint(&n)?;
+    let e = parse_biguint(&e)?;
+    let c = parse_biguint(&c)?;
+
+    if n <= BigUint::one() {
+        return Err("N 必须大于 1".into());
+    }
+
+    let (method, p, q) = match wiener_attack(&n, &e) {
+        Some((p, q)) => ("Wiener", p, q),
+        None => {
+            let (p, q) = boneh_durfee_attack(&n, &e)
+                .ok_or("Wiener 与 Boneh-Durfee 均未找到私钥 (d 可能不够小)")?;
+            ("Boneh-Durfee", p, q)
+        }
+    };
+
+    let phi = (&p - BigUint::one()) * (&q - BigUint::one());
+    let d = e.modinv(&phi).ok_or("e 与 phi 不互质")?;
+    let m = c.modpow(&d, &n);
+
+    Ok(SmallDResult {
+        method: method.into(),
+        p: p.to_string(),
+        q: q.to_string(),
+        d: d.to_string(),
+        plaintext: render_number(&m),
+    })
+}
+
+// ===================== 公共工具 =====================
+
+/// 已知 p + q 时分解 N: p, q 是 x^2 - s·x + N = 0 的两根
+pub fn factor_from_sum(n: &BigUint, s: &BigUint) -> Option<(BigUint, BigUint)> {
+    let s2 = s * s;
+    let four_n = n << 2;
+    if s2 < four_n {
+        return None;
+    }
+    let disc = s2 - four_n;
+    let r = disc.sqrt();
+    if &r * &r != disc {
+        return None;
+    }
+    let p: BigUint = (s + &r) >> 1;
+    if p.is_zero() || p.is_one() || !(n % &p).is_zero() {
+        return None;
+    }
+    let q = n / &p;
+    if q.is_one() {
+        return None;
+    }
+    Some((p, q))
+}
+
+// ===================== Wiener =====================
+
+/// Wiener 连分数攻击: 在 e/N 的渐近分数 k/d 中寻找满足 ed ≡ 1 (mod φ) 的 d
+pub fn wiener_attack(n: &BigUint, e: &BigUint) -> Option<(BigUint, BigUint)> {
+    // 渐近分数递推: h_i = a_i h_{i-1} + h_{i-2}, k_i 同理
+    let (mut num, mut den) = (e.clone(), n.clone());
+    let (mut h_prev, mut h) = (BigUint::zero(), BigUint::one());
+    let (mut k_prev, mut k) = (BigUint::one(), BigUint::zero());
+
+    while !den.is_zero() {
+        let (a, r) = num.div_rem(&den);
+        let h_next = &a * &h + &h_prev;
+        let k_next = &a * &k + &k_prev;
+        h_prev = std::mem::replace(&mut h, h_next);
+        k_prev = std::mem::replace(&mut k, k_next);
+        num = std::mem::replace(&mut den, r);
+
+        // 渐近分数 h/k ≈ e/N, 其中 h 对应 k(倍数), k 对应 d
+        let (kk, d) = (&h, &k);
+        if kk.is_zero() || d.is_zero() {
+            continue;
+        }
+        let ed_1 = e * d - BigUint::one();
+        if !(&ed_1 % kk).is_zero() {
+            continue;
+        }
+        let phi = ed_1 / kk;
+        if &phi >= n {
+            continue;
+        }
+        let s = n - &phi + BigUint::one();
+        if let Some(pq) = factor_from_sum(n, &s) {
+            return Some(pq);
+        }
+    }
+    None
+}
+
+// ===================== Boneh–Durfee =====================
+
+// 三元多项式, 键为 (u, x, y) 的指数, 其中 u = xy + 1
+type Poly3 = BTreeMap<(u32, u32, u32), BigInt>;
+// 二元多项式, 键为 (x, y) 的指数
+type Poly2 = BTreeMap<(u32, u32), BigInt>;
+
+fn poly3_mul(a: &Poly3, b: &Poly3) -> Poly3 {
+    let mut out = Poly3::new();
+    for (&(u1, x1, y1), c1) in a {
+        for (&(u2, x2, y2), c2) in b {
+            *out.entry((u1 + u2, x1 + x2, y1 + y2))
+                .or_insert_with(BigInt::zero) += c1 * c2;
+        }
+    }
+    out.retain(|_, c| !c.is_zero());
+    out
+}
+
+fn binomial(n: u32, k: u32) -> BigInt {
+    (0..k).fold(BigInt::one(), |acc, i| {
+        acc * BigInt::from(n - i) / BigInt::from(i + 1)
+    })
+}
+
+/// 把 x^a y^b 中的 xy 用 u - 1 替换, 使 y-shift 多项式只含 u^i x^j 与 u^i y^j 单项式
+fn substitute_xy(poly: &Poly3) -> Poly3 {
+    let mut out = Poly3::new();
+    for (&(u, x, y), c) in poly {
+        let s = x.min(y);
+        // (u - 1)^s = Σ C(s, i) u^i (-1)^(s-i)
+        for i in 0..=s {
+            let mut coef = c * binomial(s, i);
+            if (s - i) % 2 == 1 {
+                coef = -coef;
+            }
+            *out.entry((u + i, x - s, y - s))
+                .or_insert_with(BigInt::zero) += coef;
+        }
+    }
+    out.retain(|_, c| !c.is_zero());
+    out
+}
+
+/// 回代 u = xy + 1 得到关于 (x, y) 的整系数多项式
+fn to_poly2(poly: &Poly3) -> Poly2 {
+    let mut out = Poly2::new();
+    for (&(u, x, y), c) in poly {
+        for i in 0..=u {
+            *out.entry((x + i, y + i)).or_insert_with(BigInt::zero) += c * binomial(u, i);
+        }
+    }
+    out.retain(|_, c| !c.is_zero());
+    out
+}
+
+/// 固定 y = t 后得到关于 x 的系数 (升幂)
+fn poly2_at_y(poly: &Poly2, t: &BigInt, deg_x: usize) -> Vec<BigInt> {
+    let mut coeffs = vec![BigInt::zero(); deg_x + 1];
+    for (&(x, y), c) in poly {
+        coeffs[x as usize] += c * t.pow(y);
+    }
+    coeffs
+}
+
+/// 两个一元整系数多项式按形式次数构造 Sylvester 矩阵并求结式
+fn sylvester_resultant(f: &[BigInt], g: &[BigInt]) -> BigInt {
+    let (df, dg) = (f.len() - 1, g.len() - 1);
+    let size = df + dg;
+    if size == 0 {
+        return BigInt::one();
+    }
+    let mut m = vec![vec![BigInt::zero(); size]; size];
+    for i in 0..dg {
+        for (j, c) in f.iter().rev().enumerate() {
+            m[i][i + j] = c.clone();
+        }
+    }
+    for i in 0..df {
+        for (j, c) in g.iter().rev().enumerate() {
+            m[dg + i][i + j] = c.clone();
+        }
+    }
+    determinant(&m)
+}
+
+/// 通过在 y = 0..D 处求值再插值, 计算 Res_x(f, g) 关于 y 的多项式
+fn resultant_in_y(f: &Poly2, g: &Poly2) -> Vec<BigInt> {
+    let deg =
+        |p: &Poly2, sel: fn(&(u32, u32)) -> u32| p.keys().map(sel).max().unwrap_or(0) as usize;
+    let (fx, fy) = (deg(f, |k| k.0), deg(f, |k| k.1));
+    let (gx, gy) = (deg(g, |k| k.0), deg(g, |k| k.1));
+    let total = fx * gy + gx * fy;
+
+    let values: Vec<BigInt> = (0..=total)
+        .map(|t| {
+            let t = BigInt::from(t);
+            sylvester_resultant(&poly2_at_y(f, &t, fx), &poly2_at_y(g, &t, gx))
+        })
+        .collect();
+
+    interpolate_consecutive(&values)
+}
+
+/// 已知整系数多项式在 0, 1, ..., D 处的取值, 用 Newton 前向差分还原系数
+fn interpolate_consecutive(values: &[BigInt]) -> Vec<BigInt> {
+    let degree = values.len() - 1;
+
+    // a_k = Δ^k v(0)
+    let mut diffs = values.to_vec();
+    let mut forward = Vec::with_capacity(values.len());
+    for _ in 0..=degree {
+        forward.push(diffs[0].clone());
+        diffs = diffs.windows(2).map(|w| &w[1] - &w[0]).collect();
+    }
+
+    // D!·R(y) = Σ a_k (D!/k!) y(y-1)...(y-k+1)
+    let fact = |n: usize| (1..=n).fold(BigInt::one(), |acc, i| acc * BigInt::from(i));
+    let d_fact = fact(degree);
+    let mut result = vec![BigInt::zero(); degree + 1];
+    let mut falling = vec![BigInt::one()];
+    let mut k_fact = BigInt::one();
+    for (k, a_k) in forward.iter().enumerate() {
+        if k > 0 {
+            k_fact *= BigInt::from(k);
+            let mut next = vec![BigInt::zero(); falling.len() + 1];
+            let shift = BigInt::from(k - 1);
+            for (i, c) in falling.iter().enumerate() {
+                next[i + 1] += c;
+                next[i] -= c * &shift;
+            }
+            falling = next;
+        }
+        let weight = a_k * (&d_fact / &k_fact);
+        for (i, c) in falling.iter().enumerate() {
+            result[i] += &weight * c;
+        }
+    }
+
+    result.iter().map(|c| c / &d_fact).collect()
+}
+
+/// Boneh–Durfee 攻击 (Herrmann–May 选取的 x/y-shift 格), 可突破 Wiener 的 N^0.25 界
+pub fn boneh_durfee_attack(n: &BigUint, e: &BigUint) -> Option<(BigUint, BigUint)> {
+    BONEH_DURFEE_PARAMS
+        .iter()
+        .find_map(|&(delta, m)| boneh_durfee_with(n, e, delta, m))
+}
+
+fn boneh_durfee_with(n: &BigUint, e: &BigUint, delta: f64, m: u32) -> Option<(BigUint, BigUint)> {
+    let n_int = BigInt::from_biguint(Sign::Plus, n.clone());
+    let modulus = BigInt::from_biguint(Sign::Plus, e.clone());
+    let t = ((1.0 - 2.0 * delta) * m as f64).floor() as u32;
+
+    // 根的上界: x = 2k < 2·N^δ, |y| = (p + q) / 2 ≈ N^0.5
+    let n_bits = n.bits() as f64;
+    let x_bound = BigInt::one() << ((delta * n_bits).ceil() as u64 + 1);
+    let y_bound = BigInt::one() << ((n_bits / 2.0).ceil() as u64);
+    let u_bound = &x_bound * &y_bound + BigInt::one();
+
+    // f(x, y) = 1 + x(A + y) = u + A·x, A = (N + 1) / 2
+    let a = (&n_int + BigInt::one()) >> 1;
+    let mut f = Poly3::new();
+    f.insert((1, 0, 0), BigInt::one());
+    f.insert((0, 1, 0), a);
+
+    let f_pows: Vec<Poly3> = (0..=m).fold(vec![], |mut acc: Vec<Poly3>, k| {
+        let next = if k == 0 {
+            Poly3::from([((0, 0, 0), BigInt::one())])
+        } else {
+            poly3_mul(&acc[k as usize - 1], &f)
+        };
+        acc.push(next);
+        acc
+    });
+
+    let mut shifts: Vec<((u32, u32, u32), Poly3)> = Vec::new();
+    // x-shifts: x^i · f^k · e^(m-k)
+    for k in 0..=m {
+        for i in 0..=m - k {
+            let mono = Poly3::from([((0, i, 0), modulus.pow(m - k))]);
+            shifts.push(((k, i, 0), poly3_mul(&mono, &f_pows[k as usize])));
+        }
+    }
+    // y-shifts: y^j · f^k · e^(m-k), 再把 xy 替换为 u - 1
+    if t > 0 {
+        for j in 1..=t {
+            for k in (m / t) * j..=m {
+                let mono = Poly3::from([((0, 0, j), modulus.pow(m - k))]);
+                let shifted = substitute_xy(&poly3_mul(&mono, &f_pows[k as usize]));
+                shifts.push(((k, 0, j), shifted));
+            }
+        }
+    }
+
+    let monomials: Vec<(u32, u32, u32)> = shifts.iter().map(|(lead, _)| *lead).collect();
+    let scale: Vec<BigInt> = monomials
+        .iter()
+        .map(|&(u, x, y)| u_bound.pow(u) * x_bound.pow(x) * y_bound.pow(y))
+        .collect();
+
+    let basis: Vec<Vec<BigInt>> = shifts
+        .iter()
+        .map(|(_, poly)| {
+            monomials
+                .iter()
+                .zip(scale.iter())
+                .map(|(mono, s)| poly.get(mono).map(|c| c * s).unwrap_or_else(BigInt::zero))
+                .collect()
+        })
+        .collect();
+
+    let reduced = lll_reduce(&basis).ok()?;
+
+    // 把短向量还原成多项式 (系数除以单项式的界)
+    let polys: Vec<Poly2> = reduced
+        .iter()
+        .map(|row| {
+            let mut p3 = Poly3::new();
+            for ((mono, s), v) in monomials.iter().zip(scale.iter()).zip(row.iter()) {
+                if !v.is_zero() {
+                    p3.insert(*mono, v / s);
+                }
+            }
+            to_poly2(&p3)
+        })
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    // 在前几个最短向量中寻找代数无关的一对
+    let candidates = polys.len().min(6);
+    for i in 0..candidates {
+        for j in i + 1..candidates {
+            let res = resultant_in_y(&polys[i], &polys[j]);
+            if res.iter().all(|c| c.is_zero()) {
+                continue;
+            }
+            let res = primitive_part(&res);
+            for y0 in integer_roots(&res, &(&y_bound * 2)) {
+                // y = -(p + q) / 2
+                if !y0.is_negative() {
+                    continue;
+                }
+                let s = (-y0 << 1u32).to_biguint()?;
+                if let Some(pq) = factor_from_sum(n, &s) {
+                    return Some(pq);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn big(s: &str) -> BigUint {
+        BigUint::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_wiener_attack() {
+        let p = big("7166888967401842571367062786191324427956942076801709673968243251655999745149604124750432245932976996380746993806848611243053414573902201329972261606672257");
+        let q = big("8067532295866667086991875370176513005761572864040449424025662581595701300621533658754499897271998996403634720688934692569997220130498393160511146813419971");
+        let n = &p * &q;
+        let d = big("590209488520255358040687302011");
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let e = d.modinv(&phi).unwrap();
+
+        let (rp, rq) = wiener_attack(&n, &e).expect("Wiener 应该成功");
+        assert_eq!(&rp * &rq, n);
+
+        let m = BigUint::from_bytes_be(b"flag{wiener_small_d}");
+        let c = m.modpow(&e, &n);
+        let res =
+            small_private_exponent_attack(n.to_string(), e.to_string(), c.to_string()).unwrap();
+        assert_eq!(res.method, "Wiener");
+        assert_eq!(res.d, d.to_string());
+        assert_eq!(res.plaintext.utf8.as_deref(), Some("flag{wiener_small_d}"));
+    }
+
+    #[test]
+    fn test_boneh_durfee_beyond_wiener_bound() {
+        // 256 位 N, d 约为 N^0.26, 超出 Wiener 界
+        let p = big("277876927172831748245717298040939669619");
+        let q = big("255920875196431818715858255039767293927");
+        let n = &p * &q;
+        let d = big("62342897081152097307");
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let e = d.modinv(&phi).unwrap();
+
+        assert!(wiener_attack(&n, &e).is_none());
+        let (rp, rq) = boneh_durfee_attack(&n, &e).expect("Boneh-Durfee 应该成功");
+        assert_eq!(&rp * &rq, n);
+    }
+
+    #[test]
+    fn test_large_d_fails() {
+        let p = big("277876927172831748245717298040939669619");
+        let q = big("255920875196431818715858255039767293927");
+        let n = &p * &q;
+        let res = small_private_exponent_attack(n.to_string(), "65537".into(), "12345".into());
+        assert!(res.is_err());
+    }
+}
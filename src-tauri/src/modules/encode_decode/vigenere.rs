@@ -1,11 +1,11 @@
 use serde::Serialize;
 
-const ENGLISH_IC: f64 = 0.067; // 英文标准重合指数
-const ENGLISH_FREQ: [f64; 26] = [
-    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153,
-    0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056,
-    0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
-];
+use crate::modules::crypto::language::{
+    index_of_coincidence, language_model, letters, LanguageModel,
+};
+
+const CANDIDATE_LENGTHS: usize = 4; // 按 IC 取前几个候选长度做打分比较
+const HILL_CLIMB_ROUNDS: usize = 3;
 
 #[derive(Serialize, Debug)]
 pub struct CrackResult {
@@ -13,6 +13,7 @@ pub struct CrackResult {
     key_length: usize,
     plaintext: String,
     ic_score: f64,
+    score: f64, // 明文的四元组平均 log10 概率
 }
 
 #[tauri::command]
@@ -69,12 +70,12 @@ pub fn vigenere_cipher(input: String, key: String, mode: String) -> Result<Strin
 }
 
 #[tauri::command]
-pub fn crack_vigenere_auto(ciphertext: String) -> Result<CrackResult, String> {
-    let clean_text: Vec<u8> = ciphertext
-        .chars()
-        .filter(|c| c.is_ascii_alphabetic())
-        .map(|c| c.to_ascii_uppercase() as u8)
-        .collect();
+pub fn crack_vigenere_auto(
+    ciphertext: String,
+    language: Option<String>, // 评分所用语言模型, 默认英文
+) -> Result<CrackResult, String> {
+    let model = language_model(language.as_deref())?;
+    let clean_text = letters(&ciphertext);
 
     if clean_text.len() < 20 {
         return Err("密文太短，无法进行统计分析".into());
@@ -82,120 +83,129 @@ pub fn crack_vigenere_auto(ciphertext: String) -> Result<CrackResult, String> {
 
     // 1. 计算所有候选长度的 IC 值
     let max_try_len = 20.min(clean_text.len() / 2);
+    let target_ic = model.expected_ic();
     let mut candidates: Vec<(usize, f64)> = Vec::new();
 
     for len in 1..=max_try_len {
-        let mut total_ic = 0.0;
-        for i in 0..len {
-            let group: Vec<u8> = clean_text.iter().skip(i).step_by(len).cloned().collect();
-            total_ic += calculate_ic(&group);
-        }
-        let avg_ic = total_ic / len as f64;
-        candidates.push((len, avg_ic));
+        let total_ic: f64 = (0..len)
+            .map(|i| index_of_coincidence(&column(&clean_text, i, len)))
+            .sum();
+        candidates.push((len, total_ic / len as f64));
     }
 
-    // 2. 智能选择最佳长度
-    // 策略：先按“距离英文IC(0.067)的接近程度”排序
-    // 这样，6 和 12 都会排在 3 前面 (因为 3 的 IC 只有 0.05 左右，而 6 是 0.067)
-    candidates.sort_by(|a, b| {
-        let diff_a = (a.1 - ENGLISH_IC).abs();
-        let diff_b = (b.1 - ENGLISH_IC).abs();
-        diff_a.partial_cmp(&diff_b).unwrap()
-    });
-
-    // 取出最接近的那个（可能是 6，也可能是 12）
-    let (mut best_len, best_ic) = candidates[0];
+    // 2. 按“距离该语言期望 IC 的接近程度”排序
+    // 这样，6 和 12 都会排在 3 前面 (因为 3 的 IC 只有 0.05 左右，而 6 接近 0.066)
+    candidates.sort_by(|a, b| (a.1 - target_ic).abs().total_cmp(&(b.1 - target_ic).abs()));
 
     // 3. 倍数约简 (Factor Reduction)
-    // 如果最佳长度是 12，我们要检查它的因子（如 6, 4, 3）是否也足够好。
-    // 如果因子也 > 0.060，说明 12 只是倍数，因子才是真身。
-    // 我们从小到大检查因子。
-    let mut reduced_len = best_len;
-    for check_len in 1..best_len {
-        // 如果是因子
-        if best_len % check_len == 0 {
-            // 查找这个因子的 IC
-            if let Some(&(_, factor_ic)) = candidates.iter().find(|(l, _)| *l == check_len) {
-                // 如果因子的 IC 也非常高 (说明它是单表代换)，则优先选因子
-                if factor_ic > 0.060 {
-                    reduced_len = check_len;
-                    break; // 找到最小的合格因子即可
-                }
-            }
+    // 如果某个长度是 12，检查它的因子（如 6, 4, 3）是否也足够好。
+    // 如果因子的 IC 也达到期望值的九成，说明 12 只是倍数，因子才是真身。
+    let reduce = |len: usize| {
+        (1..len)
+            .filter(|f| len.is_multiple_of(*f))
+            .find(|f| {
+                candidates
+                    .iter()
+                    .any(|&(l, ic)| l == *f && ic > target_ic * 0.9)
+            })
+            .unwrap_or(len)
+    };
+    let mut lengths: Vec<usize> = Vec::new();
+    for &(len, _) in candidates.iter().take(CANDIDATE_LENGTHS) {
+        let len = reduce(len);
+        if !lengths.contains(&len) {
+            lengths.push(len);
         }
     }
-    best_len = reduced_len;
-
-    // 4. 确定密钥内容
-    let mut key_bytes = Vec::new();
-    for i in 0..best_len {
-        let group: Vec<u8> = clean_text
-            .iter()
-            .skip(i)
-            .step_by(best_len)
-            .cloned()
-            .collect();
-        let best_char = solve_caesar_shift(&group);
-        key_bytes.push(best_char);
-    }
 
-    let key = String::from_utf8(key_bytes).unwrap();
+    // 4. 对每个候选长度: 逐列卡方求位移, 再用四元组得分逐位爬山修正, 取得分最高者
+    let mut best: Option<(Vec<u8>, f64)> = None;
+    for len in lengths {
+        let (key, score) = solve_key(&model, &clean_text, len);
+        if best.as_ref().is_none_or(|(_, s)| score > *s + 1e-9) {
+            best = Some((key, score));
+        }
+    }
+    let (key, score) = best.ok_or("未找到候选密钥长度")?;
+    let key = shortest_period(&key);
+    let key_length = key.len();
+    let ic_score = (0..key_length)
+        .map(|i| index_of_coincidence(&column(&clean_text, i, key_length)))
+        .sum::<f64>()
+        / key_length as f64;
+
+    let key: String = key.iter().map(|&k| (b'A' + k) as char).collect();
     let decrypted = vigenere_cipher(ciphertext, key.clone(), "decrypt".into())?;
 
     Ok(CrackResult {
         key,
-        key_length: best_len,
+        key_length,
         plaintext: decrypted,
-        ic_score: best_ic,
+        ic_score,
+        score,
     })
 }
 
-fn calculate_ic(text: &[u8]) -> f64 {
-    let len = text.len();
-    if len <= 1 {
-        return 0.0;
-    }
-
-    let mut counts = [0usize; 26];
-    for &b in text {
-        if b >= b'A' && b <= b'Z' {
-            counts[(b - b'A') as usize] += 1;
-        }
-    }
-
-    let numerator: usize = counts.iter().map(|&n| n * (n.saturating_sub(1))).sum();
-    let denominator = len * (len - 1);
+fn column(text: &[u8], start: usize, step: usize) -> Vec<u8> {
+    text.iter().skip(start).step_by(step).copied().collect()
+}
 
-    numerator as f64 / denominator as f64
+fn decrypt_letters(text: &[u8], key: &[u8]) -> Vec<u8> {
+    text.iter()
+        .zip(key.iter().cycle())
+        .map(|(&c, &k)| (c + 26 - k) % 26)
+        .collect()
 }
 
-fn solve_caesar_shift(group: &[u8]) -> u8 {
-    let len = group.len() as f64;
-    let mut group_freq = [0.0; 26];
-    for &b in group {
-        if b >= b'A' && b <= b'Z' {
-            group_freq[(b - b'A') as usize] += 1.0;
+fn solve_key(model: &LanguageModel, text: &[u8], len: usize) -> (Vec<u8>, f64) {
+    let mut key: Vec<u8> = (0..len)
+        .map(|i| solve_caesar_shift(model, &column(text, i, len)))
+        .collect();
+    let fitness = |key: &[u8]| {
+        model
+            .ngram_score(&decrypt_letters(text, key), 4)
+            .unwrap_or(f64::MIN)
+    };
+    let mut score = fitness(&key);
+    for _ in 0..HILL_CLIMB_ROUNDS {
+        let mut improved = false;
+        for i in 0..len {
+            for shift in 0..26 {
+                let previous = key[i];
+                key[i] = shift;
+                let s = fitness(&key);
+                if s > score + 1e-9 {
+                    score = s;
+                    improved = true;
+                } else {
+                    key[i] = previous;
+                }
+            }
+        }
+        if !improved {
+            break;
         }
     }
-    for f in &mut group_freq {
-        *f /= len;
-    }
+    (key, score)
+}
 
-    let mut max_corr = 0.0;
-    let mut best_shift = 0;
+/// 列中每个字母减去 k 后与该语言单字母频率的卡方距离最小者即为位移
+fn solve_caesar_shift(model: &LanguageModel, group: &[u8]) -> u8 {
+    (0..26u8)
+        .min_by(|&a, &b| {
+            let chi = |k: u8| model.chi_squared(&decrypt_letters(group, &[k]));
+            chi(a).total_cmp(&chi(b))
+        })
+        .unwrap_or(0)
+}
 
-    for k in 0..26 {
-        let mut correlation = 0.0;
-        for c_idx in 0..26 {
-            let p_idx = (c_idx + 26 - k) % 26;
-            correlation += group_freq[c_idx] * ENGLISH_FREQ[p_idx];
-        }
-        if correlation > max_corr {
-            max_corr = correlation;
-            best_shift = k;
-        }
-    }
-    best_shift as u8 + b'A'
+/// 周期性的密钥 (如 CIPHERCIPHER) 收缩为最短周期
+fn shortest_period(key: &[u8]) -> Vec<u8> {
+    let period = (1..key.len())
+        .filter(|p| key.len().is_multiple_of(*p))
+        .find(|&p| key.iter().enumerate().all(|(i, &k)| k == key[i % p]))
+        .unwrap_or(key.len());
+    key[..period].to_vec()
 }
 
 #[cfg(test)]
@@ -233,7 +243,7 @@ mod tests {
         let ciphertext =
             vigenere_cipher(plaintext_long.into(), key.into(), "encrypt".into()).unwrap();
 
-        let result = crack_vigenere_auto(ciphertext).unwrap();
+        let result = crack_vigenere_auto(ciphertext, None).unwrap();
 
         println!("破解结果 Key: {}", result.key);
         println!("推测长度: {}", result.key_length);
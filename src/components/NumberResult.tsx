@@ -0,0 +1,51 @@
+import { Code, Tabs, Badge, Group } from '@mantine/core';
+import { IconFileText, IconHexagon, IconNumber, IconBinary, IconFlag } from '@tabler/icons-react';
+
+/** 与后端 codec::NumberView 对应的结果结构 */
+export interface NumberView {
+    decimal: string;
+    hex: string;
+    bytes: string;
+    utf8: string | null;
+    flag: string | null;
+}
+
+/**
+ * 大整数结果的统一展示: UTF-8 / Hex / 十进制 / bytes, 并高亮识别到的 flag
+ */
+export default function NumberResult({ value }: { value: NumberView }) {
+    return (
+        <>
+            {value.flag && (
+                <Group gap="xs" mb="xs">
+                    <IconFlag size={16} />
+                    <Badge variant="light" size="lg" style={{ textTransform: 'none' }}>
+                        {value.flag}
+                    </Badge>
+                </Group>
+            )}
+            <Tabs defaultValue={value.utf8 !== null ? 'text' : 'bytes'}>
+                <Tabs.List mb="xs">
+                    <Tabs.Tab value="text" leftSection={<IconFileText size={14} />} disabled={value.utf8 === null}>
+                        明文 (UTF-8)
+                    </Tabs.Tab>
+                    <Tabs.Tab value="bytes" leftSection={<IconBinary size={14} />}>字节 (bytes)</Tabs.Tab>
+                    <Tabs.Tab value="hex" leftSection={<IconHexagon size={14} />}>十六进制 (Hex)</Tabs.Tab>
+                    <Tabs.Tab value="decimal" leftSection={<IconNumber size={14} />}>十进制</Tabs.Tab>
+                </Tabs.List>
+                <Tabs.Panel value="text">
+                    <Code block style={{ whiteSpace: 'pre-wrap' }}>{value.utf8}</Code>
+                </Tabs.Panel>
+                <Tabs.Panel value="bytes">
+                    <Code block style={{ wordBreak: 'break-all' }}>{value.bytes}</Code>
+                </Tabs.Panel>
+                <Tabs.Panel value="hex">
+                    <Code block style={{ wordBreak: 'break-all' }}>{value.hex}</Code>
+                </Tabs.Panel>
+                <Tabs.Panel value="decimal">
+                    <Code block style={{ wordBreak: 'break-all' }}>{value.decimal}</Code>
+                </Tabs.Panel>
+            </Tabs>
+        </>
+    );
+}
@@ -1,14 +1,15 @@
 import { useState } from 'react';
-import { Paper, Stack, TextInput, Button, Text, Textarea, Code, Tabs, Group, ActionIcon } from '@mantine/core';
-import { IconLockOpen, IconHexagon, IconFileText, IconPlus, IconTrash } from '@tabler/icons-react';
+import { Paper, Stack, TextInput, Button, Text, Textarea, Group, ActionIcon } from '@mantine/core';
+import { IconLockOpen, IconPlus, IconTrash } from '@tabler/icons-react';
 import { invoke } from '@tauri-apps/api/core';
 import { handleAppError } from '../../utils/error';
+import NumberResult, { NumberView } from '../../components/NumberResult';
 
 export default function BigRSASolver() {
     const [nList, setNList] = useState<string[]>(['', '']); // 默认两层
     const [e, setE] = useState('65537');
     const [c, setC] = useState('');
-    const [hexResult, setHexResult] = useState('');
+    const [result, setResult] = useState<NumberView | null>(null);
     const [loading, setLoading] = useState(false);
 
     // 动态增删 N 输入框
@@ -28,14 +29,14 @@ export default function BigRSASolver() {
 
     const crackRSA = async () => {
         setLoading(true);
-        setHexResult('');
+        setResult(null);
         try {
-            const result = await invoke<string>('solve_multi_layer_rsa', {
+            const view = await invoke<NumberView>('solve_multi_layer_rsa', {
                 nList: nList.filter(n => n.trim() !== ''),
                 eStr: e,
                 cStr: c,
             });
-            setHexResult(result);
+            setResult(view);
         } catch (err) {
             handleAppError(err);
         } finally {
@@ -78,30 +79,11 @@ export default function BigRSASolver() {
                 </Stack>
             </Paper>
 
-            {hexResult && (
+            {result && (
                 <Paper withBorder p="md">
-                    <Tabs defaultValue="text">
-                        <Tabs.List mb="xs">
-                            <Tabs.Tab value="text" leftSection={<IconFileText size={14} />}>明文 (UTF-8)</Tabs.Tab>
-                            <Tabs.Tab value="hex" leftSection={<IconHexagon size={14} />}>十六进制 (Hex)</Tabs.Tab>
-                        </Tabs.List>
-                        <Tabs.Panel value="text">
-                            <Code block>{hexToUtf8(hexResult)}</Code>
-                        </Tabs.Panel>
-                        <Tabs.Panel value="hex">
-                            <Code block style={{ wordBreak: 'break-all' }}>{hexResult}</Code>
-                        </Tabs.Panel>
-                    </Tabs>
+                    <NumberResult value={result} />
                 </Paper>
             )}
         </Stack>
     );
 }
-
-function hexToUtf8(hex: string): string {
-    try {
-        const normalized = hex.length % 2 !== 0 ? '0' + hex : hex;
-        const bytes = new Uint8Array(normalized.match(/.{1,2}/g)!.map(b => parseInt(b, 16)));
-        return new TextDecoder().decode(bytes);
-    } catch { return "解码失败"; }
-}
\ No newline at end of file
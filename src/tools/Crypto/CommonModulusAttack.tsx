@@ -5,12 +5,13 @@ import {
     Textarea,
     Button,
     Group,
-    Code, Text,
+    Text,
 } from '@mantine/core';
 import { IconKey, IconShieldLock} from '@tabler/icons-react';
 import { showNotification } from '../../utils/notifications';
 import { handleAppError } from '../../utils/error';
 import { invoke } from '@tauri-apps/api/core';
+import NumberResult, { NumberView } from '../../components/NumberResult';
 
 /**
  * 共模攻击工具组件
@@ -22,18 +23,14 @@ export default function CommonModulusAttack() {
     const [c1, setC1] = useState<string>('');
     const [e2, setE2] = useState<string>('');
     const [c2, setC2] = useState<string>('');
-    const [result, setResult] = useState<string>('');
+    const [result, setResult] = useState<NumberView | null>(null);
     const [loading, setLoading] = useState<boolean>(false);
 
     const validateInputs = () => {
+        // 数字格式 (十进制 / 0x / Base64 / b'...') 由后端统一识别
         if (!n || !e1 || !c1 || !e2 || !c2) {
             throw new Error('所有字段均为必填项');
         }
-        // 简单校验是否为十六进制或十进制大整数
-        const hexOrDec = /^[0-9a-fA-F]+$/;
-        if (![n, e1, c1, e2, c2].every(v => hexOrDec.test(v))) {
-            throw new Error('请输入有效的十六进制或十进制整数');
-        }
     };
 
     const handleAttack = async () => {
@@ -41,7 +38,7 @@ export default function CommonModulusAttack() {
             setLoading(true);
             validateInputs();
 
-            const plaintext = await invoke<string>('common_modulus_attack', {
+            const plaintext = await invoke<NumberView>('common_modulus_attack', {
                 n: n.trim(),
                 e1: e1.trim(),
                 c1: c1.trim(),
@@ -148,9 +145,7 @@ export default function CommonModulusAttack() {
                             <IconKey size={20} />
                             <strong>恢复的明文</strong>
                         </Group>
-                        <Code block style={{ whiteSpace: 'pre-wrap', fontSize: '0.9em' }}>
-                            {result}
-                        </Code>
+                        <NumberResult value={result} />
                     </Stack>
                 </Paper>
             )}